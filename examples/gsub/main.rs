@@ -1,9 +1,9 @@
 use std::io::Read;
 
-use font_decoder::table::{is_ttc, Table};
+use font_decoder::{table::Table, FontFile};
 
 fn callback(table: &Table) {
-    if let Some(gsub) = table.get_gsub_table() {
+    if let Ok(gsub) = table.get_gsub_table() {
         for feature_record in gsub.feature_list.featureRecords {
             println!("{}", feature_record.featureTag);
         }
@@ -17,10 +17,9 @@ fn main() {
         let mut file = std::fs::File::open(filepath).unwrap();
         let mut buf = vec![];
         file.read_to_end(&mut buf).unwrap();
-        if is_ttc(&buf) {
-        } else {
-            callback(&Table::new(&buf).unwrap());
+        let font_file = FontFile::from_bytes(&buf).unwrap();
+        for (_, table) in &font_file {
+            callback(&table.unwrap());
         }
-    } else {
     }
 }
@@ -0,0 +1,218 @@
+// glyph の輪郭から bbox を求め，glyf の header と head に書かれた値と比べる．
+// control box は制御点も含めた範囲，tight bbox は曲線の極値まで求めた実際に塗られる範囲．
+use crate::{error::Result, font::Font, glyf::BBox, outline::OutlinePen};
+
+// 輪郭を受け取って control box と tight bbox を求める pen．
+#[derive(Debug, Clone, Default)]
+pub struct BBoxPen {
+    control_box: BBox,
+    tight_box: BBox,
+    current: (f64, f64),
+}
+
+impl BBoxPen {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 何も描かれていない場合は空の bbox を返す．
+    pub fn control_box(&self) -> BBox {
+        self.control_box
+    }
+
+    pub fn tight_box(&self) -> BBox {
+        self.tight_box
+    }
+
+    fn end_point(&mut self, x: f64, y: f64) {
+        self.control_box.update(x, y);
+        self.tight_box.update(x, y);
+        self.current = (x, y);
+    }
+}
+
+// 2 次ベジエ曲線の 1 軸の成分が極値になる t．(0, 1) の外にある場合は None を返す．
+fn quad_extremum(p0: f64, p1: f64, p2: f64) -> Option<f64> {
+    let denominator = p0 - 2.0 * p1 + p2;
+    if denominator == 0.0 {
+        return None;
+    }
+    let t = (p0 - p1) / denominator;
+    (t > 0.0 && t < 1.0).then_some(t)
+}
+
+// 3 次ベジエ曲線の 1 軸の成分が極値になる t．導関数 a t^2 + b t + c = 0 の (0, 1) にある解．
+fn cubic_extrema(p0: f64, p1: f64, p2: f64, p3: f64) -> [Option<f64>; 2] {
+    let a = -p0 + 3.0 * (p1 - p2) + p3;
+    let b = 2.0 * (p0 - 2.0 * p1 + p2);
+    let c = p1 - p0;
+    let in_range = |t: f64| (t > 0.0 && t < 1.0).then_some(t);
+    if a.abs() < 1e-12 {
+        if b == 0.0 {
+            return [None, None];
+        }
+        return [in_range(-c / b), None];
+    }
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return [None, None];
+    }
+    let root = discriminant.sqrt();
+    [
+        in_range((-b + root) / (2.0 * a)),
+        in_range((-b - root) / (2.0 * a)),
+    ]
+}
+
+impl OutlinePen for BBoxPen {
+    fn move_to(&mut self, x: f64, y: f64) {
+        self.end_point(x, y);
+    }
+
+    fn line_to(&mut self, x: f64, y: f64) {
+        self.end_point(x, y);
+    }
+
+    fn quad_to(&mut self, x1: f64, y1: f64, x: f64, y: f64) {
+        let (x0, y0) = self.current;
+        self.control_box.update(x1, y1);
+        for t in [quad_extremum(x0, x1, x), quad_extremum(y0, y1, y)]
+            .into_iter()
+            .flatten()
+        {
+            let u = 1.0 - t;
+            self.tight_box.update(
+                u * u * x0 + 2.0 * u * t * x1 + t * t * x,
+                u * u * y0 + 2.0 * u * t * y1 + t * t * y,
+            );
+        }
+        self.end_point(x, y);
+    }
+
+    fn curve_to(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, x: f64, y: f64) {
+        let (x0, y0) = self.current;
+        self.control_box.update(x1, y1);
+        self.control_box.update(x2, y2);
+        let [tx1, tx2] = cubic_extrema(x0, x1, x2, x);
+        let [ty1, ty2] = cubic_extrema(y0, y1, y2, y);
+        for t in [tx1, tx2, ty1, ty2].into_iter().flatten() {
+            let u = 1.0 - t;
+            let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+            self.tight_box.update(
+                a * x0 + b * x1 + c * x2 + d * x,
+                a * y0 + b * y1 + c * y2 + d * y,
+            );
+        }
+        self.end_point(x, y);
+    }
+
+    fn close(&mut self) {}
+}
+
+// fontTools と同じく，0.5 は切り上げて整数にする．空の bbox は (0, 0, 0, 0) にする．
+fn round_bbox(bbox: &BBox) -> BBox {
+    if bbox.is_empty() {
+        return BBox {
+            xmin: 0.0,
+            ymin: 0.0,
+            xmax: 0.0,
+            ymax: 0.0,
+        };
+    }
+    let round = |v: f64| (v + 0.5).floor();
+    BBox {
+        xmin: round(bbox.xmin),
+        ymin: round(bbox.ymin),
+        xmax: round(bbox.xmax),
+        ymax: round(bbox.ymax),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlyphBBoxCheck {
+    pub glyph_id: u16,
+    pub stored: Result<BBox>, // glyf の header の値．glyph を読めない場合はエラー．
+    pub computed: Result<BBox>, // 展開した点の control box を整数に丸めたもの．composite glyph を展開できない場合はエラー．
+}
+
+impl GlyphBBoxCheck {
+    pub fn is_valid(&self) -> bool {
+        matches!((self.stored, self.computed), (Ok(stored), Ok(computed)) if stored == computed)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeadBBoxCheck {
+    pub stored: BBox,
+    pub computed: BBox, // 輪郭を持つ glyph の control box を合わせたもの．
+}
+
+impl HeadBBoxCheck {
+    pub fn is_valid(&self) -> bool {
+        self.stored == self.computed
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BBoxReport {
+    pub glyphs: Vec<GlyphBBoxCheck>, // glyf にデータを持つ glyph だけを並べる．
+    // head が無い場合や，読めない glyph がある場合はエラー．
+    pub head: Result<HeadBBoxCheck>,
+}
+
+impl BBoxReport {
+    pub fn is_valid(&self) -> bool {
+        self.glyphs.iter().all(GlyphBBoxCheck::is_valid)
+            && self.head.is_ok_and(|head| head.is_valid())
+    }
+
+    pub fn mismatched_glyphs(&self) -> impl Iterator<Item = &GlyphBBoxCheck> {
+        self.glyphs.iter().filter(|glyph| !glyph.is_valid())
+    }
+}
+
+// 各 glyph の header と head の bbox を，輪郭から求めた control box と比べる．
+// 仕様上 header の値は点の座標の範囲なので，tight bbox ではなく control box を使う．
+pub fn verify_bboxes(font: &Font) -> Result<BBoxReport> {
+    let mut glyphs = vec![];
+    let mut union: Result<BBox> = Ok(BBox::default());
+    for glyph_id in 0..font.number_of_glyphs()? {
+        let check = match font.glyph(glyph_id) {
+            Ok(None) => continue,
+            Ok(Some(glyph)) => {
+                let header = &glyph.header;
+                let stored = BBox {
+                    xmin: f64::from(header.xMin),
+                    ymin: f64::from(header.yMin),
+                    xmax: f64::from(header.xMax),
+                    ymax: f64::from(header.yMax),
+                };
+                let computed = font.resolve_glyph(glyph_id).map(|resolved| resolved.bbox);
+                if let (Ok(bbox), Ok(total)) = (computed, &mut union) {
+                    *total = total.union(&bbox);
+                }
+                GlyphBBoxCheck {
+                    glyph_id,
+                    stored: Ok(stored),
+                    computed: computed.map(|bbox| round_bbox(&bbox)),
+                }
+            }
+            Err(e) => GlyphBBoxCheck {
+                glyph_id,
+                stored: Err(e),
+                computed: Err(e),
+            },
+        };
+        if let (Err(e), Ok(_)) = (check.computed, &union) {
+            union = Err(e);
+        }
+        glyphs.push(check);
+    }
+    let head = font.head().and_then(|head| {
+        Ok(HeadBBoxCheck {
+            stored: head.get_bbox(),
+            computed: round_bbox(&union?),
+        })
+    });
+    Ok(BBoxReport { glyphs, head })
+}
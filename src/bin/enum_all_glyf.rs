@@ -1,42 +1,33 @@
 use std::{fs::File, io::Read};
 
-use font_decoder::{
-    glyf::Glyph,
-    table::{is_ttc, Collection, Table},
-};
+use font_decoder::{cmap::Charmap, glyf::Glyph, table::Table, FontFile};
 
 fn callback(table: &Table) {
     let glyf = table.get_glyf_table().unwrap();
-    let cmap = table.get_cmap_table();
-    let maxp = table.get_maxp_table();
-    let head = table.get_head_table();
-    let format = head.get_loca_offset_format();
+    let cmap = table.get_cmap_table().unwrap();
+    let maxp = table.get_maxp_table().unwrap();
+    let head = table.get_head_table().unwrap();
+    let format = head.get_loca_offset_format().unwrap();
     let num_glyphs = maxp.get_number_of_glyphs();
     let loca = table.get_loca_table(format, num_glyphs).unwrap();
+    let post = table.get_post_table().ok();
 
-    for item in &cmap.header.encodingRecords {
-        match cmap.get_subtable(&item) {
-            Some(subtable) => {
-                let map = subtable.get_code_point_glyph_id_map();
-                for (c, glyph_id) in map {
-                    dbg!(c);
-                    dbg!(glyph_id);
-                    if let Some(range) = loca.get_glyf_range(glyph_id) {
-                        dbg!(&range);
-                        let data = glyf.get_data(range).unwrap();
-                        let glyph = Glyph::parse(data).unwrap();
-                        let points = glyph.get_points(&loca, &glyf);
-                        dbg!(&points);
-                        // break;
-                    } else {
-                        // glyph range が存在しない文字もある．
-                    }
-                }
-                // cmap subtable は一つだけ列挙する．
-                return;
+    if let Some(charmap) = Charmap::new(&cmap) {
+        for (c, glyph_id) in charmap.iter() {
+            dbg!(c);
+            dbg!(glyph_id);
+            if let Some(name) = post.as_ref().and_then(|post| post.glyph_name(glyph_id)) {
+                dbg!(name);
             }
-            None => {
-                panic!("cmap subtable が存在しないはずがない．");
+            if let Some(range) = loca.get_glyf_range(glyph_id) {
+                dbg!(&range);
+                let data = glyf.get_data(range).unwrap();
+                let glyph = Glyph::parse(data).unwrap();
+                let points = glyph.get_points(&loca, &glyf);
+                dbg!(&points);
+                // break;
+            } else {
+                // glyph range が存在しない文字もある．
             }
         }
     }
@@ -50,13 +41,9 @@ fn main() {
         let mut buffer = Vec::new();
         file.read_to_end(&mut buffer).unwrap();
 
-        if is_ttc(&buffer) {
-            let collection = Collection::new(&buffer).unwrap();
-            for i in 0..collection.header.numFonts as usize {
-                callback(&collection.get(i).unwrap())
-            }
-        } else {
-            callback(&Table::new(&buffer).unwrap())
+        let font_file = FontFile::from_bytes(&buffer).unwrap();
+        for (_, table) in &font_file {
+            callback(&table.unwrap())
         }
     } else {
         println!("filepath is necessary")
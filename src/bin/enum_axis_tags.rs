@@ -1,14 +1,11 @@
 use std::io::Read;
 
-use font_decoder::{
-    name::NameTableIterItem,
-    table::{is_ttc, Table},
-};
+use font_decoder::{name::NameTableIterItem, table::Table, FontFile};
 
 fn callback(table: &Table) -> Option<()> {
-    let fvar = table.get_fvar_table()?;
-    let names: Vec<NameTableIterItem> = table.get_name_table().into_iter().collect();
-    for (i, axis) in (&fvar.axes).into_iter().enumerate() {
+    let fvar = table.get_fvar_table().ok()?;
+    let names: Vec<NameTableIterItem> = table.get_name_table().ok()?.into_iter().collect();
+    for (i, axis) in fvar.axes.iter().enumerate() {
         dbg!(i);
         dbg!(axis.axisTag);
         let mut it = names.iter();
@@ -31,10 +28,9 @@ fn main() {
         let mut file = std::fs::File::open(filepath).unwrap();
         let mut buf = vec![];
         file.read_to_end(&mut buf).unwrap();
-        if is_ttc(&buf) {
-        } else {
-            callback(&Table::new(&buf).unwrap());
+        let font_file = FontFile::from_bytes(&buf).unwrap();
+        for (_, table) in &font_file {
+            callback(&table.unwrap());
         }
-    } else {
     }
 }
@@ -0,0 +1,40 @@
+use std::{fs::File, io::Read};
+
+use font_decoder::{bbox::verify_bboxes, table::Table, Font, FontFile};
+
+fn callback(table: Table) {
+    let font = Font::from_table(table);
+    let report = verify_bboxes(&font).unwrap();
+    for glyph in report.mismatched_glyphs() {
+        println!(
+            "{}: stored = {:?}, computed = {:?}, tight = {:?}",
+            glyph.glyph_id,
+            glyph.stored,
+            glyph.computed,
+            font.tight_bbox(glyph.glyph_id)
+        );
+    }
+    println!("head: {:?}", report.head);
+    println!(
+        "{} / {} glyphs are valid",
+        report.glyphs.len() - report.mismatched_glyphs().count(),
+        report.glyphs.len()
+    );
+}
+
+// cargo run --bin enum_bbox
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(filepath) = args.get(1) {
+        let mut file = File::open(filepath).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+
+        let font_file = FontFile::from_bytes(&buffer).unwrap();
+        for (_, table) in &font_file {
+            callback(table.unwrap())
+        }
+    } else {
+        println!("filepath is necessary")
+    }
+}
@@ -1,6 +1,6 @@
 use std::io::Read;
 
-use font_decoder::table::Table;
+use font_decoder::{cmap::CmapSubtable, table::Table};
 
 // `cargo run --bin enum_cmap`
 fn main() {
@@ -10,24 +10,37 @@ fn main() {
     let mut buf = vec![];
     file.read_to_end(&mut buf).unwrap();
     let table = Table::new(&buf).unwrap();
-    let cmap = table.get_cmap_table();
+    let cmap = table.get_cmap_table().unwrap();
     dbg!(&cmap.header);
     for item in &cmap.header.encodingRecords {
         dbg!(&item);
-        match cmap.get_subtable(&item) {
-            Some(subtable) => {
-                let map = subtable.get_code_point_glyph_id_map();
-                let mut map: Vec<(char, u16)> = map.into_iter().collect();
-                dbg!(map.len());
-                map.sort_by(|a, b| a.0.cmp(&b.0));
-                for (code_point, glyph_id) in map {
-                    println!(
-                        "U+{:06X} ('{:?}') => {}",
-                        code_point as u32, code_point, glyph_id
-                    );
+        if let Ok(subtable) = cmap.get_subtable(item) {
+            dbg!(subtable.get_format());
+            if let CmapSubtable::Format14(uvs) = &subtable {
+                for record in &uvs.varSelector {
+                    println!("selector U+{:06X}", record.varSelector.0);
+                    if let Ok(Some(table)) = uvs.get_default_uvs_table(&record) {
+                        for range in &table.ranges {
+                            let start = range.startUnicodeValue.0;
+                            let end = start + range.additionalCount as u32;
+                            println!("  U+{:06X}..=U+{:06X} => default", start, end);
+                        }
+                    }
+                    if let Ok(Some(table)) = uvs.get_non_default_uvs_table(&record) {
+                        for mapping in &table.uvsMappings {
+                            println!("  U+{:06X} => {}", mapping.unicodeValue.0, mapping.glyphID);
+                        }
+                    }
                 }
             }
-            None => {}
+            for (range, glyph_id) in subtable.ranges() {
+                println!(
+                    "U+{:06X}..=U+{:06X} => {}",
+                    range.start(),
+                    range.end(),
+                    glyph_id
+                );
+            }
         }
     }
 }
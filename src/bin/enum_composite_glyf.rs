@@ -1,43 +1,37 @@
 use std::{fs::File, io::Read};
 
 use font_decoder::{
+    cmap::Charmap,
     glyf::{Glyph, GlyphTable},
-    table::{is_ttc, Collection, Table},
+    table::Table,
+    FontFile,
 };
 
 fn callback(table: &Table) {
     let glyf = table.get_glyf_table().unwrap();
-    let cmap = table.get_cmap_table();
-    let maxp = table.get_maxp_table();
-    let head = table.get_head_table();
-    let format = head.get_loca_offset_format();
+    let cmap = table.get_cmap_table().unwrap();
+    let maxp = table.get_maxp_table().unwrap();
+    let head = table.get_head_table().unwrap();
+    let format = head.get_loca_offset_format().unwrap();
     let num_glyphs = maxp.get_number_of_glyphs();
     let loca = table.get_loca_table(format, num_glyphs).unwrap();
 
-    for item in &cmap.header.encodingRecords {
-        match cmap.get_subtable(&item) {
-            Some(subtable) => {
-                let code_point = 'š';
-                println!("U+{:x}", code_point as u32);
-                let glyph_id = subtable.get_glyph_id(code_point);
-                dbg!(glyph_id);
-                if let Some(glyph_id) = glyph_id {
-                    if let Some(range) = loca.get_glyf_range(glyph_id) {
-                        let data = glyf.get_data(range).unwrap();
-                        let glyph = Glyph::parse(data).unwrap();
-                        match &glyph.subtable {
-                            GlyphTable::Simple(_table) => {}
-                            GlyphTable::Composite(table) => {
-                                dbg!(&table.components);
-                            }
-                        }
-                        let points = glyph.get_points(&loca, &glyf);
-                        dbg!(&points);
-                        break;
-                    }
+    if let Some(charmap) = Charmap::new(&cmap) {
+        let code_point = 'š';
+        println!("U+{:x}", code_point as u32);
+        let glyph_id = charmap.map(code_point);
+        dbg!(glyph_id);
+        if let Some(range) = glyph_id.and_then(|glyph_id| loca.get_glyf_range(glyph_id)) {
+            let data = glyf.get_data(range).unwrap();
+            let glyph = Glyph::parse(data).unwrap();
+            match &glyph.subtable {
+                GlyphTable::Simple(_table) => {}
+                GlyphTable::Composite(table) => {
+                    dbg!(&table.components);
                 }
             }
-            None => {}
+            let points = glyph.get_points(&loca, &glyf);
+            dbg!(&points);
         }
     }
 }
@@ -50,13 +44,9 @@ fn main() {
         let mut buffer = Vec::new();
         file.read_to_end(&mut buffer).unwrap();
 
-        if is_ttc(&buffer) {
-            let collection = Collection::new(&buffer).unwrap();
-            for i in 0..collection.header.numFonts as usize {
-                callback(&collection.get(i).unwrap())
-            }
-        } else {
-            callback(&Table::new(&buffer).unwrap())
+        let font_file = FontFile::from_bytes(&buffer).unwrap();
+        for (_, table) in &font_file {
+            callback(&table.unwrap())
         }
     } else {
         println!("filepath is necessary")
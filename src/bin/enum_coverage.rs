@@ -0,0 +1,72 @@
+use std::io::Read;
+
+use font_decoder::{
+    coverage::{CharacterSet, Coverage},
+    table::Table,
+    Font, FontFile,
+};
+
+fn callback(table: Table, sets: &[CharacterSet]) {
+    let font = Font::from_table(table);
+    let Some(charmap) = font.charmap() else {
+        println!("no Unicode cmap");
+        return;
+    };
+    let coverage = Coverage::from_charmap(charmap);
+    println!("{} characters", coverage.len());
+
+    println!("blocks:");
+    for count in coverage.blocks() {
+        println!("  {}: {}/{}", count.name, count.covered, count.total);
+    }
+    println!("scripts:");
+    for count in coverage.scripts() {
+        println!("  {}: {}/{}", count.name, count.covered, count.total);
+    }
+
+    if !sets.is_empty() {
+        println!("character sets:");
+        for set in sets {
+            let result = coverage.compare(set);
+            println!("  {}: {}/{}", result.name, result.covered, result.total);
+            for c in result.missing {
+                println!("    missing U+{:04X} {}", c as u32, c);
+            }
+        }
+    }
+
+    if let Ok(os2) = font.os2() {
+        println!("OS/2:");
+        for issue in coverage.check_os2(os2) {
+            println!("  {}", issue);
+        }
+    }
+}
+
+// `cargo run --bin enum_coverage <font> [character sets]`
+// collection の場合は face ごとに出力する．
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let Some(path) = args.get(1) else {
+        println!("filepath is necessary");
+        return;
+    };
+    let mut file = std::fs::File::open(path).unwrap();
+    let mut buf = vec![];
+    file.read_to_end(&mut buf).unwrap();
+    let sets = match args.get(2) {
+        Some(path) => CharacterSet::read_sets(path).unwrap(),
+        None => vec![],
+    };
+
+    let font_file = FontFile::from_bytes(&buf).unwrap();
+    for (index, table) in &font_file {
+        if font_file.is_collection() {
+            println!("face {}:", index);
+        }
+        match table {
+            Ok(table) => callback(table, &sets),
+            Err(e) => println!("{}", e),
+        }
+    }
+}
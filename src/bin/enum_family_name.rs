@@ -1,10 +1,6 @@
 use std::{fs::File, io::Read};
 
-use font_decoder::{
-    data_types::NAME,
-    name::NameTable,
-    table::{is_ttc, Collection, Table},
-};
+use font_decoder::{data_types::NAME, name::NameTable, table::Table, FontFile};
 
 fn callback(table: &Table) {
     let name = table.get_table_data(&NAME).unwrap();
@@ -28,13 +24,9 @@ fn main() {
         let mut buffer = Vec::new();
         file.read_to_end(&mut buffer).unwrap();
 
-        if is_ttc(&buffer) {
-            let collection = Collection::new(&buffer).unwrap();
-            for i in 0..collection.header.numFonts as usize {
-                callback(&collection.get(i).unwrap())
-            }
-        } else {
-            callback(&Table::new(&buffer).unwrap())
+        let font_file = FontFile::from_bytes(&buffer).unwrap();
+        for (_, table) in &font_file {
+            callback(&table.unwrap())
         }
     } else {
         println!("filepath is necessary")
@@ -1,27 +1,30 @@
 use std::{fs::File, io::Read};
 
-use font_decoder::{data_types::FVAR, fvar::FvarTable, id::NameID, table::Table};
+use font_decoder::{data_types::FVAR, fvar::FvarTable, id::NameID, table::Table, FontFile};
 
 fn callback(table: &Table) {
     let fvar = table.get_table_data(&FVAR);
-    let name = table.get_name_table();
-    match fvar {
-        Some(fvar) => {
-            let fvar = FvarTable::parse(fvar).unwrap();
-            for (i, axis) in fvar.axes.into_iter().enumerate() {
-                dbg!(i, &axis);
-                for string in name.get_strings_by_name_id(NameID(axis.axisNameId)) {
-                    println!("{}", string);
-                }
+    let name = table.get_name_table().unwrap();
+    if let Ok(fvar) = fvar {
+        let fvar = FvarTable::parse(fvar).unwrap();
+        for (i, axis) in fvar.axes.into_iter().enumerate() {
+            dbg!(i, &axis);
+            for string in name
+                .get_strings_by_name_id(NameID(axis.axisNameId))
+                .unwrap()
+            {
+                println!("{}", string);
             }
-            for (i, instance) in fvar.instances.into_iter().enumerate() {
-                dbg!(i, &instance);
-                for string in name.get_strings_by_name_id(NameID(instance.subfamilyNameId)) {
-                    println!("{}", string);
-                }
+        }
+        for (i, instance) in fvar.instances.into_iter().enumerate() {
+            dbg!(i, &instance);
+            for string in name
+                .get_strings_by_name_id(NameID(instance.subfamilyNameId))
+                .unwrap()
+            {
+                println!("{}", string);
             }
         }
-        None => {}
     }
 }
 
@@ -32,7 +35,10 @@ fn main() {
         let mut buffer = Vec::new();
         file.read_to_end(&mut buffer).unwrap();
 
-        callback(&Table::new(&buffer).unwrap());
+        let font_file = FontFile::from_bytes(&buffer).unwrap();
+        for (_, table) in &font_file {
+            callback(&table.unwrap());
+        }
     } else {
         println!("filepath is required");
     }
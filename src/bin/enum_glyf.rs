@@ -1,39 +1,16 @@
 use std::{fs::File, io::Read};
 
-use font_decoder::{
-    glyf::Glyph,
-    table::{is_ttc, Collection, Table},
-};
+use font_decoder::{table::Table, Font, FontFile};
 
-fn callback(table: &Table) {
-    let glyf = table.get_glyf_table().unwrap();
-    let cmap = table.get_cmap_table();
-    let maxp = table.get_maxp_table();
-    let head = table.get_head_table();
-    let format = head.get_loca_offset_format();
-    let num_glyphs = maxp.get_number_of_glyphs();
-    let loca = table.get_loca_table(format, num_glyphs).unwrap();
-
-    for item in &cmap.header.encodingRecords {
-        match cmap.get_subtable(&item) {
-            Some(subtable) => {
-                let glyph_id = subtable.get_glyph_id('L');
-                if let Some(glyph_id) = glyph_id {
-                    if let Some(range) = loca.get_glyf_range(glyph_id) {
-                        let data = glyf.get_data(range).unwrap();
-                        let glyph = Glyph::parse(data).unwrap();
-                        let points = glyph.get_points(&loca, &glyf);
-                        dbg!(&points);
-                        // break;
-                    }
-                }
-            }
-            None => {}
-        }
+fn callback(table: Table) {
+    let font = Font::from_table(table);
+    if let Some(glyph_id) = font.glyph_index('L') {
+        let points = font.outline(glyph_id);
+        dbg!(&points);
     }
 }
 
-// cargo run --bin enum_names
+// cargo run --bin enum_glyf
 fn main() {
     let args: Vec<String> = std::env::args().collect();
     if let Some(filepath) = args.get(1) {
@@ -41,13 +18,9 @@ fn main() {
         let mut buffer = Vec::new();
         file.read_to_end(&mut buffer).unwrap();
 
-        if is_ttc(&buffer) {
-            let collection = Collection::new(&buffer).unwrap();
-            for i in 0..collection.header.numFonts as usize {
-                callback(&collection.get(i).unwrap())
-            }
-        } else {
-            callback(&Table::new(&buffer).unwrap())
+        let font_file = FontFile::from_bytes(&buffer).unwrap();
+        for (_, table) in &font_file {
+            callback(table.unwrap())
         }
     } else {
         println!("filepath is necessary")
@@ -0,0 +1,51 @@
+use std::{fs, path::Path};
+
+use font_decoder::{raster::rasterize_glyph, Font};
+
+// `cargo run --bin enum_glyph_bitmap <font> <output dir> [--size <px>] [--pgm] [text]`
+// text の文字の glyph を <glyph id>.png (--pgm なら .pgm) に書き出す．text を省略するとすべての glyph を書き出す．
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let data = fs::read(&args[1]).unwrap();
+    let output = Path::new(&args[2]);
+    fs::create_dir_all(output).unwrap();
+
+    let mut pixel_size = 64.0;
+    let mut pgm = false;
+    let mut text = None;
+    let mut rest = args[3..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--size" => pixel_size = rest.next().unwrap().parse().unwrap(),
+            "--pgm" => pgm = true,
+            _ => text = Some(arg.clone()),
+        }
+    }
+
+    let font = Font::new(&data).unwrap();
+    let glyph_ids: Vec<u16> = match text {
+        Some(text) => text.chars().filter_map(|c| font.glyph_index(c)).collect(),
+        None => (0..font.number_of_glyphs().unwrap()).collect(),
+    };
+    for glyph_id in glyph_ids {
+        let bitmap = match rasterize_glyph(&font, glyph_id, pixel_size, (0.0, 0.0)) {
+            Ok(bitmap) => bitmap,
+            Err(e) => {
+                eprintln!("glyph {}: {}", glyph_id, e);
+                continue;
+            }
+        };
+        println!(
+            "glyph {}: {}x{} left={} top={}",
+            glyph_id, bitmap.width, bitmap.height, bitmap.left, bitmap.top
+        );
+        let extension = if pgm { "pgm" } else { "png" };
+        let mut file =
+            fs::File::create(output.join(format!("{}.{}", glyph_id, extension))).unwrap();
+        if pgm {
+            bitmap.write_pgm(&mut file).unwrap();
+        } else {
+            bitmap.write_png(&mut file).unwrap();
+        }
+    }
+}
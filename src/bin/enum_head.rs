@@ -1,9 +1,9 @@
 use std::{fs::File, io::Read};
 
-use font_decoder::table::{is_ttc, Collection, Table};
+use font_decoder::{table::Table, FontFile};
 
 fn callback(table: &Table) {
-    let head = table.get_head_table();
+    let head = table.get_head_table().unwrap();
     dbg!(head);
 }
 
@@ -15,13 +15,9 @@ fn main() {
         let mut buffer = Vec::new();
         file.read_to_end(&mut buffer).unwrap();
 
-        if is_ttc(&buffer) {
-            let collection = Collection::new(&buffer).unwrap();
-            for i in 0..collection.header.numFonts as usize {
-                callback(&collection.get(i).unwrap())
-            }
-        } else {
-            callback(&Table::new(&buffer).unwrap())
+        let font_file = FontFile::from_bytes(&buffer).unwrap();
+        for (_, table) in &font_file {
+            callback(&table.unwrap())
         }
     } else {
         println!("filepath is necessary")
@@ -0,0 +1,37 @@
+use std::{fs::File, io::Read};
+
+use font_decoder::{table::Table, FontFile};
+
+fn callback(table: &Table) {
+    let hhea = table.get_hhea_table().unwrap();
+    dbg!(&hhea);
+    let num_glyphs = table.get_maxp_table().unwrap().get_number_of_glyphs();
+    let hmtx = table
+        .get_hmtx_table(hhea.numberOfHMetrics, num_glyphs)
+        .unwrap();
+    for glyph_id in 0..num_glyphs {
+        if let Some(metric) = hmtx.glyph_h_metrics(glyph_id) {
+            println!(
+                "{}: advanceWidth = {}, lsb = {}",
+                glyph_id, metric.advanceWidth, metric.lsb
+            );
+        }
+    }
+}
+
+// cargo run --bin enum_hmtx
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(filepath) = args.get(1) {
+        let mut file = File::open(filepath).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+
+        let font_file = FontFile::from_bytes(&buffer).unwrap();
+        for (_, table) in &font_file {
+            callback(&table.unwrap())
+        }
+    } else {
+        println!("filepath is necessary")
+    }
+}
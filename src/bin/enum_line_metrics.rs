@@ -0,0 +1,56 @@
+use std::{fs::File, io::Read};
+
+use font_decoder::{
+    line_metrics::{line_metrics, LineMetricsConvention},
+    table::Table,
+    Font, FontFile,
+};
+
+fn callback(table: Table) {
+    let font = Font::from_table(table);
+    if let Ok(os2) = font.os2() {
+        println!("USE_TYPO_METRICS = {}", os2.use_typo_metrics());
+    }
+    // 可変フォントでは各軸の最小，既定，最大の位置の値も出す．
+    let mut coords = vec![vec![]];
+    if let Ok(fvar) = font.table().get_fvar_table() {
+        for value in [-1.0, 1.0] {
+            coords.push(vec![value; fvar.axes.len()]);
+        }
+    }
+    for convention in [
+        LineMetricsConvention::Windows,
+        LineMetricsConvention::Mac,
+        LineMetricsConvention::Css,
+    ] {
+        for coords in &coords {
+            match line_metrics(&font, convention, coords) {
+                Ok(metrics) => println!(
+                    "{:?} {:?}: {:?}, line height = {}",
+                    convention,
+                    coords,
+                    metrics,
+                    metrics.line_height()
+                ),
+                Err(e) => println!("{:?} {:?}: {}", convention, coords, e),
+            }
+        }
+    }
+}
+
+// cargo run --bin enum_line_metrics
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(filepath) = args.get(1) {
+        let mut file = File::open(filepath).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+
+        let font_file = FontFile::from_bytes(&buffer).unwrap();
+        for (_, table) in &font_file {
+            callback(table.unwrap())
+        }
+    } else {
+        println!("filepath is necessary")
+    }
+}
@@ -1,16 +1,13 @@
 use std::{fs::File, io::Read};
 
-use font_decoder::{
-    loca::LocaTable,
-    table::{is_ttc, Collection, Table},
-};
+use font_decoder::{loca::LocaTable, table::Table, FontFile};
 
 fn callback(table: &Table) {
     let glyf = table.get_glyf_table().unwrap();
     println!("glyf tableの長さ {}", glyf.0.len());
-    let maxp = table.get_maxp_table();
-    let head = table.get_head_table();
-    let format = head.get_loca_offset_format();
+    let maxp = table.get_maxp_table().unwrap();
+    let head = table.get_head_table().unwrap();
+    let format = head.get_loca_offset_format().unwrap();
     let num_glyphs = maxp.get_number_of_glyphs();
     let loca = table.get_loca_table(format, num_glyphs).unwrap();
     match &loca {
@@ -21,7 +18,7 @@ fn callback(table: &Table) {
         println!(
             "glyph id [{}], offset = [{}], range = [{:?}]",
             i,
-            loca.at(i),
+            loca.at(i).unwrap(),
             loca.get_glyf_range(i as u16)
         );
     }
@@ -35,13 +32,9 @@ fn main() {
         let mut buffer = Vec::new();
         file.read_to_end(&mut buffer).unwrap();
 
-        if is_ttc(&buffer) {
-            let collection = Collection::new(&buffer).unwrap();
-            for i in 0..collection.header.numFonts as usize {
-                callback(&collection.get(i).unwrap())
-            }
-        } else {
-            callback(&Table::new(&buffer).unwrap())
+        let font_file = FontFile::from_bytes(&buffer).unwrap();
+        for (_, table) in &font_file {
+            callback(&table.unwrap())
         }
     } else {
         println!("filepath is necessary")
@@ -1,11 +1,11 @@
 use std::io::Read;
 
-use font_decoder::table::{is_ttc, Table};
+use font_decoder::{table::Table, FontFile};
 
 fn callback(table: &Table) -> Option<()> {
     let names: Vec<font_decoder::name::NameTableIterItem> =
-        table.get_name_table().into_iter().collect();
-    let fvar = table.get_fvar_table()?;
+        table.get_name_table().ok()?.into_iter().collect();
+    let fvar = table.get_fvar_table().ok()?;
     for instance in &fvar.instances {
         dbg!(&instance);
         let mut it = names.iter();
@@ -31,9 +31,9 @@ fn main() {
         let mut buf = Vec::new();
         file.read_to_end(&mut buf).unwrap();
 
-        if is_ttc(&buf) {
-        } else {
-            callback(&Table::new(&buf).unwrap()).unwrap();
+        let font_file = FontFile::from_bytes(&buf).unwrap();
+        for (_, table) in &font_file {
+            callback(&table.unwrap()).unwrap();
         }
     } else {
         println!("filepath is necessary");
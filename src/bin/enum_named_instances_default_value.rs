@@ -5,25 +5,22 @@ use std::io::Read;
 use font_decoder::{
     data_types::{Fixed, Tag},
     name::NameTableIterItem,
-    table::{is_ttc, Table},
+    table::Table,
+    FontFile,
 };
 
 fn callback(table: &Table) -> Option<()> {
-    let names: Vec<NameTableIterItem> = table.get_name_table().into_iter().collect();
-    let fvar = table.get_fvar_table()?;
+    let names: Vec<NameTableIterItem> = table.get_name_table().ok()?.into_iter().collect();
+    let fvar = table.get_fvar_table().ok()?;
     let into_iter = fvar.axes.into_iter();
     let default_values: Vec<(Fixed, Tag)> =
         into_iter.map(|x| (x.defaultValue, x.axisTag)).collect();
     for instance in &fvar.instances {
-        let mut it = names.iter();
-        loop {
-            let name = it.find(|x| x.nameId.0 == instance.subfamilyNameId);
-            if let Some(name) = name {
-                dbg!(&name.name);
-                break;
-            } else {
-                break;
-            }
+        if let Some(name) = names
+            .iter()
+            .find(|x| x.nameId.0 == instance.subfamilyNameId)
+        {
+            dbg!(&name.name);
         }
         for (i, coord) in instance.coordinates.coordinates.iter().enumerate() {
             if coord == &default_values[i].0 {
@@ -46,9 +43,9 @@ fn main() {
         let mut buf = Vec::new();
         file.read_to_end(&mut buf).unwrap();
 
-        if is_ttc(&buf) {
-        } else {
-            callback(&Table::new(&buf).unwrap()).unwrap();
+        let font_file = FontFile::from_bytes(&buf).unwrap();
+        for (_, table) in &font_file {
+            callback(&table.unwrap()).unwrap();
         }
     } else {
         println!("filepath is necessary");
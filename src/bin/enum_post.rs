@@ -0,0 +1,34 @@
+use std::{fs::File, io::Read};
+
+use font_decoder::{table::Table, FontFile};
+
+fn callback(table: &Table) {
+    let post = table.get_post_table().unwrap();
+    dbg!(&post.version);
+    println!("italicAngle = {}", post.italic_angle());
+    println!("underlinePosition = {}", post.underlinePosition);
+    println!("underlineThickness = {}", post.underlineThickness);
+    println!("isFixedPitch = {}", post.is_fixed_pitch());
+    for glyph_id in 0..post.len() as u16 {
+        if let Some(name) = post.glyph_name(glyph_id) {
+            println!("{}: {}", glyph_id, name);
+        }
+    }
+}
+
+// cargo run --bin enum_post
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(filepath) = args.get(1) {
+        let mut file = File::open(filepath).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+
+        let font_file = FontFile::from_bytes(&buffer).unwrap();
+        for (_, table) in &font_file {
+            callback(&table.unwrap())
+        }
+    } else {
+        println!("filepath is necessary")
+    }
+}
@@ -1,12 +1,33 @@
 use std::{fs::File, io::Read};
 
-use font_decoder::table::{is_ttc, Collection, Table};
+use font_decoder::{table::Table, FontFile};
 
 fn callback(table: &Table) {
-    for record in &table.table_directory.tableRecords {
+    let report = table.verify_checksums();
+    for (record, checksum) in table
+        .table_directory
+        .tableRecords
+        .iter()
+        .zip(&report.tables)
+    {
         let start = record.offset as usize;
         let end = start + record.length as usize;
-        println!("{:?} = [{}..{}]", record.tableTag, start, end);
+        let status = if checksum.is_valid() { "ok" } else { "NG" };
+        println!(
+            "{:?} = [{}..{}] checksum = 0x{:08x} ({})",
+            record.tableTag, start, end, record.checksum, status
+        );
+    }
+    for issue in table.validate_directory() {
+        println!("directory: {}", issue);
+    }
+    match report.adjustment {
+        Ok(None) => println!("checksumAdjustment is ignored in a collection"),
+        Ok(Some(adjustment)) => println!(
+            "checksumAdjustment = 0x{:08x} (computed = 0x{:08x})",
+            adjustment.stored, adjustment.computed
+        ),
+        Err(e) => println!("checksumAdjustment = {}", e),
     }
 }
 
@@ -18,19 +39,22 @@ fn main() {
         let mut buffer = Vec::new();
         file.read_to_end(&mut buffer).unwrap();
 
-        if is_ttc(&buffer) {
-            let collection = Collection::new(&buffer).unwrap();
+        let font_file = FontFile::from_bytes(&buffer).unwrap();
+        if let FontFile::Collection(collection) = &font_file {
             println!("collection = [0..{}]", buffer.len());
-            for i in 0..collection.header.numFonts as usize {
+            if let Ok(Some(dsig)) = collection.get_dsig_data() {
+                println!("DSIG = [{} bytes]", dsig.len());
+            }
+        }
+        for (i, table) in &font_file {
+            if let FontFile::Collection(collection) = &font_file {
                 println!(
                     "table directory[{}] = [{}..]",
                     i,
                     collection.header.tableDirectoryOffsets.get(i).unwrap()
                 );
-                callback(&collection.get(i).unwrap())
             }
-        } else {
-            callback(&Table::new(&buffer).unwrap())
+            callback(&table.unwrap())
         }
     } else {
         println!("filepath is necessary")
@@ -0,0 +1,40 @@
+use std::{fs::File, io::Read};
+
+use font_decoder::{table::Table, Font, FontFile};
+
+fn callback(table: Table) {
+    if let Ok(vhea) = table.get_vhea_table() {
+        dbg!(&vhea);
+    }
+    if let Ok(vorg) = table.get_vorg_table() {
+        println!("VORG: defaultVertOriginY = {}", vorg.defaultVertOriginY);
+    }
+    // vhea と vmtx が無いフォントでは OS/2 と bbox から求めた値になる．
+    let font = Font::from_table(table);
+    for glyph_id in 0..font.number_of_glyphs().unwrap() {
+        match font.glyph_v_metrics(glyph_id) {
+            Ok(metrics) => println!(
+                "{}: advanceHeight = {}, tsb = {}, vertOriginY = {}",
+                glyph_id, metrics.advance_height, metrics.top_side_bearing, metrics.vert_origin_y
+            ),
+            Err(e) => println!("{}: {}", glyph_id, e),
+        }
+    }
+}
+
+// cargo run --bin enum_vmtx
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(filepath) = args.get(1) {
+        let mut file = File::open(filepath).unwrap();
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer).unwrap();
+
+        let font_file = FontFile::from_bytes(&buffer).unwrap();
+        for (_, table) in &font_file {
+            callback(table.unwrap())
+        }
+    } else {
+        println!("filepath is necessary")
+    }
+}
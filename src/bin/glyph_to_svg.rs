@@ -0,0 +1,75 @@
+use std::{error::Error, fs, path::Path, process::ExitCode};
+
+use font_decoder::{
+    svg::{glyph_sheet_svg, glyph_to_svg, SvgOptions},
+    Font, FontFile,
+};
+
+const USAGE: &str = "usage: glyph_to_svg <font> <output dir> [--face <index>] [--sheet] [--points] [--bbox] [--metrics] [glyph id...]";
+
+// `cargo run --bin glyph_to_svg <font> <output dir> [--face <index>] [--sheet] [--points] [--bbox] [--metrics] [glyph id...]`
+// collection の場合は --face で face の index を指定する．省略すると 0 番目の face を使う．
+// glyph id を省略するとすべての glyph を書き出す．
+// --sheet を付けると，1 枚の sheet.svg に並べて書き出す．それ以外は <glyph id>.svg を glyph ごとに書き出す．
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = std::env::args().collect();
+    let [_, font_path, output, rest @ ..] = args.as_slice() else {
+        return Err(USAGE.into());
+    };
+    let data = fs::read(font_path).map_err(|e| format!("{}: {}", font_path, e))?;
+    let output = Path::new(output);
+
+    let mut face = 0;
+    let mut options = SvgOptions::default();
+    let mut sheet = false;
+    let mut glyph_ids = vec![];
+    let mut rest = rest.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--face" => {
+                let index = rest.next().ok_or(USAGE)?;
+                face = index
+                    .parse()
+                    .map_err(|e| format!("face index {}: {}", index, e))?;
+            }
+            "--sheet" => sheet = true,
+            "--points" => options.points = true,
+            "--bbox" => options.bbox = true,
+            "--metrics" => options.metrics = true,
+            _ => glyph_ids.push(
+                arg.parse::<u16>()
+                    .map_err(|e| format!("glyph id {}: {}", arg, e))?,
+            ),
+        }
+    }
+
+    let table = FontFile::from_bytes(&data)?.face(face)?;
+    let font = Font::from_table(table);
+    if glyph_ids.is_empty() {
+        glyph_ids = (0..font.number_of_glyphs()?).collect();
+    }
+
+    fs::create_dir_all(output)?;
+    if sheet {
+        let svg = glyph_sheet_svg(&font, glyph_ids, 16, 100.0, &options);
+        fs::write(output.join("sheet.svg"), svg)?;
+        return Ok(());
+    }
+    for glyph_id in glyph_ids {
+        match glyph_to_svg(&font, glyph_id, &options) {
+            Ok(svg) => fs::write(output.join(format!("{}.svg", glyph_id)), svg)?,
+            Err(e) => eprintln!("glyph {}: {}", glyph_id, e),
+        }
+    }
+    Ok(())
+}
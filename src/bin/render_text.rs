@@ -0,0 +1,53 @@
+use std::{error::Error, fs, process::ExitCode};
+
+use font_decoder::{text::render_text, Font, FontFile};
+
+const USAGE: &str =
+    "usage: render_text <font> <output.png|output.pgm> <pixel size> <text> [--face <index>]";
+
+// `cargo run --bin render_text <font> <output.png|output.pgm> <pixel size> <text> [--face <index>]`
+// text の "\n" は改行として扱う．
+// collection の場合は --face で face の index を指定する．省略すると 0 番目の face を使う．
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = std::env::args().collect();
+    let [_, font_path, output, pixel_size, text, rest @ ..] = args.as_slice() else {
+        return Err(USAGE.into());
+    };
+    let data = fs::read(font_path).map_err(|e| format!("{}: {}", font_path, e))?;
+    let pixel_size: f64 = pixel_size
+        .parse()
+        .map_err(|e| format!("pixel size {}: {}", pixel_size, e))?;
+    let text = text.replace("\\n", "\n");
+    let face = match rest {
+        [] => 0,
+        [option, index] if option == "--face" => index
+            .parse()
+            .map_err(|e| format!("face index {}: {}", index, e))?,
+        _ => return Err(USAGE.into()),
+    };
+
+    let table = FontFile::from_bytes(&data)?.face(face)?;
+    let font = Font::from_table(table);
+    let bitmap = render_text(&font, &text, pixel_size)?;
+    println!(
+        "{}x{} left={} top={}",
+        bitmap.width, bitmap.height, bitmap.left, bitmap.top
+    );
+    let mut file = fs::File::create(output).map_err(|e| format!("{}: {}", output, e))?;
+    if output.ends_with(".pgm") {
+        bitmap.write_pgm(&mut file)?;
+    } else {
+        bitmap.write_png(&mut file)?;
+    }
+    Ok(())
+}
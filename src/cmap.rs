@@ -1,6 +1,10 @@
+use core::{cmp::Ordering, ops::RangeInclusive};
+
 use crate::{
-    data_types::{int16, uint16, Offset32},
-    decoder::{FromData, Stream},
+    data_types::{int16, uint16, uint32, uint8, Offset32, Uint24},
+    decoder::{parse_optional_subtable, parse_subtable, FromData, LazyArray, Stream},
+    encoding::CmapEncoding,
+    error::{Context, Error, Result},
 };
 
 #[allow(non_snake_case)]
@@ -13,12 +17,12 @@ pub struct CmapHeader {
 
 impl CmapHeader {
     #[allow(non_snake_case)]
-    pub fn parse(data: &[u8]) -> Option<Self> {
+    pub fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let version = s.read()?;
-        let numTables = s.read()?;
-        let encodingRecords = s.read_array(numTables as usize)?;
-        Some(Self {
+        let version = s.read().field("version")?;
+        let numTables = s.read().field("numTables")?;
+        let encodingRecords = s.read_array(numTables as usize).field("encodingRecords")?;
+        Ok(Self {
             version,
             numTables,
             encodingRecords,
@@ -34,15 +38,21 @@ pub struct EncodingRecord {
     pub subtableOffset: Offset32, // Byte offset from beginning of table to the subtable for this encoding.
 }
 
+impl EncodingRecord {
+    pub fn get_cmap_encoding(&self) -> CmapEncoding {
+        CmapEncoding::new(self.platformID, self.encodingID)
+    }
+}
+
 impl FromData for EncodingRecord {
     const SIZE: usize = 4 + 4;
     #[allow(non_snake_case)]
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let platformID = s.read()?;
-        let encodingID = s.read()?;
-        let subtableOffset = s.read()?;
-        Some(Self {
+        let platformID = s.read().field("platformID")?;
+        let encodingID = s.read().field("encodingID")?;
+        let subtableOffset = s.read().field("subtableOffset")?;
+        Ok(Self {
             platformID,
             encodingID,
             subtableOffset,
@@ -50,83 +60,344 @@ impl FromData for EncodingRecord {
     }
 }
 
-pub enum CmapSubtable {
-    Format0,
-    Format2,
-    Format4(CmapSubtableFormat4),
-    Format6,
-    Format8,
-    Format10,
-    Format12,
-    Format13,
-    Format14,
+pub enum CmapSubtable<'a> {
+    Format0(CmapSubtableFormat0<'a>),
+    Format2(CmapSubtableFormat2<'a>),
+    Format4(CmapSubtableFormat4<'a>),
+    Format6(CmapSubtableFormat6<'a>),
+    Format8(CmapSubtableFormat8<'a>),
+    Format10(CmapSubtableFormat10<'a>),
+    Format12(CmapSubtableFormat12<'a>),
+    Format13(CmapSubtableFormat13<'a>),
+    Format14(CmapSubtableFormat14<'a>),
 }
 
-impl CmapSubtable {
-    pub fn parse(data: &[u8]) -> Option<Self> {
+impl<'a> CmapSubtable<'a> {
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let format: u16 = s.read()?;
+        let format: u16 = s.read().field("format")?;
         match format {
-            4 => Some(Self::Format4(CmapSubtableFormat4::parse(data)?)),
-            _ => None,
+            0 => Ok(Self::Format0(CmapSubtableFormat0::parse(data)?)),
+            2 => Ok(Self::Format2(CmapSubtableFormat2::parse(data)?)),
+            4 => Ok(Self::Format4(CmapSubtableFormat4::parse(data)?)),
+            6 => Ok(Self::Format6(CmapSubtableFormat6::parse(data)?)),
+            8 => Ok(Self::Format8(CmapSubtableFormat8::parse(data)?)),
+            10 => Ok(Self::Format10(CmapSubtableFormat10::parse(data)?)),
+            12 => Ok(Self::Format12(CmapSubtableFormat12::parse(data)?)),
+            13 => Ok(Self::Format13(CmapSubtableFormat13::parse(data)?)),
+            14 => Ok(Self::Format14(CmapSubtableFormat14::parse(data)?)),
+            _ => Err(Error::unsupported(format.into(), 0).with_field("format")),
+        }
+    }
+
+    pub fn get_format(&self) -> u16 {
+        match self {
+            Self::Format0(_) => 0,
+            Self::Format2(_) => 2,
+            Self::Format4(_) => 4,
+            Self::Format6(_) => 6,
+            Self::Format8(_) => 8,
+            Self::Format10(_) => 10,
+            Self::Format12(_) => 12,
+            Self::Format13(_) => 13,
+            Self::Format14(_) => 14,
         }
     }
 
+    // Unicode 以外のエンコーディングの subtable では，code_point をそのまま文字コードとして引く．
     pub fn get_glyph_id(&self, code_point: char) -> Option<u16> {
+        self.get_glyph_id_by_code(code_point as u32)
+    }
+
+    // 文字コードが subtable の範囲内で対応する glyph が無い場合は Some(0) (.notdef) を返す．
+    // 文字コードが format で表せない場合や，format 14 の場合は None を返す．
+    pub fn get_glyph_id_by_code(&self, code: u32) -> Option<u16> {
+        match self {
+            Self::Format0(x) => x.get_glyph_id_by_code(code),
+            Self::Format2(x) => x.get_glyph_id_by_code(code),
+            Self::Format4(x) => x.get_glyph_id_by_code(code),
+            Self::Format6(x) => x.get_glyph_id_by_code(code),
+            Self::Format8(x) => x.get_glyph_id_by_code(code),
+            Self::Format10(x) => x.get_glyph_id_by_code(code),
+            Self::Format12(x) => x.get_glyph_id_by_code(code),
+            Self::Format13(x) => x.get_glyph_id_by_code(code),
+            Self::Format14(_) => None,
+        }
+    }
+
+    // format 14 以外の subtable では None を返す．
+    pub fn get_variant_glyph_id(&self, code_point: char, selector: char) -> Option<VariantGlyph> {
         match self {
-            Self::Format4(x) => x.get_glyph_id(code_point),
-            _ => todo!(),
+            Self::Format14(x) => x.get_variant_glyph_id(code_point, selector),
+            _ => None,
         }
     }
 
-    // TODO: Iterator
+    // glyph が割り当てられている (文字コード, glyph id) を文字コードの昇順に返す．
+    // 前の区間と重なる区間や，Unicode の範囲外の文字コードは無視する．
+    pub fn iter(&self) -> CmapIter<'_, 'a> {
+        CmapIter {
+            subtable: self,
+            segment: 0,
+            glyphs: SegmentGlyphs::Lookup,
+            code: 1,
+            end: 0,
+            next_code: 0,
+        }
+    }
+
+    // 文字コードと glyph id がともに連続している区間を (文字コードの範囲, 先頭の glyph id) として返す．
+    pub fn ranges(&self) -> CmapRanges<CmapIter<'_, 'a>> {
+        CmapRanges::new(self.iter())
+    }
+
     pub fn get_code_point_glyph_id_map(&self) -> Vec<(char, u16)> {
+        self.iter()
+            .filter_map(|(code, glyph_id)| Some((char::from_u32(code)?, glyph_id)))
+            .collect()
+    }
+
+    // 文字コードの区間の数．
+    fn get_segment_count(&self) -> usize {
+        match self {
+            Self::Format0(_) | Self::Format6(_) | Self::Format10(_) => 1,
+            Self::Format2(_) => 256,
+            Self::Format4(x) => x.startCode.len(),
+            Self::Format8(x) => x.groups.len(),
+            Self::Format12(x) => x.groups.len(),
+            Self::Format13(x) => x.groups.len(),
+            Self::Format14(_) => 0,
+        }
+    }
+
+    // i 番目の区間の (最初の文字コード, 最後の文字コード)．
+    fn get_segment(&self, i: usize) -> Option<(u32, u32)> {
         match self {
-            Self::Format4(x) => x.get_code_point_glyph_id_map(),
-            _ => todo!(),
+            Self::Format0(_) => Some((0, 0xFF)),
+            Self::Format2(x) => {
+                let high_byte = u8::try_from(i).ok()?;
+                match (high_byte, x.get_sub_header_index(high_byte)?) {
+                    (0, _) => Some((0, 0xFF)),
+                    (_, 0) => None,
+                    _ => {
+                        let start = (high_byte as u32) << 8;
+                        Some((start, start | 0xFF))
+                    }
+                }
+            }
+            Self::Format4(x) => Some((x.startCode.get(i)? as u32, x.endCode.get(i)? as u32)),
+            Self::Format6(x) => {
+                let start = x.firstCode as u32;
+                Some((start, (start + x.entryCount as u32).checked_sub(1)?))
+            }
+            Self::Format8(x) => x.groups.get(i).map(|group| group.get_range()),
+            Self::Format10(x) => {
+                let start = x.startCharCode;
+                Some((start, start.saturating_add(x.numChars).checked_sub(1)?))
+            }
+            Self::Format12(x) => x.groups.get(i).map(|group| group.get_range()),
+            Self::Format13(x) => x.groups.get(i).map(|group| group.get_range()),
+            Self::Format14(_) => None,
+        }
+    }
+
+    // i 番目の区間の glyph id の求め方．
+    fn get_segment_glyphs(&self, i: usize) -> Option<SegmentGlyphs> {
+        match self {
+            // format 8 は is32 と合わない文字コードを除くため，一つずつ引く．
+            Self::Format12(x) => x.groups.get(i).map(SegmentGlyphs::from),
+            Self::Format13(x) => x.groups.get(i).map(SegmentGlyphs::from),
+            _ => Some(SegmentGlyphs::Lookup),
+        }
+    }
+
+    // i 番目の区間に含まれる code の glyph id．
+    fn get_segment_glyph_id(&self, i: usize, code: u32) -> Option<u16> {
+        match self {
+            Self::Format4(x) => x.get_segment_glyph_id(i, u16::try_from(code).ok()?),
+            _ => self.get_glyph_id_by_code(code),
         }
     }
 }
 
+// format 0: 1 バイトの文字コードから glyph id への表．
 #[allow(non_snake_case)]
-pub struct CmapSubtableFormat4 {
-    pub format: uint16,              // Format number is set to 4.
-    pub length: uint16,              // This is the length in bytes of the subtable.
+pub struct CmapSubtableFormat0<'a> {
+    pub format: uint16,                     // Format number is set to 0.
+    pub length: uint16,                     // This is the length in bytes of the subtable.
+    pub language: uint16, // For requirements on use of the language field, see “Use of the language field in 'cmap' subtables” in this document.
+    pub glyphIdArray: LazyArray<'a, uint8>, // [256] An array that maps character codes to glyph index values.
+}
+
+impl<'a> CmapSubtableFormat0<'a> {
+    #[allow(non_snake_case)]
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let format = s.read().field("format")?;
+        let length = s.read().field("length")?;
+        s.set_len(length as usize).field("length")?;
+        let language = s.read().field("language")?;
+        let glyphIdArray = s.read_lazy_array(256).field("glyphIdArray")?;
+        Ok(Self {
+            format,
+            length,
+            language,
+            glyphIdArray,
+        })
+    }
+
+    pub fn get_glyph_id_by_code(&self, code: u32) -> Option<u16> {
+        self.glyphIdArray
+            .get(usize::try_from(code).ok()?)
+            .map(u16::from)
+    }
+}
+
+// format 2: 日中韓の 1 バイトと 2 バイトが混在する文字コード向けの表．
+#[allow(non_snake_case)]
+pub struct CmapSubtableFormat2<'a> {
+    data: &'a [u8],
+    pub format: uint16,                       // Format number is set to 2.
+    pub length: uint16,                       // This is the length in bytes of the subtable.
+    pub language: uint16, // For requirements on use of the language field, see “Use of the language field in 'cmap' subtables” in this document.
+    pub subHeaderKeys: LazyArray<'a, uint16>, // [256] Array that maps high bytes to subHeaders: value is subHeader index × 8.
+    pub subHeaders: LazyArray<'a, SubHeader>, // [ ] Variable-length array of SubHeader records.
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Copy)]
+pub struct SubHeader {
+    pub firstCode: uint16,     // First valid low byte for this SubHeader.
+    pub entryCount: uint16,    // Number of valid low bytes for this SubHeader.
+    pub idDelta: int16,        // See text below.
+    pub idRangeOffset: uint16, // See text below.
+}
+
+impl FromData for SubHeader {
+    const SIZE: usize = 2 * 4;
+    fn parse(data: &[u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        Ok(Self {
+            firstCode: s.read().field("firstCode")?,
+            entryCount: s.read().field("entryCount")?,
+            idDelta: s.read().field("idDelta")?,
+            idRangeOffset: s.read().field("idRangeOffset")?,
+        })
+    }
+}
+
+impl<'a> CmapSubtableFormat2<'a> {
+    const SUB_HEADERS_OFFSET: usize = 6 + 256 * 2;
+
+    #[allow(non_snake_case)]
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let format = s.read().field("format")?;
+        let length: u16 = s.read().field("length")?;
+        let data = data
+            .get(..length as usize)
+            .ok_or(Error::eof(data.len()))
+            .field("length")?;
+        let mut s = Stream::new(data);
+        s.set_offset(4);
+        let language = s.read().field("language")?;
+        let subHeaderKeys: LazyArray<u16> = s.read_lazy_array(256).field("subHeaderKeys")?;
+        // subHeader の数は subHeaderKeys の最大値から決まる．
+        let count = subHeaderKeys.iter().max().unwrap_or(0) as usize / 8 + 1;
+        let subHeaders = s.read_lazy_array(count).field("subHeaders")?;
+        Ok(Self {
+            data,
+            format,
+            length,
+            language,
+            subHeaderKeys,
+            subHeaders,
+        })
+    }
+
+    // 上位バイトに対応する subHeader の番号．0 は 1 バイト文字．
+    fn get_sub_header_index(&self, high_byte: u8) -> Option<usize> {
+        Some(self.subHeaderKeys.get(high_byte as usize)? as usize / 8)
+    }
+
+    // 文字コードは 1 バイト文字なら 0x00XX，2 バイト文字なら上位バイトと下位バイトを並べたもの．
+    pub fn get_glyph_id_by_code(&self, code: u32) -> Option<u16> {
+        let code = u16::try_from(code).ok()?;
+        let [high_byte, low_byte] = code.to_be_bytes();
+        let index = if high_byte == 0 {
+            // 2 バイト文字の先頭バイトは単独では文字にならない．
+            if self.get_sub_header_index(low_byte)? != 0 {
+                return Some(0);
+            }
+            0
+        } else {
+            match self.get_sub_header_index(high_byte)? {
+                0 => return Some(0),
+                index => index,
+            }
+        };
+        self.get_sub_header_glyph_id(index, low_byte)
+    }
+
+    // index 番目の subHeader から low_byte の glyph id を引く．
+    fn get_sub_header_glyph_id(&self, index: usize, low_byte: u8) -> Option<u16> {
+        let sub_header = self.subHeaders.get(index)?;
+        let delta = (low_byte as u16).checked_sub(sub_header.firstCode);
+        let Some(delta) = delta.filter(|&delta| delta < sub_header.entryCount) else {
+            return Some(0);
+        };
+        // idRangeOffset は idRangeOffset 自身の位置からのバイト数．
+        let id_range_offset_position = Self::SUB_HEADERS_OFFSET + index * SubHeader::SIZE + 6;
+        let mut s = Stream::new(self.data);
+        s.set_offset(
+            id_range_offset_position + sub_header.idRangeOffset as usize + delta as usize * 2,
+        );
+        let glyph_id: u16 = s.read().ok()?;
+        if glyph_id == 0 {
+            return Some(0);
+        }
+        Some(glyph_id.wrapping_add(sub_header.idDelta as u16))
+    }
+}
+
+#[allow(non_snake_case)]
+pub struct CmapSubtableFormat4<'a> {
+    pub format: uint16,                        // Format number is set to 4.
+    pub length: uint16,                        // This is the length in bytes of the subtable.
     pub language: uint16, // For requirements on use of the language field, see “Use of the language field in 'cmap' subtables” in this document.
     pub segCountX2: uint16, // 2 × segCount. u16 の配列があるので，2をかけている．
     pub searchRange: uint16, // Maximum power of 2 less than or equal to segCount, times 2 ((2**floor(log2(segCount))) * 2, where “**” is an exponentiation operator)
     pub entrySelector: uint16, // Log2 of the maximum power of 2 less than or equal to segCount (log2(searchRange/2), which is equal to floor(log2(segCount)))
     pub rangeShift: uint16,    // segCount times 2, minus searchRange ((segCount * 2) - searchRange)
-    pub endCode: Vec<uint16>,  // [segCount] End characterCode for each segment, last=0xFFFF.
-    pub reservedPad: uint16,   // Set to 0.
-    pub startCode: Vec<uint16>, // [segCount] Start character code for each segment.
-    pub idDelta: Vec<int16>,   // [segCount] Delta for all character codes in segment.
-    pub idRangeOffsets: Vec<uint16>, // [segCount] Offsets into glyphIdArray or 0
-    pub glyphIdArray: Vec<uint16>, // [ ] Glyph index array (arbitrary length)
+    pub endCode: LazyArray<'a, uint16>, // [segCount] End characterCode for each segment, last=0xFFFF.
+    pub reservedPad: uint16,            // Set to 0.
+    pub startCode: LazyArray<'a, uint16>, // [segCount] Start character code for each segment.
+    pub idDelta: LazyArray<'a, int16>,  // [segCount] Delta for all character codes in segment.
+    pub idRangeOffsets: LazyArray<'a, uint16>, // [segCount] Offsets into glyphIdArray or 0
+    pub glyphIdArray: LazyArray<'a, uint16>, // [ ] Glyph index array (arbitrary length)
 }
 
-impl CmapSubtableFormat4 {
+impl<'a> CmapSubtableFormat4<'a> {
     #[allow(non_snake_case)]
-    pub fn parse(data: &[u8]) -> Option<Self> {
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let format = s.read()?;
-        let length = s.read()?;
-        s.set_len(length as usize);
-        let language = s.read()?;
-        let segCountX2 = s.read()?;
+        let format = s.read().field("format")?;
+        // 大きな subtable では 16 ビットの length があふれるので，length は使わずに data の終わりまで読む．
+        let length = s.read().field("length")?;
+        let language = s.read().field("language")?;
+        let segCountX2 = s.read().field("segCountX2")?;
         let segCount = (segCountX2 / 2) as usize;
-        let searchRange = s.read()?;
-        let entrySelector = s.read()?;
-        let rangeShift = s.read()?;
-        let endCode = s.read_array(segCount)?;
-        assert_eq!(endCode.last().unwrap(), &0xFFFF);
-        let reservedPad = s.read()?;
-        assert_eq!(reservedPad, 0);
-        let startCode = s.read_array(segCount)?;
-        let idDelta = s.read_array(segCount)?;
-        let idRangeOffsets = s.read_array(segCount)?;
-        let glyphIdArray = s.read_all_array()?;
-        Some(Self {
+        let searchRange = s.read().field("searchRange")?;
+        let entrySelector = s.read().field("entrySelector")?;
+        let rangeShift = s.read().field("rangeShift")?;
+        // 最後の endCode が 0xFFFF でないフォントや reservedPad が 0 でないフォントも出回っているので，検査しない．
+        let endCode = s.read_lazy_array(segCount).field("endCode")?;
+        let reservedPad = s.read().field("reservedPad")?;
+        let startCode = s.read_lazy_array(segCount).field("startCode")?;
+        let idDelta = s.read_lazy_array(segCount).field("idDelta")?;
+        let idRangeOffsets = s.read_lazy_array(segCount).field("idRangeOffsets")?;
+        let glyphIdArray = s.read_all_lazy_array().field("glyphIdArray")?;
+        Ok(Self {
             format,
             length,
             language,
@@ -144,20 +415,23 @@ impl CmapSubtableFormat4 {
     }
 
     pub fn get_glyph_id(&self, code_point: char) -> Option<u16> {
-        // 0xFFFF 以上の Unicode Scalar Value の場合は None を返す．
-        let code_point = u16::try_from(code_point as u32).ok()?;
+        self.get_glyph_id_by_code(code_point as u32)
+    }
+
+    pub fn get_glyph_id_by_code(&self, code: u32) -> Option<u16> {
+        // 0xFFFF より大きい文字コードの場合は None を返す．
+        let code_point = u16::try_from(code).ok()?;
         let mut start = 0;
         let mut end = self.startCode.len(); // == segCount.
         while end > start {
             let mid = (start + end) / 2;
-            let end_code_point = *self.endCode.get(mid)?;
+            let end_code_point = self.endCode.get(mid)?;
             if end_code_point < code_point {
                 // [... , mid, start, ..., end]
                 start = mid + 1;
                 continue;
             }
-            // endCode.len() == startCode.len() が保証されているので，値は必ず存在する．
-            let start_code_point = *self.startCode.get(mid).unwrap();
+            let start_code_point = self.startCode.get(mid)?;
             if code_point < start_code_point {
                 // [start, ... , end = mid, ...]
                 end = mid;
@@ -165,63 +439,678 @@ impl CmapSubtableFormat4 {
             }
 
             // start_code_point <= code_point <= end_code_point の範囲に含まれている．
+            return self.get_segment_glyph_id(mid, code_point);
+        }
+        Some(0) // notdef.
+    }
 
-            let id_range_offset = *self.idRangeOffsets.get(mid)?;
-            let id_delta = *self.idDelta.get(mid)?;
-            if id_range_offset == 0 {
-                // 2の補数表現を使っているから，negative i16 を u16 と解釈してオーバフロー分を無視して加算すれば減算と同じ．
-                // 例: FFFF (= -1) + 0001 (= 1) = 0
-                return Some(code_point.wrapping_add(id_delta as u16));
-            }
+    // i 番目のセグメントに含まれる code_point の glyph id を返す．
+    fn get_segment_glyph_id(&self, i: usize, code_point: u16) -> Option<u16> {
+        let start_code_point = self.startCode.get(i)?;
+        let id_range_offset = self.idRangeOffsets.get(i)?;
+        let id_delta = self.idDelta.get(i)?;
+        if id_range_offset == 0 {
+            // 2の補数表現を使っているから，negative i16 を u16 と解釈してオーバフロー分を無視して加算すれば減算と同じ．
+            // 例: FFFF (= -1) + 0001 (= 1) = 0
+            return Some(code_point.wrapping_add(id_delta as u16));
+        }
+
+        // id_range_offset が 0 で無い場合は，i の場所から id_range_offset の分だけオフセットした位置の glyph_id_array を取得する．
 
-            // id_range_offset が 0 で無い場合は，mid の場所から id_range_offset の分だけオフセットした位置の glyph_id_array を取得する．
-
-            // curCode                          24
-            // startCode                 [0, 7, 23, ...]
-            // indices of idRangeOffsets [0, 1,  2, 3, 4, 5]
-            // idRangeOffsets[u16]       [ ,  , 12,  ,  ,  ]
-            //                                   ↑        ↑
-            //                                   i       (segCount - 1)
-            // curPtr = &idRangeOffsets[i]       ↑
-            // indices of glyphIdArray                      [ 0,  1,  2,  3, ...]
-            // glyphIdArray[u16]                            [15, 16, 20, 21, ...]
-            // curPtr + (segCount - i)                        ↑
-            // curPtr + (idRangeOffsets[i]/2) =                       ↑
-            // curPtr + (idRangeOffsets[i]/2) + (curCode - startCode[i])  ↑
-            //                                                |-----------|
-            let gid_array_index_from_id_range_offset = id_range_offset as usize / 2;
-            let gid_array_start_from_id_range_offset = self.idRangeOffsets.len() - mid;
-            let gid_array_index =
-                gid_array_index_from_id_range_offset - gid_array_start_from_id_range_offset;
-            let delta = (code_point - start_code_point) as usize;
-            let glyph_id_array_index = gid_array_index + delta;
-            return Some(*self.glyphIdArray.get(glyph_id_array_index as usize)?);
-        }
-        return Some(0); // notdef.
+        // curCode                          24
+        // startCode                 [0, 7, 23, ...]
+        // indices of idRangeOffsets [0, 1,  2, 3, 4, 5]
+        // idRangeOffsets[u16]       [ ,  , 12,  ,  ,  ]
+        //                                   ↑        ↑
+        //                                   i       (segCount - 1)
+        // curPtr = &idRangeOffsets[i]       ↑
+        // indices of glyphIdArray                      [ 0,  1,  2,  3, ...]
+        // glyphIdArray[u16]                            [15, 16, 20, 21, ...]
+        // curPtr + (segCount - i)                        ↑
+        // curPtr + (idRangeOffsets[i]/2) =                       ↑
+        // curPtr + (idRangeOffsets[i]/2) + (curCode - startCode[i])  ↑
+        //                                                |-----------|
+        let gid_array_index_from_id_range_offset = id_range_offset as usize / 2;
+        let gid_array_start_from_id_range_offset = self.idRangeOffsets.len() - i;
+        // 不正な id_range_offset の場合は glyphIdArray の前を指すことがある．
+        let gid_array_index = gid_array_index_from_id_range_offset
+            .checked_sub(gid_array_start_from_id_range_offset)?;
+        let delta = code_point.checked_sub(start_code_point)? as usize;
+        let glyph_id_array_index = gid_array_index + delta;
+        let glyph_id = self.glyphIdArray.get(glyph_id_array_index)?;
+        // glyphIdArray の値が 0 (.notdef) でなければ，idDelta を足す．
+        if glyph_id == 0 {
+            return Some(0);
+        }
+        Some(glyph_id.wrapping_add(id_delta as u16))
     }
+}
 
-    pub fn get_code_point_glyph_id_map(&self) -> Vec<(char, u16)> {
-        let mut map = Vec::new();
-        for (i, start_code_point) in self.startCode.iter().enumerate() {
-            let end_code_point = *self.endCode.get(i).unwrap();
-            let id_delta = *self.idDelta.get(i).unwrap();
-            let id_range_offset = *self.idRangeOffsets.get(i).unwrap();
-            for code_point in *start_code_point..=end_code_point {
-                let glyph_id = if id_range_offset == 0 {
-                    code_point.wrapping_add(id_delta as u16)
+// format 6: 連続した 16 ビットの文字コードの範囲の表．
+#[allow(non_snake_case)]
+pub struct CmapSubtableFormat6<'a> {
+    pub format: uint16,                      // Format number is set to 6.
+    pub length: uint16,                      // This is the length in bytes of the subtable.
+    pub language: uint16, // For requirements on use of the language field, see “Use of the language field in 'cmap' subtables” in this document.
+    pub firstCode: uint16, // First character code of subrange.
+    pub entryCount: uint16, // Number of character codes in subrange.
+    pub glyphIdArray: LazyArray<'a, uint16>, // [entryCount] Array of glyph index values for character codes in the range.
+}
+
+impl<'a> CmapSubtableFormat6<'a> {
+    #[allow(non_snake_case)]
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let format = s.read().field("format")?;
+        let length = s.read().field("length")?;
+        s.set_len(length as usize).field("length")?;
+        let language = s.read().field("language")?;
+        let firstCode = s.read().field("firstCode")?;
+        let entryCount = s.read().field("entryCount")?;
+        let glyphIdArray = s
+            .read_lazy_array(entryCount as usize)
+            .field("glyphIdArray")?;
+        Ok(Self {
+            format,
+            length,
+            language,
+            firstCode,
+            entryCount,
+            glyphIdArray,
+        })
+    }
+
+    pub fn get_glyph_id_by_code(&self, code: u32) -> Option<u16> {
+        let code = u16::try_from(code).ok()?;
+        match code.checked_sub(self.firstCode) {
+            Some(index) if index < self.entryCount => self.glyphIdArray.get(index as usize),
+            _ => Some(0),
+        }
+    }
+}
+
+// format 8, 12 の group．startCharCode から順に startGlyphID から連続した glyph に対応する．
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Copy)]
+pub struct SequentialMapGroup {
+    pub startCharCode: uint32, // First character code in this group.
+    pub endCharCode: uint32,   // Last character code in this group.
+    pub startGlyphID: uint32,  // Glyph index corresponding to the starting character code.
+}
+
+impl FromData for SequentialMapGroup {
+    const SIZE: usize = 4 * 3;
+    fn parse(data: &[u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        Ok(Self {
+            startCharCode: s.read().field("startCharCode")?,
+            endCharCode: s.read().field("endCharCode")?,
+            startGlyphID: s.read().field("startGlyphID")?,
+        })
+    }
+}
+
+// format 13 の group．範囲内のすべての文字が同じ glyph に対応する．
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Copy)]
+pub struct ConstantMapGroup {
+    pub startCharCode: uint32, // First character code in this group.
+    pub endCharCode: uint32,   // Last character code in this group.
+    pub glyphID: uint32, // Glyph index to be used for all the characters in the group’s range.
+}
+
+impl FromData for ConstantMapGroup {
+    const SIZE: usize = 4 * 3;
+    fn parse(data: &[u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        Ok(Self {
+            startCharCode: s.read().field("startCharCode")?,
+            endCharCode: s.read().field("endCharCode")?,
+            glyphID: s.read().field("glyphID")?,
+        })
+    }
+}
+
+// format 8, 12, 13 の group を同じように扱うためのトレイト．
+pub trait MapGroup: FromData {
+    fn get_range(&self) -> (u32, u32);
+    // 範囲内の code に対応する glyph id．u16 に収まらない場合は None．
+    fn get_glyph_id(&self, code: u32) -> Option<u16>;
+}
+
+impl MapGroup for SequentialMapGroup {
+    fn get_range(&self) -> (u32, u32) {
+        (self.startCharCode, self.endCharCode)
+    }
+
+    fn get_glyph_id(&self, code: u32) -> Option<u16> {
+        let glyph_id = self
+            .startGlyphID
+            .checked_add(code.checked_sub(self.startCharCode)?)?;
+        u16::try_from(glyph_id).ok()
+    }
+}
+
+impl MapGroup for ConstantMapGroup {
+    fn get_range(&self) -> (u32, u32) {
+        (self.startCharCode, self.endCharCode)
+    }
+
+    fn get_glyph_id(&self, _code: u32) -> Option<u16> {
+        u16::try_from(self.glyphID).ok()
+    }
+}
+
+// group は startCharCode の昇順に並んでいて重ならない．
+fn get_group_glyph_id<T: MapGroup>(groups: &LazyArray<T>, code: u32) -> Option<u16> {
+    let found = groups.binary_search_by(|group| {
+        let (start, end) = group.get_range();
+        if end < code {
+            Ordering::Less
+        } else if code < start {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    });
+    match found {
+        Some((_, group)) => group.get_glyph_id(code),
+        None => Some(0),
+    }
+}
+
+// format 8: 16 ビットと 32 ビットが混在する文字コード向けの表．
+#[allow(non_snake_case)]
+pub struct CmapSubtableFormat8<'a> {
+    pub format: uint16,                            // Subtable format; set to 8.
+    pub reserved: uint16,                          // Reserved; set to 0
+    pub length: uint32,   // Byte length of this subtable (including the header)
+    pub language: uint32, // For requirements on use of the language field, see “Use of the language field in 'cmap' subtables” in this document.
+    pub is32: LazyArray<'a, uint8>, // [8192] Tightly packed array of bits (8K bytes total) indicating whether the particular 16-bit (index) value is the start of a 32-bit character code
+    pub numGroups: uint32,          // Number of groupings which follow
+    pub groups: LazyArray<'a, SequentialMapGroup>, // [numGroups] Array of SequentialMapGroup records.
+}
+
+impl<'a> CmapSubtableFormat8<'a> {
+    #[allow(non_snake_case)]
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let format = s.read().field("format")?;
+        let reserved = s.read().field("reserved")?;
+        let length: u32 = s.read().field("length")?;
+        s.set_len(length as usize).field("length")?;
+        let language = s.read().field("language")?;
+        let is32 = s.read_lazy_array(8192).field("is32")?;
+        let numGroups: u32 = s.read().field("numGroups")?;
+        let groups = s.read_lazy_array(numGroups as usize).field("groups")?;
+        Ok(Self {
+            format,
+            reserved,
+            length,
+            language,
+            is32,
+            numGroups,
+            groups,
+        })
+    }
+
+    // 32 ビットの文字コードは上位 16 ビットの値の is32 が立っていて，16 ビットの文字コードは自身の is32 が立っていない．
+    // is32 と合わない文字コードは subtable に含まれないものとして .notdef を返す．
+    pub fn get_glyph_id_by_code(&self, code: u32) -> Option<u16> {
+        let high = (code >> 16) as u16;
+        let is_valid = if high == 0 {
+            !self.is_32(code as u16)
+        } else {
+            self.is_32(high)
+        };
+        if !is_valid {
+            return Some(0);
+        }
+        get_group_glyph_id(&self.groups, code)
+    }
+
+    // value が 32 ビットの文字コードの上位 16 ビットかどうか．ビットは上位から順に並ぶ．
+    pub fn is_32(&self, value: u16) -> bool {
+        self.is32
+            .get(value as usize / 8)
+            .is_some_and(|bits| bits & (0x80 >> (value % 8)) != 0)
+    }
+}
+
+// format 10: 連続した 32 ビットの文字コードの範囲の表．
+#[allow(non_snake_case)]
+pub struct CmapSubtableFormat10<'a> {
+    pub format: uint16,                      // Subtable format; set to 10.
+    pub reserved: uint16,                    // Reserved; set to 0
+    pub length: uint32,                      // Byte length of this subtable (including the header)
+    pub language: uint32, // For requirements on use of the language field, see “Use of the language field in 'cmap' subtables” in this document.
+    pub startCharCode: uint32, // First character code covered
+    pub numChars: uint32, // Number of character codes covered
+    pub glyphIdArray: LazyArray<'a, uint16>, // Array of glyph indices for the character codes covered
+}
+
+impl<'a> CmapSubtableFormat10<'a> {
+    #[allow(non_snake_case)]
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let format = s.read().field("format")?;
+        let reserved = s.read().field("reserved")?;
+        let length: u32 = s.read().field("length")?;
+        s.set_len(length as usize).field("length")?;
+        let language = s.read().field("language")?;
+        let startCharCode = s.read().field("startCharCode")?;
+        let numChars: u32 = s.read().field("numChars")?;
+        let glyphIdArray = s.read_lazy_array(numChars as usize).field("glyphIdArray")?;
+        Ok(Self {
+            format,
+            reserved,
+            length,
+            language,
+            startCharCode,
+            numChars,
+            glyphIdArray,
+        })
+    }
+
+    pub fn get_glyph_id_by_code(&self, code: u32) -> Option<u16> {
+        match code.checked_sub(self.startCharCode) {
+            Some(index) if index < self.numChars => self.glyphIdArray.get(index as usize),
+            _ => Some(0),
+        }
+    }
+}
+
+// format 12: Unicode の全範囲を表せる表．BMP 外の文字を含むフォントで使われる．
+#[allow(non_snake_case)]
+pub struct CmapSubtableFormat12<'a> {
+    pub format: uint16,                            // Subtable format; set to 12.
+    pub reserved: uint16,                          // Reserved; set to 0
+    pub length: uint32,    // Byte length of this subtable (including the header)
+    pub language: uint32, // For requirements on use of the language field, see “Use of the language field in 'cmap' subtables” in this document.
+    pub numGroups: uint32, // Number of groupings which follow
+    pub groups: LazyArray<'a, SequentialMapGroup>, // [numGroups] Array of SequentialMapGroup records.
+}
+
+impl<'a> CmapSubtableFormat12<'a> {
+    #[allow(non_snake_case)]
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let format = s.read().field("format")?;
+        let reserved = s.read().field("reserved")?;
+        let length: u32 = s.read().field("length")?;
+        s.set_len(length as usize).field("length")?;
+        let language = s.read().field("language")?;
+        let numGroups: u32 = s.read().field("numGroups")?;
+        let groups = s.read_lazy_array(numGroups as usize).field("groups")?;
+        Ok(Self {
+            format,
+            reserved,
+            length,
+            language,
+            numGroups,
+            groups,
+        })
+    }
+
+    pub fn get_glyph_id_by_code(&self, code: u32) -> Option<u16> {
+        get_group_glyph_id(&self.groups, code)
+    }
+}
+
+// format 13: 多くの文字を同じ glyph に対応させる表 (Last Resort フォントなど)．
+#[allow(non_snake_case)]
+pub struct CmapSubtableFormat13<'a> {
+    pub format: uint16,                          // Subtable format; set to 13.
+    pub reserved: uint16,                        // Reserved; set to 0
+    pub length: uint32,    // Byte length of this subtable (including the header)
+    pub language: uint32, // For requirements on use of the language field, see “Use of the language field in 'cmap' subtables” in this document.
+    pub numGroups: uint32, // Number of groupings which follow
+    pub groups: LazyArray<'a, ConstantMapGroup>, // [numGroups] Array of ConstantMapGroup records.
+}
+
+impl<'a> CmapSubtableFormat13<'a> {
+    #[allow(non_snake_case)]
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let format = s.read().field("format")?;
+        let reserved = s.read().field("reserved")?;
+        let length: u32 = s.read().field("length")?;
+        s.set_len(length as usize).field("length")?;
+        let language = s.read().field("language")?;
+        let numGroups: u32 = s.read().field("numGroups")?;
+        let groups = s.read_lazy_array(numGroups as usize).field("groups")?;
+        Ok(Self {
+            format,
+            reserved,
+            length,
+            language,
+            numGroups,
+            groups,
+        })
+    }
+
+    pub fn get_glyph_id_by_code(&self, code: u32) -> Option<u16> {
+        get_group_glyph_id(&self.groups, code)
+    }
+}
+
+// format 14 で (文字, 異体字セレクタ) を引いた結果．
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VariantGlyph {
+    Default,    // Default UVS: Unicode の subtable で文字だけを引いた glyph を使う．
+    Glyph(u16), // Non-Default UVS: 指定された glyph を使う．
+}
+
+// format 14: Unicode Variation Sequences．
+#[allow(non_snake_case)]
+pub struct CmapSubtableFormat14<'a> {
+    data: &'a [u8],
+    pub format: uint16,                // Subtable format. Set to 14.
+    pub length: uint32,                // Byte length of this subtable (including this header)
+    pub numVarSelectorRecords: uint32, // Number of variation Selector Records
+    pub varSelector: LazyArray<'a, VariationSelectorRecord>, // [numVarSelectorRecords] Array of VariationSelector records.
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Copy)]
+pub struct VariationSelectorRecord {
+    pub varSelector: Uint24,           // Variation selector
+    pub defaultUVSOffset: Offset32, // Offset from the start of the format 14 subtable to Default UVS Table. May be 0.
+    pub nonDefaultUVSOffset: Offset32, // Offset from the start of the format 14 subtable to Non-Default UVS Table. May be 0.
+}
+
+impl FromData for VariationSelectorRecord {
+    const SIZE: usize = 3 + 4 + 4;
+    fn parse(data: &[u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        Ok(Self {
+            varSelector: s.read().field("varSelector")?,
+            defaultUVSOffset: s.read().field("defaultUVSOffset")?,
+            nonDefaultUVSOffset: s.read().field("nonDefaultUVSOffset")?,
+        })
+    }
+}
+
+#[allow(non_snake_case)]
+pub struct DefaultUvsTable<'a> {
+    pub numUnicodeValueRanges: uint32, // Number of Unicode character ranges.
+    pub ranges: LazyArray<'a, UnicodeRange>, // [numUnicodeValueRanges] Array of UnicodeRange records.
+}
+
+impl<'a> DefaultUvsTable<'a> {
+    #[allow(non_snake_case)]
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let numUnicodeValueRanges: u32 = s.read().field("numUnicodeValueRanges")?;
+        let ranges = s
+            .read_lazy_array(numUnicodeValueRanges as usize)
+            .field("ranges")?;
+        Ok(Self {
+            numUnicodeValueRanges,
+            ranges,
+        })
+    }
+
+    pub fn contains(&self, code: u32) -> bool {
+        self.ranges
+            .binary_search_by(|range| {
+                let start = range.startUnicodeValue.0;
+                if start + (range.additionalCount as u32) < code {
+                    Ordering::Less
+                } else if code < start {
+                    Ordering::Greater
                 } else {
-                    let gid_array_index_from_id_range_offset = id_range_offset as usize / 2;
-                    let gid_array_start_from_id_range_offset = self.idRangeOffsets.len() - i;
-                    let gid_array_index =
-                        gid_array_index_from_id_range_offset - gid_array_start_from_id_range_offset;
-                    let delta = (code_point - start_code_point) as usize;
-                    let glyph_id_array_index = gid_array_index + delta;
-                    *self.glyphIdArray.get(glyph_id_array_index).unwrap()
-                };
-                map.push((char::from_u32(code_point as u32).unwrap(), glyph_id));
+                    Ordering::Equal
+                }
+            })
+            .is_some()
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Copy)]
+pub struct UnicodeRange {
+    pub startUnicodeValue: Uint24, // First value in this range
+    pub additionalCount: uint8,    // Number of additional values in this range
+}
+
+impl FromData for UnicodeRange {
+    const SIZE: usize = 3 + 1;
+    fn parse(data: &[u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        Ok(Self {
+            startUnicodeValue: s.read().field("startUnicodeValue")?,
+            additionalCount: s.read().field("additionalCount")?,
+        })
+    }
+}
+
+#[allow(non_snake_case)]
+pub struct NonDefaultUvsTable<'a> {
+    pub numUVSMappings: uint32, // Number of UVS Mappings that follow
+    pub uvsMappings: LazyArray<'a, UvsMapping>, // [numUVSMappings] Array of UVSMapping records.
+}
+
+impl<'a> NonDefaultUvsTable<'a> {
+    #[allow(non_snake_case)]
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let numUVSMappings: u32 = s.read().field("numUVSMappings")?;
+        let uvsMappings = s
+            .read_lazy_array(numUVSMappings as usize)
+            .field("uvsMappings")?;
+        Ok(Self {
+            numUVSMappings,
+            uvsMappings,
+        })
+    }
+
+    pub fn get_glyph_id(&self, code: u32) -> Option<u16> {
+        self.uvsMappings
+            .binary_search_by(|mapping| mapping.unicodeValue.0.cmp(&code))
+            .map(|(_, mapping)| mapping.glyphID)
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Copy)]
+pub struct UvsMapping {
+    pub unicodeValue: Uint24, // Base Unicode value of the UVS
+    pub glyphID: uint16,      // Glyph ID of the UVS
+}
+
+impl FromData for UvsMapping {
+    const SIZE: usize = 3 + 2;
+    fn parse(data: &[u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        Ok(Self {
+            unicodeValue: s.read().field("unicodeValue")?,
+            glyphID: s.read().field("glyphID")?,
+        })
+    }
+}
+
+impl<'a> CmapSubtableFormat14<'a> {
+    #[allow(non_snake_case)]
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let format = s.read().field("format")?;
+        let length: u32 = s.read().field("length")?;
+        let data = data
+            .get(..length as usize)
+            .ok_or(Error::eof(data.len()))
+            .field("length")?;
+        let mut s = Stream::new(data);
+        s.set_offset(6);
+        let numVarSelectorRecords: u32 = s.read().field("numVarSelectorRecords")?;
+        let varSelector = s
+            .read_lazy_array(numVarSelectorRecords as usize)
+            .field("varSelector")?;
+        Ok(Self {
+            data,
+            format,
+            length,
+            numVarSelectorRecords,
+            varSelector,
+        })
+    }
+
+    pub fn get_default_uvs_table(
+        &self,
+        record: &VariationSelectorRecord,
+    ) -> Result<Option<DefaultUvsTable<'a>>> {
+        parse_optional_subtable(self.data, record.defaultUVSOffset, DefaultUvsTable::parse)
+            .field("defaultUVSOffset")
+    }
+
+    pub fn get_non_default_uvs_table(
+        &self,
+        record: &VariationSelectorRecord,
+    ) -> Result<Option<NonDefaultUvsTable<'a>>> {
+        parse_optional_subtable(
+            self.data,
+            record.nonDefaultUVSOffset,
+            NonDefaultUvsTable::parse,
+        )
+        .field("nonDefaultUVSOffset")
+    }
+
+    // 異体字シーケンスが登録されていない場合は None を返す．
+    pub fn get_variant_glyph_id(&self, code_point: char, selector: char) -> Option<VariantGlyph> {
+        let (code, selector) = (code_point as u32, selector as u32);
+        let (_, record) = self
+            .varSelector
+            .binary_search_by(|record| record.varSelector.0.cmp(&selector))?;
+        if let Ok(Some(table)) = self.get_default_uvs_table(&record) {
+            if table.contains(code) {
+                return Some(VariantGlyph::Default);
             }
         }
-        map
+        let table = self.get_non_default_uvs_table(&record).ok()??;
+        table.get_glyph_id(code).map(VariantGlyph::Glyph)
+    }
+}
+
+// 区間内の glyph id の求め方．group を持つ format では文字コードごとに group を読み直さない．
+#[derive(Clone, Copy)]
+enum SegmentGlyphs {
+    Sequential {
+        start_code: u32,
+        start_glyph_id: u32,
+    },
+    Constant(u32),
+    Lookup,
+}
+
+impl From<SequentialMapGroup> for SegmentGlyphs {
+    fn from(group: SequentialMapGroup) -> Self {
+        Self::Sequential {
+            start_code: group.startCharCode,
+            start_glyph_id: group.startGlyphID,
+        }
+    }
+}
+
+impl From<ConstantMapGroup> for SegmentGlyphs {
+    fn from(group: ConstantMapGroup) -> Self {
+        Self::Constant(group.glyphID)
+    }
+}
+
+// CmapSubtable::iter の戻り値．区間ごとに文字コードを一つずつ引く．
+pub struct CmapIter<'b, 'a> {
+    subtable: &'b CmapSubtable<'a>,
+    segment: usize,
+    glyphs: SegmentGlyphs,
+    code: u32,      // 次に引く文字コード．
+    end: u32,       // 今の区間の最後の文字コード．
+    next_code: u32, // これより前の文字コードは出力済み．
+}
+
+impl<'b, 'a> CmapIter<'b, 'a> {
+    fn get_glyph_id(&self, code: u32) -> Option<u16> {
+        match self.glyphs {
+            SegmentGlyphs::Sequential {
+                start_code,
+                start_glyph_id,
+            } => u16::try_from(start_glyph_id.checked_add(code - start_code)?).ok(),
+            SegmentGlyphs::Constant(glyph_id) => u16::try_from(glyph_id).ok(),
+            SegmentGlyphs::Lookup => self.subtable.get_segment_glyph_id(self.segment - 1, code),
+        }
+    }
+}
+
+impl<'b, 'a> Iterator for CmapIter<'b, 'a> {
+    type Item = (u32, u16);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.code <= self.end {
+                let code = self.code;
+                self.code += 1;
+                match self.get_glyph_id(code) {
+                    Some(0) | None => continue,
+                    Some(glyph_id) => return Some((code, glyph_id)),
+                }
+            }
+            if self.segment >= self.subtable.get_segment_count() {
+                return None;
+            }
+            self.segment += 1;
+            let i = self.segment - 1;
+            let (Some((start, end)), Some(glyphs)) = (
+                self.subtable.get_segment(i),
+                self.subtable.get_segment_glyphs(i),
+            ) else {
+                continue;
+            };
+            let (start, end) = (start.max(self.next_code), end.min(char::MAX as u32));
+            if start <= end {
+                self.next_code = end + 1;
+                // glyph 0 だけの区間は飛ばす．
+                if let SegmentGlyphs::Constant(0) = glyphs {
+                    continue;
+                }
+                (self.glyphs, self.code, self.end) = (glyphs, start, end);
+            }
+        }
+    }
+}
+
+// (文字コード, glyph id) の列を，文字コードと glyph id がともに連続する区間にまとめる．
+pub struct CmapRanges<I> {
+    iter: I,
+    current: Option<(u32, u32, u16)>, // (最初の文字コード, 最後の文字コード, 最初の glyph id)
+}
+
+impl<I: Iterator<Item = (u32, u16)>> CmapRanges<I> {
+    pub fn new(iter: I) -> Self {
+        Self {
+            iter,
+            current: None,
+        }
+    }
+}
+
+impl<I: Iterator<Item = (u32, u16)>> Iterator for CmapRanges<I> {
+    type Item = (RangeInclusive<u32>, u16);
+    fn next(&mut self) -> Option<Self::Item> {
+        for (code, glyph_id) in self.iter.by_ref() {
+            let Some((start, end, start_glyph_id)) = self.current else {
+                self.current = Some((code, code, glyph_id));
+                continue;
+            };
+            let offset = end - start + 1;
+            let continues = code == end + 1
+                && (start_glyph_id as u32).checked_add(offset) == Some(glyph_id as u32);
+            if continues {
+                self.current = Some((start, code, start_glyph_id));
+            } else {
+                self.current = Some((code, code, glyph_id));
+                return Some((start..=end, start_glyph_id));
+            }
+        }
+        let (start, end, start_glyph_id) = self.current.take()?;
+        Some((start..=end, start_glyph_id))
     }
 }
 
@@ -231,14 +1120,232 @@ pub struct CmapTable<'a> {
 }
 
 impl<'a> CmapTable<'a> {
-    pub fn parse(data: &'a [u8]) -> Option<Self> {
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
         let header = CmapHeader::parse(data)?;
-        Some(Self { data, header })
+        Ok(Self { data, header })
+    }
+
+    pub fn get_subtable(&self, encoding_record: &EncodingRecord) -> Result<CmapSubtable<'a>> {
+        parse_subtable(
+            self.data,
+            encoding_record.subtableOffset,
+            CmapSubtable::parse,
+        )
+        .field("subtableOffset")
+    }
+}
+
+// Unicode の文字から glyph を引くための，最適な subtable を選んだ cmap．
+pub struct Charmap<'a> {
+    platform_id: u16,
+    encoding_id: u16,
+    encoding: CmapEncoding,
+    subtable: CmapSubtable<'a>,
+    variation_subtable: Option<CmapSubtableFormat14<'a>>,
+}
+
+impl<'a> Charmap<'a> {
+    // (platformID, encodingID) の優先順位．
+    // (0, 0..=2) は古い Unicode のバージョンを表すが，BMP の subtable として扱う．
+    // (0, 6) は format 13 の full repertoire で，ラストリゾートフォントのように多くの文字を同じ glyph に対応させる．
+    // Unicode の subtable が無い場合は，文字をそれぞれのエンコーディングに変換して引く．
+    const PRIORITY: [(u16, u16); 19] = [
+        (3, 10),
+        (0, 4),
+        (0, 6),
+        (0, 3),
+        (3, 1),
+        (0, 2),
+        (0, 1),
+        (0, 0),
+        (3, 2),
+        (3, 3),
+        (3, 4),
+        (3, 5),
+        (3, 6),
+        (1, 1),
+        (1, 2),
+        (1, 3),
+        (1, 25),
+        (3, 0),
+        (1, 0),
+    ];
+
+    // 使える subtable が無い場合は None を返す．壊れた subtable は飛ばして次の候補を使う．
+    pub fn new(cmap: &CmapTable<'a>) -> Option<Self> {
+        let records = &cmap.header.encodingRecords;
+        let find = |platform_id: u16, encoding_id: u16| {
+            records.iter().find_map(|record| {
+                if (record.platformID, record.encodingID) != (platform_id, encoding_id) {
+                    return None;
+                }
+                cmap.get_subtable(record).ok()
+            })
+        };
+        let (platform_id, encoding_id, subtable) =
+            Self::PRIORITY
+                .iter()
+                .find_map(|&(platform_id, encoding_id)| {
+                    let subtable = find(platform_id, encoding_id)
+                        .filter(|subtable| !matches!(subtable, CmapSubtable::Format14(_)))?;
+                    Some((platform_id, encoding_id, subtable))
+                })?;
+        let variation_subtable = match find(0, 5) {
+            Some(CmapSubtable::Format14(subtable)) => Some(subtable),
+            _ => None,
+        };
+        Some(Self {
+            platform_id,
+            encoding_id,
+            encoding: CmapEncoding::new(platform_id, encoding_id),
+            subtable,
+            variation_subtable,
+        })
+    }
+
+    // 選ばれた subtable の (platformID, encodingID)．
+    pub fn get_encoding(&self) -> (u16, u16) {
+        (self.platform_id, self.encoding_id)
+    }
+
+    pub fn get_cmap_encoding(&self) -> CmapEncoding {
+        self.encoding
+    }
+
+    pub fn get_subtable(&self) -> &CmapSubtable<'a> {
+        &self.subtable
+    }
+
+    pub fn get_variation_subtable(&self) -> Option<&CmapSubtableFormat14<'a>> {
+        self.variation_subtable.as_ref()
+    }
+
+    // 対応する glyph が無い場合 (.notdef) は None を返す．
+    pub fn map(&self, c: char) -> Option<u16> {
+        let code = self.encoding.encode(c)?;
+        let glyph_id = self.subtable.get_glyph_id_by_code(code);
+        // Symbol の subtable は U+F020..=U+F0FF に記号を置くので，1 バイトの文字はそこも探す．
+        match glyph_id {
+            Some(0) | None if self.encoding == CmapEncoding::Symbol && code < 0x100 => self
+                .subtable
+                .get_glyph_id_by_code(0xF000 | code)
+                .filter(|&glyph_id| glyph_id != 0),
+            _ => glyph_id.filter(|&glyph_id| glyph_id != 0),
+        }
+    }
+
+    // 異体字シーケンスが登録されていない場合は，selector を無視して基底文字の glyph を返す．
+    pub fn map_with_selector(&self, c: char, selector: char) -> Option<u16> {
+        let variant = self
+            .variation_subtable
+            .as_ref()
+            .and_then(|subtable| subtable.get_variant_glyph_id(c, selector));
+        match variant {
+            Some(VariantGlyph::Glyph(glyph_id)) => Some(glyph_id),
+            Some(VariantGlyph::Default) | None => self.map(c),
+        }
+    }
+
+    // glyph が割り当てられている (文字, glyph id) を文字コードの昇順に返す．
+    // Unicode 以外のエンコーディングでは，文字の順にはならない．
+    pub fn iter(&self) -> impl Iterator<Item = (char, u16)> + '_ {
+        self.subtable
+            .iter()
+            .filter_map(|(code, glyph_id)| Some((self.encoding.decode(code)?, glyph_id)))
+    }
+
+    // 文字と glyph id がともに連続している区間を (文字の範囲, 先頭の glyph id) として返す．
+    pub fn ranges(&self) -> CmapRanges<impl Iterator<Item = (u32, u16)> + '_> {
+        CmapRanges::new(self.iter().map(|(c, glyph_id)| (c as u32, glyph_id)))
+    }
+
+    // glyph が割り当てられている文字を返す．
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.iter().map(|(c, _)| c)
+    }
+}
+
+impl<'a> Charmap<'a> {
+    pub fn build_reverse(&self) -> ReverseCmap {
+        let mut chars: Vec<_> = self.iter().map(|(c, glyph_id)| (glyph_id, c)).collect();
+        chars.sort_unstable();
+        chars.dedup();
+
+        let mut variants = Vec::new();
+        if let Some(subtable) = &self.variation_subtable {
+            for record in &subtable.varSelector {
+                let Some(selector) = char::from_u32(record.varSelector.0) else {
+                    continue;
+                };
+                if let Ok(Some(table)) = subtable.get_default_uvs_table(&record) {
+                    for range in &table.ranges {
+                        let start = range.startUnicodeValue.0;
+                        for code in start..=start + range.additionalCount as u32 {
+                            let Some(c) = char::from_u32(code) else {
+                                continue;
+                            };
+                            if let Some(glyph_id) = self.map(c) {
+                                variants.push((glyph_id, c, selector));
+                            }
+                        }
+                    }
+                }
+                if let Ok(Some(table)) = subtable.get_non_default_uvs_table(&record) {
+                    for mapping in &table.uvsMappings {
+                        if let Some(c) = char::from_u32(mapping.unicodeValue.0) {
+                            variants.push((mapping.glyphID, c, selector));
+                        }
+                    }
+                }
+            }
+        }
+        variants.sort_unstable();
+        variants.dedup();
+        ReverseCmap { chars, variants }
+    }
+}
+
+// glyph id から，その glyph に対応する文字を引くための表．
+pub struct ReverseCmap {
+    chars: Vec<(u16, char)>,          // (glyph id, 文字) の昇順．
+    variants: Vec<(u16, char, char)>, // (glyph id, 基底文字, 異体字セレクタ) の昇順．
+}
+
+// glyph_id の要素だけを取り出す．
+fn equal_range<T>(items: &[T], glyph_id: u16, key: impl Fn(&T) -> u16) -> &[T] {
+    let start = items.partition_point(|item| key(item) < glyph_id);
+    let end = items.partition_point(|item| key(item) <= glyph_id);
+    &items[start..end]
+}
+
+impl ReverseCmap {
+    // 使える subtable が無い場合は None を返す．
+    pub fn new(cmap: &CmapTable) -> Option<Self> {
+        Charmap::new(cmap).map(|charmap| charmap.build_reverse())
+    }
+
+    // glyph_id に対応する文字を昇順に返す．
+    pub fn get_chars(&self, glyph_id: u16) -> impl Iterator<Item = char> + '_ {
+        equal_range(&self.chars, glyph_id, |&(glyph_id, _)| glyph_id)
+            .iter()
+            .map(|&(_, c)| c)
+    }
+
+    // 最も小さい文字を代表として返す．ToUnicode などで一つだけ必要な場合に使う．
+    pub fn get_char(&self, glyph_id: u16) -> Option<char> {
+        self.get_chars(glyph_id).next()
+    }
+
+    // glyph_id になる異体字シーケンス (基底文字, 異体字セレクタ) を返す．
+    // Default UVS のシーケンスは基底文字の glyph として含む．
+    pub fn get_variants(&self, glyph_id: u16) -> impl Iterator<Item = (char, char)> + '_ {
+        equal_range(&self.variants, glyph_id, |&(glyph_id, _, _)| glyph_id)
+            .iter()
+            .map(|&(_, c, selector)| (c, selector))
     }
 
-    pub fn get_subtable(&self, encoding_record: &EncodingRecord) -> Option<CmapSubtable> {
-        let offset = encoding_record.subtableOffset as usize;
-        let data = self.data.get(offset..)?;
-        CmapSubtable::parse(data)
+    // 文字が対応している (glyph id, 文字) を glyph id の昇順に返す．
+    pub fn iter(&self) -> impl Iterator<Item = (u16, char)> + '_ {
+        self.chars.iter().copied()
     }
 }
@@ -0,0 +1,780 @@
+// cmap に登録されている文字が，Unicode のブロック，用字，文字集合をどれだけ満たしているかを調べる．
+// OS/2 の ulUnicodeRange と ulCodePageRange の申告が実際の cmap と食い違っていないかも調べる．
+use core::fmt;
+use std::{io, path::Path, sync::OnceLock};
+
+use encoding_rs::Encoding;
+
+use crate::{
+    cmap::Charmap,
+    encoding::CmapEncoding,
+    error::{Error, Result},
+    os_2::OS2Table,
+    unicode_data::{BLOCKS, SCRIPTS},
+};
+
+const UNKNOWN_SCRIPT: &str = "Unknown";
+
+// ブロックや用字などの名前と，そこに含まれる文字の数．
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoverageCount {
+    pub name: &'static str,
+    pub covered: u32,
+    pub total: u32,
+}
+
+impl CoverageCount {
+    pub fn missing(&self) -> u32 {
+        self.total - self.covered
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.covered == self.total
+    }
+}
+
+pub struct Coverage {
+    chars: Vec<char>, // 昇順で重複なし．
+}
+
+impl Coverage {
+    pub fn new(chars: impl IntoIterator<Item = char>) -> Self {
+        let mut chars: Vec<_> = chars.into_iter().collect();
+        chars.sort_unstable();
+        chars.dedup();
+        Self { chars }
+    }
+
+    // glyph が割り当てられている (.notdef 以外の) 文字を集める．
+    pub fn from_charmap(charmap: &Charmap) -> Self {
+        Self::new(charmap.chars())
+    }
+
+    pub fn chars(&self) -> &[char] {
+        &self.chars
+    }
+
+    pub fn len(&self) -> usize {
+        self.chars.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    pub fn contains(&self, c: char) -> bool {
+        self.chars.binary_search(&c).is_ok()
+    }
+
+    // first..=last の範囲にある文字の数．
+    fn count_range(&self, first: u32, last: u32) -> u32 {
+        let start = self.chars.partition_point(|&c| (c as u32) < first);
+        let end = self.chars.partition_point(|&c| (c as u32) <= last);
+        end.saturating_sub(start) as u32
+    }
+
+    // 文字が一つでも含まれるブロックを，ブロックの順に返す．
+    // total はブロックのうち文字が割り当てられている数で，私用領域はすべてを数える．
+    pub fn blocks(&self) -> Vec<CoverageCount> {
+        BLOCKS
+            .iter()
+            .map(|&(first, last, name)| self.count_block(first, last, name))
+            .filter(|count| count.covered > 0)
+            .collect()
+    }
+
+    pub fn get_block_coverage(&self, name: &str) -> Option<CoverageCount> {
+        let &(first, last, name) = BLOCKS.iter().find(|block| block.2 == name)?;
+        Some(self.count_block(first, last, name))
+    }
+
+    fn count_block(&self, first: u32, last: u32, name: &'static str) -> CoverageCount {
+        let mut count = CoverageCount {
+            name,
+            covered: 0,
+            total: 0,
+        };
+        if name.contains("Private Use") {
+            count.covered = self.count_range(first, last);
+            count.total = last - first + 1;
+            return count;
+        }
+        // 割り当てられていない文字は数えない．
+        let start = SCRIPTS.partition_point(|range| range.1 < first);
+        for &(script_first, script_last, _) in
+            SCRIPTS[start..].iter().take_while(|range| range.0 <= last)
+        {
+            let (first, last) = (script_first.max(first), script_last.min(last));
+            count.covered += self.count_range(first, last);
+            count.total += last - first + 1;
+        }
+        count
+    }
+
+    // 文字が一つでも含まれる用字を，表に最初に現れる順に返す．Common と Inherited も一つの用字として数える．
+    // 表に無い文字の Unknown は最後に置く．
+    pub fn scripts(&self) -> Vec<CoverageCount> {
+        let mut counts: Vec<CoverageCount> = Vec::new();
+        for &(first, last, name) in SCRIPTS {
+            let index = match counts.iter().position(|count| count.name == name) {
+                Some(index) => index,
+                None => {
+                    counts.push(CoverageCount {
+                        name,
+                        covered: 0,
+                        total: 0,
+                    });
+                    counts.len() - 1
+                }
+            };
+            counts[index].covered += self.count_range(first, last);
+            counts[index].total += last - first + 1;
+        }
+        counts.push(self.count_unknown());
+        counts.retain(|count| count.covered > 0);
+        counts
+    }
+
+    // Scripts.txt に無い文字 (未割り当て，私用，非文字) は Unknown (Zzzz) として数える．
+    // サロゲートは char にならないので total に含めない．
+    fn count_unknown(&self) -> CoverageCount {
+        let (covered, total) =
+            SCRIPTS
+                .iter()
+                .fold((0, 0), |(covered, total), &(first, last, _)| {
+                    (
+                        covered + self.count_range(first, last),
+                        total + last - first + 1,
+                    )
+                });
+        let code_points = char::MAX as u32 + 1 - (0xDFFF - 0xD800 + 1);
+        CoverageCount {
+            name: UNKNOWN_SCRIPT,
+            covered: self.chars.len() as u32 - covered,
+            total: code_points - total,
+        }
+    }
+
+    pub fn get_script_coverage(&self, name: &str) -> Option<CoverageCount> {
+        if name == UNKNOWN_SCRIPT {
+            return Some(self.count_unknown());
+        }
+        let mut ranges = SCRIPTS.iter().filter(|range| range.2 == name).peekable();
+        let name = ranges.peek()?.2;
+        let mut count = CoverageCount {
+            name,
+            covered: 0,
+            total: 0,
+        };
+        for &(first, last, _) in ranges {
+            count.covered += self.count_range(first, last);
+            count.total += last - first + 1;
+        }
+        Some(count)
+    }
+
+    // 文字集合のうち cmap に無い文字を返す．
+    pub fn compare<'b>(&self, set: &'b CharacterSet) -> SetCoverage<'b> {
+        let missing: Vec<_> = set
+            .chars
+            .iter()
+            .copied()
+            .filter(|&c| !self.contains(c))
+            .collect();
+        SetCoverage {
+            name: &set.name,
+            covered: (set.chars.len() - missing.len()) as u32,
+            total: set.chars.len() as u32,
+            missing,
+        }
+    }
+
+    // 調べられるコードページについて，その文字 (ASCII 以外) をどれだけ含むかを返す．
+    pub fn code_pages(&self) -> Vec<(u8, CoverageCount)> {
+        CODE_PAGES
+            .iter()
+            .zip(code_page_chars())
+            .map(|(&(bit, name, _), chars)| {
+                let covered = chars.iter().filter(|&&c| self.contains(c)).count();
+                let count = CoverageCount {
+                    name,
+                    covered: covered as u32,
+                    total: chars.len() as u32,
+                };
+                (bit, count)
+            })
+            .collect()
+    }
+
+    // OS/2 のビットと cmap が食い違っているものを返す．
+    // ulUnicodeRange は範囲に文字が一つでもあれば対応しているとみなし，
+    // ulCodePageRange はコードページの文字をすべて含む場合に対応しているとみなす．
+    pub fn check_os2(&self, os2: &OS2Table) -> Vec<Os2Issue> {
+        let unicode_range = [
+            os2.ulUnicodeRange1,
+            os2.ulUnicodeRange2,
+            os2.ulUnicodeRange3,
+            os2.ulUnicodeRange4,
+        ];
+        let mut issues = Vec::new();
+        for (bit, &(name, ranges)) in UNICODE_RANGES.iter().enumerate() {
+            let bit = bit as u8;
+            let claimed = unicode_range[bit as usize / 32] & (1 << (bit % 32)) != 0;
+            let covered = if bit == NON_PLANE_0_BIT {
+                self.count_range(0x10000, char::MAX as u32)
+            } else {
+                ranges
+                    .iter()
+                    .map(|&(first, last)| self.count_range(first, last))
+                    .sum()
+            };
+            match (claimed, covered) {
+                (true, 0) => issues.push(Os2Issue::UnicodeRangeNotCovered { bit, name }),
+                (false, 1..) => {
+                    issues.push(Os2Issue::UnicodeRangeNotClaimed { bit, name, covered })
+                }
+                _ => {}
+            }
+        }
+
+        // version 0 のテーブルには ulCodePageRange が無い．
+        if let (Some(range1), Some(range2)) = (os2.ulCodePageRange1, os2.ulCodePageRange2) {
+            let code_page_range = (range2 as u64) << 32 | range1 as u64;
+            for (bit, count) in self.code_pages() {
+                let claimed = code_page_range & (1 << bit) != 0;
+                match (claimed, count.is_complete()) {
+                    (true, false) => issues.push(Os2Issue::CodePageIncomplete {
+                        bit,
+                        name: count.name,
+                        missing: count.missing(),
+                        total: count.total,
+                    }),
+                    (false, true) => issues.push(Os2Issue::CodePageNotClaimed {
+                        bit,
+                        name: count.name,
+                    }),
+                    _ => {}
+                }
+            }
+        }
+        issues
+    }
+}
+
+// 名前の付いた文字の集合．
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharacterSet {
+    pub name: String,
+    chars: Vec<char>, // 昇順で重複なし．
+}
+
+impl CharacterSet {
+    pub fn new(name: impl Into<String>, chars: impl IntoIterator<Item = char>) -> Self {
+        let mut chars: Vec<_> = chars.into_iter().collect();
+        chars.sort_unstable();
+        chars.dedup();
+        Self {
+            name: name.into(),
+            chars,
+        }
+    }
+
+    pub fn chars(&self) -> &[char] {
+        &self.chars
+    }
+
+    // 次のような書式の文字集合を読み込む．# から行末まではコメント．
+    //
+    //   [Vietnamese]
+    //   U+0041..U+005A 0061..007A
+    //   00C0-00C3 1EA0..1EF9
+    //
+    // [名前] の行で集合が始まり，続く行に 16 進数の文字コードか範囲を空白で区切って並べる．
+    // エラーの offset は text の先頭からのバイト位置．
+    pub fn parse_sets(text: &str) -> Result<Vec<Self>> {
+        let mut sets: Vec<(String, Vec<char>)> = Vec::new();
+        let mut line_offset = 0;
+        for line in text.split_inclusive('\n') {
+            let content = line.split('#').next().unwrap();
+            let trimmed = content.trim();
+            let offset = line_offset + (content.len() - content.trim_start().len());
+            line_offset += line.len();
+            if trimmed.is_empty() {
+                continue;
+            }
+            if let Some(name) = trimmed
+                .strip_prefix('[')
+                .and_then(|name| name.strip_suffix(']'))
+            {
+                sets.push((name.trim().to_string(), Vec::new()));
+                continue;
+            }
+            let Some((_, chars)) = sets.last_mut() else {
+                return Err(Error::invalid(offset).with_field("characterSetName"));
+            };
+            for token in trimmed.split_whitespace() {
+                let token_offset = offset + (token.as_ptr() as usize - trimmed.as_ptr() as usize);
+                let (first, last) = parse_range(token)
+                    .ok_or_else(|| Error::invalid(token_offset).with_field("characterSetRange"))?;
+                chars.extend((first..=last).filter_map(char::from_u32));
+            }
+        }
+        Ok(sets
+            .into_iter()
+            .map(|(name, chars)| Self::new(name, chars))
+            .collect())
+    }
+
+    pub fn read_sets(path: impl AsRef<Path>) -> io::Result<Vec<Self>> {
+        let text = std::fs::read_to_string(path)?;
+        Self::parse_sets(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+fn parse_code_point(s: &str) -> Option<u32> {
+    let s = s
+        .strip_prefix("U+")
+        .or_else(|| s.strip_prefix("u+"))
+        .unwrap_or(s);
+    if s.is_empty() || s.len() > 6 {
+        return None;
+    }
+    u32::from_str_radix(s, 16)
+        .ok()
+        .filter(|&code| code <= char::MAX as u32)
+}
+
+// "0041", "0041..005A", "0041-005A" のいずれか．
+fn parse_range(token: &str) -> Option<(u32, u32)> {
+    let (first, last) = token
+        .split_once("..")
+        .or_else(|| token.split_once('-'))
+        .unwrap_or((token, token));
+    let (first, last) = (parse_code_point(first)?, parse_code_point(last)?);
+    (first <= last).then_some((first, last))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetCoverage<'a> {
+    pub name: &'a str,
+    pub covered: u32,
+    pub total: u32,
+    pub missing: Vec<char>,
+}
+
+impl SetCoverage<'_> {
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+// OS/2 と cmap の食い違い．
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Os2Issue {
+    UnicodeRangeNotCovered {
+        bit: u8,
+        name: &'static str,
+    }, // ビットが立っているが，範囲の文字が無い．
+    UnicodeRangeNotClaimed {
+        bit: u8,
+        name: &'static str,
+        covered: u32,
+    }, // 範囲の文字があるが，ビットが立っていない．
+    CodePageIncomplete {
+        bit: u8,
+        name: &'static str,
+        missing: u32,
+        total: u32,
+    }, // ビットが立っているが，足りない文字がある．
+    CodePageNotClaimed {
+        bit: u8,
+        name: &'static str,
+    }, // 文字がすべてあるが，ビットが立っていない．
+}
+
+impl fmt::Display for Os2Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnicodeRangeNotCovered { bit, name } => {
+                write!(
+                    f,
+                    "ulUnicodeRange bit {} ({}) is set but not covered",
+                    bit, name
+                )
+            }
+            Self::UnicodeRangeNotClaimed { bit, name, covered } => write!(
+                f,
+                "ulUnicodeRange bit {} ({}) is not set but {} characters are covered",
+                bit, name, covered
+            ),
+            Self::CodePageIncomplete {
+                bit,
+                name,
+                missing,
+                total,
+            } => write!(
+                f,
+                "ulCodePageRange bit {} ({}) is set but {} of {} characters are missing",
+                bit, name, missing, total
+            ),
+            Self::CodePageNotClaimed { bit, name } => {
+                write!(
+                    f,
+                    "ulCodePageRange bit {} ({}) is not set but fully covered",
+                    bit, name
+                )
+            }
+        }
+    }
+}
+
+// bit 57 は BMP 外の文字が一つでもあれば立てる．
+const NON_PLANE_0_BIT: u8 = 57;
+
+// ulUnicodeRange の各ビットに対応する範囲．bit 123..=127 は予約．
+const UNICODE_RANGES: [(&str, &[(u32, u32)]); 123] = [
+    ("Basic Latin", &[(0x0000, 0x007F)]),
+    ("Latin-1 Supplement", &[(0x0080, 0x00FF)]),
+    ("Latin Extended-A", &[(0x0100, 0x017F)]),
+    ("Latin Extended-B", &[(0x0180, 0x024F)]),
+    (
+        "IPA Extensions",
+        &[(0x0250, 0x02AF), (0x1D00, 0x1D7F), (0x1D80, 0x1DBF)],
+    ),
+    (
+        "Spacing Modifier Letters",
+        &[(0x02B0, 0x02FF), (0xA700, 0xA71F)],
+    ),
+    (
+        "Combining Diacritical Marks",
+        &[(0x0300, 0x036F), (0x1DC0, 0x1DFF)],
+    ),
+    ("Greek and Coptic", &[(0x0370, 0x03FF)]),
+    ("Coptic", &[(0x2C80, 0x2CFF)]),
+    (
+        "Cyrillic",
+        &[
+            (0x0400, 0x04FF),
+            (0x0500, 0x052F),
+            (0x2DE0, 0x2DFF),
+            (0xA640, 0xA69F),
+        ],
+    ),
+    ("Armenian", &[(0x0530, 0x058F)]),
+    ("Hebrew", &[(0x0590, 0x05FF)]),
+    ("Vai", &[(0xA500, 0xA63F)]),
+    ("Arabic", &[(0x0600, 0x06FF), (0x0750, 0x077F)]),
+    ("NKo", &[(0x07C0, 0x07FF)]),
+    ("Devanagari", &[(0x0900, 0x097F)]),
+    ("Bengali", &[(0x0980, 0x09FF)]),
+    ("Gurmukhi", &[(0x0A00, 0x0A7F)]),
+    ("Gujarati", &[(0x0A80, 0x0AFF)]),
+    ("Oriya", &[(0x0B00, 0x0B7F)]),
+    ("Tamil", &[(0x0B80, 0x0BFF)]),
+    ("Telugu", &[(0x0C00, 0x0C7F)]),
+    ("Kannada", &[(0x0C80, 0x0CFF)]),
+    ("Malayalam", &[(0x0D00, 0x0D7F)]),
+    ("Thai", &[(0x0E00, 0x0E7F)]),
+    ("Lao", &[(0x0E80, 0x0EFF)]),
+    ("Georgian", &[(0x10A0, 0x10FF), (0x2D00, 0x2D2F)]),
+    ("Balinese", &[(0x1B00, 0x1B7F)]),
+    ("Hangul Jamo", &[(0x1100, 0x11FF)]),
+    (
+        "Latin Extended Additional",
+        &[(0x1E00, 0x1EFF), (0x2C60, 0x2C7F), (0xA720, 0xA7FF)],
+    ),
+    ("Greek Extended", &[(0x1F00, 0x1FFF)]),
+    ("General Punctuation", &[(0x2000, 0x206F), (0x2E00, 0x2E7F)]),
+    ("Superscripts And Subscripts", &[(0x2070, 0x209F)]),
+    ("Currency Symbols", &[(0x20A0, 0x20CF)]),
+    (
+        "Combining Diacritical Marks For Symbols",
+        &[(0x20D0, 0x20FF)],
+    ),
+    ("Letterlike Symbols", &[(0x2100, 0x214F)]),
+    ("Number Forms", &[(0x2150, 0x218F)]),
+    (
+        "Arrows",
+        &[
+            (0x2190, 0x21FF),
+            (0x27F0, 0x27FF),
+            (0x2900, 0x297F),
+            (0x2B00, 0x2BFF),
+        ],
+    ),
+    (
+        "Mathematical Operators",
+        &[
+            (0x2200, 0x22FF),
+            (0x2A00, 0x2AFF),
+            (0x27C0, 0x27EF),
+            (0x2980, 0x29FF),
+        ],
+    ),
+    ("Miscellaneous Technical", &[(0x2300, 0x23FF)]),
+    ("Control Pictures", &[(0x2400, 0x243F)]),
+    ("Optical Character Recognition", &[(0x2440, 0x245F)]),
+    ("Enclosed Alphanumerics", &[(0x2460, 0x24FF)]),
+    ("Box Drawing", &[(0x2500, 0x257F)]),
+    ("Block Elements", &[(0x2580, 0x259F)]),
+    ("Geometric Shapes", &[(0x25A0, 0x25FF)]),
+    ("Miscellaneous Symbols", &[(0x2600, 0x26FF)]),
+    ("Dingbats", &[(0x2700, 0x27BF)]),
+    ("CJK Symbols And Punctuation", &[(0x3000, 0x303F)]),
+    ("Hiragana", &[(0x3040, 0x309F)]),
+    ("Katakana", &[(0x30A0, 0x30FF), (0x31F0, 0x31FF)]),
+    ("Bopomofo", &[(0x3100, 0x312F), (0x31A0, 0x31BF)]),
+    ("Hangul Compatibility Jamo", &[(0x3130, 0x318F)]),
+    ("Phags-pa", &[(0xA840, 0xA87F)]),
+    ("Enclosed CJK Letters And Months", &[(0x3200, 0x32FF)]),
+    ("CJK Compatibility", &[(0x3300, 0x33FF)]),
+    ("Hangul Syllables", &[(0xAC00, 0xD7AF)]),
+    ("Non-Plane 0", &[]),
+    ("Phoenician", &[(0x10900, 0x1091F)]),
+    (
+        "CJK Unified Ideographs",
+        &[
+            (0x4E00, 0x9FFF),
+            (0x2E80, 0x2EFF),
+            (0x2F00, 0x2FDF),
+            (0x2FF0, 0x2FFF),
+            (0x3400, 0x4DBF),
+            (0x20000, 0x2A6DF),
+            (0x3190, 0x319F),
+        ],
+    ),
+    ("Private Use Area (plane 0)", &[(0xE000, 0xF8FF)]),
+    (
+        "CJK Strokes",
+        &[(0x31C0, 0x31EF), (0xF900, 0xFAFF), (0x2F800, 0x2FA1F)],
+    ),
+    ("Alphabetic Presentation Forms", &[(0xFB00, 0xFB4F)]),
+    ("Arabic Presentation Forms-A", &[(0xFB50, 0xFDFF)]),
+    ("Combining Half Marks", &[(0xFE20, 0xFE2F)]),
+    ("Vertical Forms", &[(0xFE10, 0xFE1F), (0xFE30, 0xFE4F)]),
+    ("Small Form Variants", &[(0xFE50, 0xFE6F)]),
+    ("Arabic Presentation Forms-B", &[(0xFE70, 0xFEFF)]),
+    ("Halfwidth And Fullwidth Forms", &[(0xFF00, 0xFFEF)]),
+    ("Specials", &[(0xFFF0, 0xFFFF)]),
+    ("Tibetan", &[(0x0F00, 0x0FFF)]),
+    ("Syriac", &[(0x0700, 0x074F)]),
+    ("Thaana", &[(0x0780, 0x07BF)]),
+    ("Sinhala", &[(0x0D80, 0x0DFF)]),
+    ("Myanmar", &[(0x1000, 0x109F)]),
+    (
+        "Ethiopic",
+        &[(0x1200, 0x137F), (0x1380, 0x139F), (0x2D80, 0x2DDF)],
+    ),
+    ("Cherokee", &[(0x13A0, 0x13FF)]),
+    ("Unified Canadian Aboriginal Syllabics", &[(0x1400, 0x167F)]),
+    ("Ogham", &[(0x1680, 0x169F)]),
+    ("Runic", &[(0x16A0, 0x16FF)]),
+    ("Khmer", &[(0x1780, 0x17FF), (0x19E0, 0x19FF)]),
+    ("Mongolian", &[(0x1800, 0x18AF)]),
+    ("Braille Patterns", &[(0x2800, 0x28FF)]),
+    ("Yi Syllables", &[(0xA000, 0xA48F), (0xA490, 0xA4CF)]),
+    (
+        "Tagalog",
+        &[
+            (0x1700, 0x171F),
+            (0x1720, 0x173F),
+            (0x1740, 0x175F),
+            (0x1760, 0x177F),
+        ],
+    ),
+    ("Old Italic", &[(0x10300, 0x1032F)]),
+    ("Gothic", &[(0x10330, 0x1034F)]),
+    ("Deseret", &[(0x10400, 0x1044F)]),
+    (
+        "Byzantine Musical Symbols",
+        &[(0x1D000, 0x1D0FF), (0x1D100, 0x1D1FF), (0x1D200, 0x1D24F)],
+    ),
+    ("Mathematical Alphanumeric Symbols", &[(0x1D400, 0x1D7FF)]),
+    (
+        "Private Use (plane 15)",
+        &[(0xF0000, 0xFFFFD), (0x100000, 0x10FFFD)],
+    ),
+    (
+        "Variation Selectors",
+        &[(0xFE00, 0xFE0F), (0xE0100, 0xE01EF)],
+    ),
+    ("Tags", &[(0xE0000, 0xE007F)]),
+    ("Limbu", &[(0x1900, 0x194F)]),
+    ("Tai Le", &[(0x1950, 0x197F)]),
+    ("New Tai Lue", &[(0x1980, 0x19DF)]),
+    ("Buginese", &[(0x1A00, 0x1A1F)]),
+    ("Glagolitic", &[(0x2C00, 0x2C5F)]),
+    ("Tifinagh", &[(0x2D30, 0x2D7F)]),
+    ("Yijing Hexagram Symbols", &[(0x4DC0, 0x4DFF)]),
+    ("Syloti Nagri", &[(0xA800, 0xA82F)]),
+    (
+        "Linear B Syllabary",
+        &[(0x10000, 0x1007F), (0x10080, 0x100FF), (0x10100, 0x1013F)],
+    ),
+    ("Ancient Greek Numbers", &[(0x10140, 0x1018F)]),
+    ("Ugaritic", &[(0x10380, 0x1039F)]),
+    ("Old Persian", &[(0x103A0, 0x103DF)]),
+    ("Shavian", &[(0x10450, 0x1047F)]),
+    ("Osmanya", &[(0x10480, 0x104AF)]),
+    ("Cypriot Syllabary", &[(0x10800, 0x1083F)]),
+    ("Kharoshthi", &[(0x10A00, 0x10A5F)]),
+    ("Tai Xuan Jing Symbols", &[(0x1D300, 0x1D35F)]),
+    ("Cuneiform", &[(0x12000, 0x123FF), (0x12400, 0x1247F)]),
+    ("Counting Rod Numerals", &[(0x1D360, 0x1D37F)]),
+    ("Sundanese", &[(0x1B80, 0x1BBF)]),
+    ("Lepcha", &[(0x1C00, 0x1C4F)]),
+    ("Ol Chiki", &[(0x1C50, 0x1C7F)]),
+    ("Saurashtra", &[(0xA880, 0xA8DF)]),
+    ("Kayah Li", &[(0xA900, 0xA92F)]),
+    ("Rejang", &[(0xA930, 0xA95F)]),
+    ("Cham", &[(0xAA00, 0xAA5F)]),
+    ("Ancient Symbols", &[(0x10190, 0x101CF)]),
+    ("Phaistos Disc", &[(0x101D0, 0x101FF)]),
+    (
+        "Carian",
+        &[(0x102A0, 0x102DF), (0x10280, 0x1029F), (0x10920, 0x1093F)],
+    ),
+    ("Domino Tiles", &[(0x1F030, 0x1F09F), (0x1F000, 0x1F02F)]),
+];
+
+// コードページの文字の集め方．
+enum Repertoire {
+    SingleByte(&'static Encoding), // 0x80..=0xFF の文字．
+    DoubleByte(CmapEncoding, &'static [(u8, u8)], (u8, u8)), // 上位バイトの範囲と下位バイトの範囲．
+}
+
+// 文字を集められるコードページ．他のビットは調べない．
+// 2 バイトのコードページは，ベンダーの拡張を除いた基本の文字集合 (JIS X 0208, GB 2312, KS X 1001, Big5) の範囲だけを使う．
+const CODE_PAGES: [(u8, &str, Repertoire); 16] = [
+    (
+        0,
+        "cp1252",
+        Repertoire::SingleByte(encoding_rs::WINDOWS_1252),
+    ),
+    (
+        1,
+        "cp1250",
+        Repertoire::SingleByte(encoding_rs::WINDOWS_1250),
+    ),
+    (
+        2,
+        "cp1251",
+        Repertoire::SingleByte(encoding_rs::WINDOWS_1251),
+    ),
+    (
+        3,
+        "cp1253",
+        Repertoire::SingleByte(encoding_rs::WINDOWS_1253),
+    ),
+    (
+        4,
+        "cp1254",
+        Repertoire::SingleByte(encoding_rs::WINDOWS_1254),
+    ),
+    (
+        5,
+        "cp1255",
+        Repertoire::SingleByte(encoding_rs::WINDOWS_1255),
+    ),
+    (
+        6,
+        "cp1256",
+        Repertoire::SingleByte(encoding_rs::WINDOWS_1256),
+    ),
+    (
+        7,
+        "cp1257",
+        Repertoire::SingleByte(encoding_rs::WINDOWS_1257),
+    ),
+    (
+        8,
+        "cp1258",
+        Repertoire::SingleByte(encoding_rs::WINDOWS_1258),
+    ),
+    (
+        16,
+        "cp874",
+        Repertoire::SingleByte(encoding_rs::WINDOWS_874),
+    ),
+    (
+        17,
+        "cp932",
+        Repertoire::DoubleByte(
+            CmapEncoding::ShiftJis,
+            &[(0x81, 0x86), (0x88, 0x9F), (0xE0, 0xEA)], // 0x87 は NEC の特殊文字．
+            (0x40, 0xFC),
+        ),
+    ),
+    (
+        18,
+        "cp936",
+        Repertoire::DoubleByte(CmapEncoding::Prc, &[(0xA1, 0xF7)], (0xA1, 0xFE)),
+    ),
+    (
+        19,
+        "cp949",
+        Repertoire::DoubleByte(CmapEncoding::Wansung, &[(0xA1, 0xFE)], (0xA1, 0xFE)),
+    ),
+    (
+        20,
+        "cp950",
+        Repertoire::DoubleByte(CmapEncoding::Big5, &[(0xA1, 0xF9)], (0x40, 0xFE)),
+    ),
+    (
+        21,
+        "cp1361",
+        Repertoire::DoubleByte(CmapEncoding::Johab, &[(0x84, 0xD3)], (0x41, 0xFE)),
+    ),
+    (
+        29,
+        "Mac Roman",
+        Repertoire::SingleByte(encoding_rs::MACINTOSH),
+    ),
+];
+
+fn is_private_use(c: char) -> bool {
+    matches!(c, '\u{E000}'..='\u{F8FF}' | '\u{F0000}'..)
+}
+
+impl Repertoire {
+    // 制御文字と私用領域の文字は除く．
+    fn collect_chars(&self) -> Vec<char> {
+        let mut chars: Vec<char> = match self {
+            Self::SingleByte(encoding) => (0x80..=0xFF)
+                .filter_map(|byte| {
+                    let bytes = [byte];
+                    let s = encoding.decode_without_bom_handling_and_without_replacement(&bytes)?;
+                    let mut chars = s.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) => Some(c),
+                        _ => None,
+                    }
+                })
+                .collect(),
+            Self::DoubleByte(encoding, leads, (trail_first, trail_last)) => leads
+                .iter()
+                .flat_map(|&(first, last)| first..=last)
+                .flat_map(|lead| {
+                    (*trail_first..=*trail_last).filter_map(move |trail| {
+                        encoding.decode(u16::from_be_bytes([lead, trail]) as u32)
+                    })
+                })
+                .collect(),
+        };
+        chars.retain(|&c| !c.is_control() && !is_private_use(c));
+        chars.sort_unstable();
+        chars.dedup();
+        chars
+    }
+}
+
+// コードページの文字はフォントによらないので，一度だけ作る．
+fn code_page_chars() -> &'static [Vec<char>] {
+    static CHARS: OnceLock<Vec<Vec<char>>> = OnceLock::new();
+    CHARS.get_or_init(|| {
+        CODE_PAGES
+            .iter()
+            .map(|(_, _, repertoire)| repertoire.collect_chars())
+            .collect()
+    })
+}
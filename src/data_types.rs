@@ -12,21 +12,18 @@ impl Tag {
     pub fn to_array(&self) -> [u8; 4] {
         self.0.to_be_bytes()
     }
-    #[inline]
-    pub fn to_string(&self) -> String {
-        self.to_array().iter().map(|&c| c as char).collect()
-    }
 }
 
 impl fmt::Debug for Tag {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{0} (= 0x{0:08x} = {1})", self.0, self.to_string())
+        write!(f, "{0} (= 0x{0:08x} = {1})", self.0, self)
     }
 }
 
 impl fmt::Display for Tag {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.to_string())
+        let s: String = self.to_array().iter().map(|&c| c as char).collect();
+        write!(f, "{}", s)
     }
 }
 
@@ -34,12 +31,21 @@ pub const NAME: Tag = Tag::from_be_bytes(*b"name");
 pub const FVAR: Tag = Tag::from_be_bytes(*b"fvar");
 pub const STAT: Tag = Tag::from_be_bytes(*b"STAT");
 pub const CMAP: Tag = Tag::from_be_bytes(*b"cmap");
+pub const DSIG: Tag = Tag::from_be_bytes(*b"DSIG");
 pub const OS_2: Tag = Tag::from_be_bytes(*b"OS/2");
 pub const LOCA: Tag = Tag::from_be_bytes(*b"loca");
 pub const HEAD: Tag = Tag::from_be_bytes(*b"head");
 pub const MAXP: Tag = Tag::from_be_bytes(*b"maxp");
 pub const GLYF: Tag = Tag::from_be_bytes(*b"glyf");
 pub const GSUB: Tag = Tag::from_be_bytes(*b"GSUB");
+pub const HHEA: Tag = Tag::from_be_bytes(*b"hhea");
+pub const HMTX: Tag = Tag::from_be_bytes(*b"hmtx");
+pub const KERN: Tag = Tag::from_be_bytes(*b"kern");
+pub const POST: Tag = Tag::from_be_bytes(*b"post");
+pub const VHEA: Tag = Tag::from_be_bytes(*b"vhea");
+pub const VMTX: Tag = Tag::from_be_bytes(*b"vmtx");
+pub const VORG: Tag = Tag::from_be_bytes(*b"VORG");
+pub const MVAR: Tag = Tag::from_be_bytes(*b"MVAR");
 // 32-bit signed fixed-point number (16.16)
 #[derive(PartialEq)]
 pub struct Fixed(pub i32);
@@ -73,9 +79,36 @@ impl F2DOT14 {
     }
 }
 
+// 3 バイトの符号なし整数．cmap format 14 の Unicode 値などで使う．
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Uint24(pub u32);
+
 pub type TableTag = Tag;
 pub type Offset32 = u32;
 pub type Offset16 = u16;
+
+// Offset16 と Offset32 を区別せずにサブテーブルの位置として扱うためのトレイト．
+pub trait Offset: Copy {
+    fn to_usize(self) -> usize;
+
+    fn is_null(self) -> bool {
+        self.to_usize() == 0
+    }
+}
+
+impl Offset for Offset16 {
+    #[inline]
+    fn to_usize(self) -> usize {
+        self as usize
+    }
+}
+
+impl Offset for Offset32 {
+    #[inline]
+    fn to_usize(self) -> usize {
+        self as usize
+    }
+}
 #[allow(non_camel_case_types)]
 pub type uint32 = u32;
 #[allow(non_camel_case_types)]
@@ -84,3 +117,5 @@ pub type uint16 = u16;
 pub type int16 = i16;
 #[allow(non_camel_case_types)]
 pub type uint8 = u8;
+pub type FWORD = int16; // int16 that describes a quantity in font design units.
+pub type UFWORD = uint16; // uint16 that describes a quantity in font design units.
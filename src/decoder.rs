@@ -1,71 +1,101 @@
+use core::{cmp::Ordering, fmt, marker::PhantomData};
 use std::mem::size_of;
 
-use crate::data_types::{Fixed, Tag, Version16Dot16, F2DOT14, LONGDATETIME};
+use crate::{
+    data_types::{Fixed, Offset, Tag, Uint24, Version16Dot16, F2DOT14, LONGDATETIME},
+    error::{Context, Error, Result},
+};
 
 pub trait FromData: Sized {
     const SIZE: usize;
-    fn parse(data: &[u8]) -> Option<Self>;
+    fn parse(data: &[u8]) -> Result<Self>;
 }
 
 impl FromData for u8 {
     const SIZE: usize = size_of::<Self>();
-    fn parse(data: &[u8]) -> Option<Self> {
-        data.try_into().map(Self::from_be_bytes).ok()
+    fn parse(data: &[u8]) -> Result<Self> {
+        data.try_into()
+            .map(Self::from_be_bytes)
+            .map_err(|_| Error::eof(data.len()))
     }
 }
 
 impl FromData for u16 {
     const SIZE: usize = size_of::<Self>();
-    fn parse(data: &[u8]) -> Option<Self> {
-        data.try_into().map(Self::from_be_bytes).ok()
+    fn parse(data: &[u8]) -> Result<Self> {
+        data.try_into()
+            .map(Self::from_be_bytes)
+            .map_err(|_| Error::eof(data.len()))
     }
 }
 
 impl FromData for u32 {
     const SIZE: usize = size_of::<Self>();
-    fn parse(data: &[u8]) -> Option<Self> {
-        data.try_into().map(Self::from_be_bytes).ok()
+    fn parse(data: &[u8]) -> Result<Self> {
+        data.try_into()
+            .map(Self::from_be_bytes)
+            .map_err(|_| Error::eof(data.len()))
     }
 }
 
 impl FromData for i8 {
     const SIZE: usize = size_of::<Self>();
-    fn parse(data: &[u8]) -> Option<Self> {
-        data.try_into().map(Self::from_be_bytes).ok()
+    fn parse(data: &[u8]) -> Result<Self> {
+        data.try_into()
+            .map(Self::from_be_bytes)
+            .map_err(|_| Error::eof(data.len()))
     }
 }
 
 impl FromData for i16 {
     const SIZE: usize = size_of::<Self>();
-    fn parse(data: &[u8]) -> Option<Self> {
-        data.try_into().map(Self::from_be_bytes).ok()
+    fn parse(data: &[u8]) -> Result<Self> {
+        data.try_into()
+            .map(Self::from_be_bytes)
+            .map_err(|_| Error::eof(data.len()))
     }
 }
 
 impl FromData for i32 {
     const SIZE: usize = size_of::<Self>();
-    fn parse(data: &[u8]) -> Option<Self> {
-        data.try_into().map(Self::from_be_bytes).ok()
+    fn parse(data: &[u8]) -> Result<Self> {
+        data.try_into()
+            .map(Self::from_be_bytes)
+            .map_err(|_| Error::eof(data.len()))
     }
 }
 
 impl FromData for i64 {
     const SIZE: usize = size_of::<Self>();
-    fn parse(data: &[u8]) -> Option<Self> {
-        data.try_into().map(Self::from_be_bytes).ok()
+    fn parse(data: &[u8]) -> Result<Self> {
+        data.try_into()
+            .map(Self::from_be_bytes)
+            .map_err(|_| Error::eof(data.len()))
     }
 }
 
 impl FromData for f32 {
     const SIZE: usize = size_of::<Self>();
-    fn parse(data: &[u8]) -> Option<Self> {
-        data.try_into().map(Self::from_be_bytes).ok()
+    fn parse(data: &[u8]) -> Result<Self> {
+        data.try_into()
+            .map(Self::from_be_bytes)
+            .map_err(|_| Error::eof(data.len()))
+    }
+}
+
+impl FromData for Uint24 {
+    const SIZE: usize = 3;
+    fn parse(data: &[u8]) -> Result<Self> {
+        match data {
+            &[a, b, c] => Ok(Self(u32::from_be_bytes([0, a, b, c]))),
+            _ => Err(Error::eof(data.len())),
+        }
     }
 }
 
 impl FromData for Tag {
     const SIZE: usize = u32::SIZE;
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         type T = u32;
         T::parse(data).map(Self)
     }
@@ -73,7 +103,7 @@ impl FromData for Tag {
 
 impl FromData for Fixed {
     const SIZE: usize = i32::SIZE;
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         type T = i32;
         T::parse(data).map(Self)
     }
@@ -81,7 +111,7 @@ impl FromData for Fixed {
 
 impl FromData for LONGDATETIME {
     const SIZE: usize = i64::SIZE;
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         type T = i64;
         T::parse(data).map(Self)
     }
@@ -89,7 +119,7 @@ impl FromData for LONGDATETIME {
 
 impl FromData for Version16Dot16 {
     const SIZE: usize = u32::SIZE;
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         type T = u32;
         T::parse(data).map(Self)
     }
@@ -97,7 +127,7 @@ impl FromData for Version16Dot16 {
 
 impl FromData for F2DOT14 {
     const SIZE: usize = i16::SIZE;
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         type T = i16;
         T::parse(data).map(Self)
     }
@@ -109,61 +139,90 @@ pub struct Stream<'a> {
     offset: usize,
 }
 
+// 読み込みに失敗した場合は offset を進めない．
 impl<'a> Stream<'a> {
     pub fn new(data: &'a [u8]) -> Self {
         Self { data, offset: 0 }
     }
 
-    pub fn read_bytes(&mut self, len: usize) -> Option<&'a [u8]> {
-        let bytes = self.data.get(self.offset..self.offset + len);
+    // offset を進めずに len バイトを取得する．
+    fn peek_bytes(&self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .ok_or(Error::eof(self.offset))?;
+        self.data
+            .get(self.offset..end)
+            .ok_or(Error::eof(self.offset))
+    }
+
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let bytes = self.peek_bytes(len)?;
         self.offset += len;
-        bytes
+        Ok(bytes)
     }
 
-    pub fn read<T: FromData>(&mut self) -> Option<T> {
-        let data = self.read_bytes(T::SIZE)?;
-        // read_bytes でバイト長が保証されているので， parse は成功する．
-        Some(T::parse(data).unwrap())
+    pub fn read<T: FromData>(&mut self) -> Result<T> {
+        let offset = self.offset;
+        // 値の検証でエラーになった場合は，このストリーム上の位置に直す．
+        let value = T::parse(self.peek_bytes(T::SIZE)?).offset_by(offset)?;
+        self.offset += T::SIZE;
+        Ok(value)
     }
 
-    pub fn read_array<T: FromData>(&mut self, count: usize) -> Option<Vec<T>> {
-        let len = count * T::SIZE;
-        let data = self.read_bytes(len)?;
-        let mut s = Stream::new(data);
-        let mut v = vec![];
-        for _ in 0..count {
-            // read_bytes() が成功しているので，バイト長は保証され， unwrap できる．
-            v.push(s.read().unwrap());
-        }
-        Some(v)
+    pub fn read_array<T: FromData>(&mut self, count: usize) -> Result<Vec<T>> {
+        self.read_unsized_array(count, T::SIZE, T::parse)
     }
 
     pub fn read_unsized_array<T>(
         &mut self,
         data_count: usize,
         data_size: usize,
-        parse: Box<dyn Fn(&'a [u8]) -> Option<T>>,
-    ) -> Option<Vec<T>> {
-        let mut v = vec![];
-        for _ in 0..data_count {
-            // read_bytes() が成功しているので，バイト長は保証され， unwrap できる．
-            let data = self.read_bytes(data_size)?;
-            let value = parse(data).unwrap();
-            v.push(value);
+        parse: impl Fn(&'a [u8]) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        let len = data_count
+            .checked_mul(data_size)
+            .ok_or(Error::eof(self.offset))?;
+        let data = self.peek_bytes(len)?;
+        let mut v = Vec::with_capacity(data.len() / data_size.max(1));
+        if data_size == 0 {
+            // 大きさが 0 の要素でも parse は data_count 回呼ぶ．
+            for _ in 0..data_count {
+                v.push(parse(data).offset_by(self.offset)?);
+            }
+        } else {
+            for (i, chunk) in data.chunks_exact(data_size).enumerate() {
+                v.push(parse(chunk).offset_by(self.offset + i * data_size)?);
+            }
         }
-        Some(v)
+        self.offset += len;
+        Ok(v)
+    }
+
+    // Vec を作らずに，バイト列を借用したまま配列として返す．
+    pub fn read_lazy_array<T: FromData>(&mut self, count: usize) -> Result<LazyArray<'a, T>> {
+        let len = count.checked_mul(T::SIZE).ok_or(Error::eof(self.offset))?;
+        let data = self.read_bytes(len)?;
+        Ok(LazyArray::new(data))
+    }
+
+    // 残りのバイト列をすべて LazyArray にして返す．
+    pub fn read_all_lazy_array<T: FromData>(&mut self) -> Result<LazyArray<'a, T>> {
+        let count = self.data.len().saturating_sub(self.offset) / T::SIZE;
+        self.read_lazy_array(count)
     }
 
     // 残りのバイト列をすべてVecにして返す．
-    pub fn read_all_array<T: FromData>(&mut self) -> Option<Vec<T>> {
-        let count = (self.data.len() - self.offset) / T::SIZE;
+    pub fn read_all_array<T: FromData>(&mut self) -> Result<Vec<T>> {
+        let count = self.data.len().saturating_sub(self.offset) / T::SIZE;
         self.read_array(count)
     }
 
     pub fn is_end(&self) -> bool {
-        self.offset == self.data.len()
+        self.offset >= self.data.len()
     }
 
+    // データの範囲外を指定しても良いが，その後の読み込みはエラーになる．
     pub fn set_offset(&mut self, offset: usize) {
         self.offset = offset;
     }
@@ -176,12 +235,162 @@ impl<'a> Stream<'a> {
         self.offset = self.data.len()
     }
 
-    pub fn set_len(&mut self, len: usize) -> Option<()> {
-        self.data = self.data.get(0..len)?;
-        Some(())
+    pub fn set_len(&mut self, len: usize) -> Result<()> {
+        self.data = self.data.get(0..len).ok_or(Error::eof(self.data.len()))?;
+        Ok(())
     }
 
-    pub fn get_tail(self) -> Option<&'a [u8]> {
-        self.data.get(self.offset..self.data.len())
+    pub fn get_tail(self) -> Result<&'a [u8]> {
+        self.data.get(self.offset..).ok_or(Error::eof(self.offset))
+    }
+}
+
+/// バイト列を借用したまま，要素を取り出すときにパースする配列．
+pub struct LazyArray<'a, T> {
+    data: &'a [u8],
+    phantom: PhantomData<T>,
+}
+
+// T が Copy でなくてもコピーできるように手で実装する．
+impl<'a, T> Clone for LazyArray<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for LazyArray<'a, T> {}
+
+impl<'a, T: FromData> LazyArray<'a, T> {
+    // 末尾の T::SIZE に満たないバイトは無視する．
+    pub fn new(data: &'a [u8]) -> Self {
+        let len = data.len() - data.len() % T::SIZE.max(1);
+        Self {
+            data: &data[..len],
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len() / T::SIZE.max(1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    // 範囲外か，要素のパースに失敗した場合は None を返す．
+    pub fn get(&self, index: usize) -> Option<T> {
+        let start = index.checked_mul(T::SIZE)?;
+        let data = self.data.get(start..start.checked_add(T::SIZE)?)?;
+        T::parse(data).ok()
+    }
+
+    pub fn first(&self) -> Option<T> {
+        self.get(0)
+    }
+
+    pub fn last(&self) -> Option<T> {
+        self.get(self.len().checked_sub(1)?)
+    }
+
+    pub fn iter(&self) -> LazyArrayIter<'a, T> {
+        LazyArrayIter {
+            array: *self,
+            index: 0,
+        }
+    }
+
+    // 要素が f の順に並んでいることを前提に二分探索する．
+    // 見つかった場合は (index, 要素) を返し，見つからない場合は None を返す．
+    pub fn binary_search_by<F>(&self, mut f: F) -> Option<(usize, T)>
+    where
+        F: FnMut(&T) -> Ordering,
+    {
+        let mut start = 0;
+        let mut end = self.len();
+        while start < end {
+            let mid = start + (end - start) / 2;
+            let value = self.get(mid)?;
+            match f(&value) {
+                Ordering::Less => start = mid + 1,
+                Ordering::Greater => end = mid,
+                Ordering::Equal => return Some((mid, value)),
+            }
+        }
+        None
+    }
+
+    pub fn to_vec(&self) -> Vec<T> {
+        self.iter().collect()
+    }
+}
+
+impl<'a, T: FromData + fmt::Debug> fmt::Debug for LazyArray<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T: FromData> IntoIterator for LazyArray<'a, T> {
+    type Item = T;
+    type IntoIter = LazyArrayIter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T: FromData> IntoIterator for &LazyArray<'a, T> {
+    type Item = T;
+    type IntoIter = LazyArrayIter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// パースに失敗した要素に当たった時点で終わる．
+pub struct LazyArrayIter<'a, T> {
+    array: LazyArray<'a, T>,
+    index: usize,
+}
+
+impl<'a, T: FromData> Iterator for LazyArrayIter<'a, T> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.array.get(self.index)?;
+        self.index += 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.array.len().saturating_sub(self.index)))
+    }
+}
+
+/// テーブルの先頭から offset の位置にあるサブテーブルのバイト列を返す．
+pub fn get_subtable_data<O: Offset>(data: &[u8], offset: O) -> Result<&[u8]> {
+    let offset = offset.to_usize();
+    data.get(offset..).ok_or(Error::eof(offset))
+}
+
+/// offset の位置にあるサブテーブルをパースする．エラーの offset はテーブルの先頭からの位置になる．
+pub fn parse_subtable<'a, O: Offset, T>(
+    data: &'a [u8],
+    offset: O,
+    parse: impl FnOnce(&'a [u8]) -> Result<T>,
+) -> Result<T> {
+    let data = get_subtable_data(data, offset)?;
+    parse(data).offset_by(offset.to_usize())
+}
+
+/// NULL (= 0) を許す offset の場合．NULL なら Ok(None) を返す．
+pub fn parse_optional_subtable<'a, O: Offset, T>(
+    data: &'a [u8],
+    offset: O,
+    parse: impl FnOnce(&'a [u8]) -> Result<T>,
+) -> Result<Option<T>> {
+    if offset.is_null() {
+        Ok(None)
+    } else {
+        parse_subtable(data, offset, parse).map(Some)
     }
 }
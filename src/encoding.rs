@@ -0,0 +1,136 @@
+// cmap の subtable の文字コードと Unicode の文字を相互に変換する．
+// 2 バイトの文字コードは上位バイトと下位バイトを並べた値 (0xHHLL) で表す．これは format 2 の引き方と同じ．
+use encoding_rs::Encoding;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CmapEncoding {
+    Unicode,
+    Symbol, // (3, 0): U+F020..=U+F0FF の私用領域に記号を置く．
+    ShiftJis,
+    Prc, // GB2312 (GBK として扱う)．
+    Big5,
+    Wansung, // EUC-KR．
+    Johab,
+    MacRoman,
+    MacJapanese,
+    MacChineseTraditional,
+    MacKorean,
+    MacChineseSimplified,
+    Unknown,
+}
+
+impl CmapEncoding {
+    pub fn new(platform_id: u16, encoding_id: u16) -> Self {
+        match (platform_id, encoding_id) {
+            (0, _) | (3, 1) | (3, 10) => Self::Unicode,
+            (3, 0) => Self::Symbol,
+            (3, 2) => Self::ShiftJis,
+            (3, 3) => Self::Prc,
+            (3, 4) => Self::Big5,
+            (3, 5) => Self::Wansung,
+            (3, 6) => Self::Johab,
+            (1, 0) => Self::MacRoman,
+            (1, 1) => Self::MacJapanese,
+            (1, 2) => Self::MacChineseTraditional,
+            (1, 3) => Self::MacKorean,
+            (1, 25) => Self::MacChineseSimplified,
+            _ => Self::Unknown,
+        }
+    }
+
+    // encoding_rs で変換できるエンコーディング．
+    fn get_encoding(&self) -> Option<&'static Encoding> {
+        match self {
+            Self::ShiftJis | Self::MacJapanese => Some(encoding_rs::SHIFT_JIS),
+            Self::Prc | Self::MacChineseSimplified => Some(encoding_rs::GBK),
+            Self::Big5 | Self::MacChineseTraditional => Some(encoding_rs::BIG5),
+            Self::Wansung | Self::MacKorean => Some(encoding_rs::EUC_KR),
+            Self::MacRoman => Some(encoding_rs::MACINTOSH),
+            _ => None,
+        }
+    }
+
+    // 文字を subtable の文字コードにする．エンコーディングで表せない文字は None．
+    pub fn encode(&self, c: char) -> Option<u32> {
+        match self {
+            Self::Unicode | Self::Symbol => Some(c as u32),
+            Self::Johab => encode_johab(c),
+            Self::Unknown => None,
+            _ => {
+                let mut buffer = [0; 4];
+                let (bytes, _, had_errors) =
+                    self.get_encoding()?.encode(c.encode_utf8(&mut buffer));
+                match (had_errors, bytes.as_ref()) {
+                    (false, &[byte]) => Some(byte as u32),
+                    (false, &[high_byte, low_byte]) => {
+                        Some(u16::from_be_bytes([high_byte, low_byte]) as u32)
+                    }
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    // subtable の文字コードを文字にする．1 文字にならない文字コードは None．
+    pub fn decode(&self, code: u32) -> Option<char> {
+        match self {
+            Self::Unicode | Self::Symbol => char::from_u32(code),
+            Self::Johab => decode_johab(code),
+            Self::Unknown => None,
+            _ => {
+                let code = u16::try_from(code).ok()?;
+                let [high_byte, low_byte] = code.to_be_bytes();
+                let bytes: &[u8] = if high_byte == 0 {
+                    &[low_byte]
+                } else {
+                    &[high_byte, low_byte]
+                };
+                let s = self
+                    .get_encoding()?
+                    .decode_without_bom_handling_and_without_replacement(bytes)?;
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Some(c),
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+// Johab は encoding_rs に無いので，ASCII とハングル音節だけを計算で変換する．
+// ハングル音節は 1 ビットの印と 5 ビットずつの初声，中声，終声を並べた値になる．
+const JOHAB_MEDIALS: [u32; 21] = [
+    3, 4, 5, 6, 7, 10, 11, 12, 13, 14, 15, 18, 19, 20, 21, 22, 23, 26, 27, 28, 29,
+];
+
+fn johab_final(index: u32) -> u32 {
+    match index {
+        0 => 1, // 終声なし．
+        1..=16 => index + 1,
+        _ => index + 2, // 18 は使われない．
+    }
+}
+
+fn encode_johab(c: char) -> Option<u32> {
+    let code = c as u32;
+    if code < 0x80 {
+        return Some(code);
+    }
+    let index = code.checked_sub(0xAC00).filter(|&index| index < 11172)?;
+    let (initial, medial, final_) = (index / (21 * 28), index / 28 % 21, index % 28);
+    Some(0x8000 | (initial + 2) << 10 | JOHAB_MEDIALS[medial as usize] << 5 | johab_final(final_))
+}
+
+fn decode_johab(code: u32) -> Option<char> {
+    if code < 0x80 {
+        return char::from_u32(code);
+    }
+    if code & !0x7FFF != 0x8000 {
+        return None;
+    }
+    let initial = (code >> 10 & 0x1F).checked_sub(2).filter(|&i| i < 19)?;
+    let medial = JOHAB_MEDIALS.iter().position(|&m| m == code >> 5 & 0x1F)? as u32;
+    let final_ = (0..28).find(|&i| johab_final(i) == code & 0x1F)?;
+    char::from_u32(0xAC00 + (initial * 21 + medial) * 28 + final_)
+}
@@ -0,0 +1,120 @@
+use core::fmt;
+
+use crate::data_types::Tag;
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    UnexpectedEof,          // 読み込みに必要なバイト数が足りない．
+    InvalidValue,           // 値が仕様の範囲外．
+    UnsupportedFormat(u32), // 未対応の format や version．
+    MissingTable,           // table directory にテーブルが存在しない．
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEof => write!(f, "unexpected end of data"),
+            Self::InvalidValue => write!(f, "invalid value"),
+            Self::UnsupportedFormat(format) => write!(f, "unsupported format {}", format),
+            Self::MissingTable => write!(f, "missing table"),
+        }
+    }
+}
+
+/// パースに失敗した場所を表すエラー．
+/// offset はパース中はデータの先頭からの相対位置で，呼び出し元へ伝播するたびに親の位置が加算され，
+/// `Table` から返るときにはファイル先頭からの位置になる．
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error {
+    pub kind: ErrorKind,
+    pub tag: Option<Tag>,
+    pub field: Option<&'static str>,
+    pub offset: usize,
+}
+
+impl Error {
+    pub fn new(kind: ErrorKind, offset: usize) -> Self {
+        Self {
+            kind,
+            tag: None,
+            field: None,
+            offset,
+        }
+    }
+
+    pub fn eof(offset: usize) -> Self {
+        Self::new(ErrorKind::UnexpectedEof, offset)
+    }
+
+    pub fn invalid(offset: usize) -> Self {
+        Self::new(ErrorKind::InvalidValue, offset)
+    }
+
+    pub fn unsupported(format: u32, offset: usize) -> Self {
+        Self::new(ErrorKind::UnsupportedFormat(format), offset)
+    }
+
+    pub fn missing(tag: Tag) -> Self {
+        Self {
+            tag: Some(tag),
+            ..Self::new(ErrorKind::MissingTable, 0)
+        }
+    }
+
+    // 最も内側で設定された field を優先する．
+    pub fn with_field(mut self, field: &'static str) -> Self {
+        self.field.get_or_insert(field);
+        self
+    }
+
+    pub fn with_tag(mut self, tag: Tag) -> Self {
+        self.tag.get_or_insert(tag);
+        self
+    }
+
+    pub fn offset_by(mut self, base: usize) -> Self {
+        self.offset = self.offset.saturating_add(base);
+        self
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+        if let Some(tag) = self.tag {
+            write!(f, " in '{}'", tag)?;
+        }
+        if let Some(field) = self.field {
+            write!(f, " at {}", field)?;
+        }
+        write!(f, " (offset {})", self.offset)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// `Result` にエラーの発生箇所を付け足すためのヘルパー．
+pub trait Context<T> {
+    fn field(self, field: &'static str) -> Result<T>;
+    fn table(self, tag: Tag) -> Result<T>;
+    fn offset_by(self, base: usize) -> Result<T>;
+}
+
+impl<T> Context<T> for Result<T> {
+    #[inline]
+    fn field(self, field: &'static str) -> Result<T> {
+        self.map_err(|e| e.with_field(field))
+    }
+
+    #[inline]
+    fn table(self, tag: Tag) -> Result<T> {
+        self.map_err(|e| e.with_tag(tag))
+    }
+
+    #[inline]
+    fn offset_by(self, base: usize) -> Result<T> {
+        self.map_err(|e| e.offset_by(base))
+    }
+}
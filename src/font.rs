@@ -0,0 +1,377 @@
+// Table をまとめて扱うための高水準な API．
+// 各テーブルは最初に使われたときにパースし，その結果 (エラーも含む) を保持する．
+use std::sync::OnceLock;
+
+use crate::{
+    bbox::BBoxPen,
+    cmap::{Charmap, CmapTable},
+    data_types::GLYF,
+    error::{Context, Error, ErrorKind, Result},
+    glyf::{BBox, CompositeResolver, GlyfTable, Glyph, GlyphPoint, ResolvedGlyph},
+    head::HeadTable,
+    hhea::HheaTable,
+    hmtx::{HmtxTable, LongHorMetric},
+    id::{NameID, PlatformID},
+    kern::KernTable,
+    loca::LocaTable,
+    maxp::MaxpTable,
+    mvar::MvarTable,
+    name::{NameRecord, NameTable},
+    os_2::OS2Table,
+    outline::{draw_points, OutlinePen},
+    post::PostTable,
+    table::Table,
+    vhea::VheaTable,
+    vmtx::{GlyphVMetrics, VmtxTable},
+    vorg::VorgTable,
+};
+
+pub struct Font<'a> {
+    table: Table<'a>,
+    head: OnceLock<Result<HeadTable>>,
+    maxp: OnceLock<Result<MaxpTable>>,
+    loca: OnceLock<Result<LocaTable<'a>>>,
+    glyf: OnceLock<Result<GlyfTable<'a>>>,
+    cmap: OnceLock<Result<CmapTable<'a>>>,
+    name: OnceLock<Result<NameTable<'a>>>,
+    os2: OnceLock<Result<OS2Table>>,
+    post: OnceLock<Result<PostTable<'a>>>,
+    hhea: OnceLock<Result<HheaTable>>,
+    hmtx: OnceLock<Result<HmtxTable<'a>>>,
+    vhea: OnceLock<Result<VheaTable>>,
+    vmtx: OnceLock<Result<VmtxTable<'a>>>,
+    vorg: OnceLock<Result<VorgTable<'a>>>,
+    kern: OnceLock<Result<KernTable<'a>>>,
+    mvar: OnceLock<Result<MvarTable<'a>>>,
+    charmap: OnceLock<Option<Charmap<'a>>>,
+}
+
+// OnceLock に入れた Result を参照として取り出す．
+fn cached<T>(cell: &OnceLock<Result<T>>, init: impl FnOnce() -> Result<T>) -> Result<&T> {
+    cell.get_or_init(init).as_ref().map_err(|e| *e)
+}
+
+impl<'a> Font<'a> {
+    pub fn new(data: &'a [u8]) -> Result<Self> {
+        Ok(Self::from_table(Table::new(data)?))
+    }
+
+    pub fn from_table(table: Table<'a>) -> Self {
+        Self {
+            table,
+            head: OnceLock::new(),
+            maxp: OnceLock::new(),
+            loca: OnceLock::new(),
+            glyf: OnceLock::new(),
+            cmap: OnceLock::new(),
+            name: OnceLock::new(),
+            os2: OnceLock::new(),
+            post: OnceLock::new(),
+            hhea: OnceLock::new(),
+            hmtx: OnceLock::new(),
+            vhea: OnceLock::new(),
+            vmtx: OnceLock::new(),
+            vorg: OnceLock::new(),
+            kern: OnceLock::new(),
+            mvar: OnceLock::new(),
+            charmap: OnceLock::new(),
+        }
+    }
+
+    pub fn table(&self) -> &Table<'a> {
+        &self.table
+    }
+
+    pub fn head(&self) -> Result<&HeadTable> {
+        cached(&self.head, || self.table.get_head_table())
+    }
+
+    pub fn maxp(&self) -> Result<&MaxpTable> {
+        cached(&self.maxp, || self.table.get_maxp_table())
+    }
+
+    pub fn loca(&self) -> Result<&LocaTable<'a>> {
+        cached(&self.loca, || {
+            let format = self.head()?.get_loca_offset_format()?;
+            self.table.get_loca_table(format, self.number_of_glyphs()?)
+        })
+    }
+
+    pub fn glyf(&self) -> Result<&GlyfTable<'a>> {
+        cached(&self.glyf, || self.table.get_glyf_table())
+    }
+
+    pub fn cmap(&self) -> Result<&CmapTable<'a>> {
+        cached(&self.cmap, || self.table.get_cmap_table())
+    }
+
+    pub fn name(&self) -> Result<&NameTable<'a>> {
+        cached(&self.name, || self.table.get_name_table())
+    }
+
+    pub fn hhea(&self) -> Result<&HheaTable> {
+        cached(&self.hhea, || self.table.get_hhea_table())
+    }
+
+    pub fn hmtx(&self) -> Result<&HmtxTable<'a>> {
+        cached(&self.hmtx, || {
+            let number_of_h_metrics = self.hhea()?.numberOfHMetrics;
+            self.table
+                .get_hmtx_table(number_of_h_metrics, self.number_of_glyphs()?)
+        })
+    }
+
+    // hmtx の advance width と left side bearing．numberOfHMetrics 以降の glyph は最後の advanceWidth を共有する．
+    // USE_MY_METRICS を持つ composite glyph はコンポーネントの値を返す．
+    pub fn glyph_h_metrics(&self, glyph_id: u16) -> Result<LongHorMetric> {
+        let metrics_glyph_id = self.metrics_glyph_id(glyph_id)?;
+        self.hmtx()?
+            .glyph_h_metrics(metrics_glyph_id)
+            .ok_or(Error::invalid(0).with_field("glyphId"))
+    }
+
+    pub fn vhea(&self) -> Result<&VheaTable> {
+        cached(&self.vhea, || self.table.get_vhea_table())
+    }
+
+    pub fn vmtx(&self) -> Result<&VmtxTable<'a>> {
+        cached(&self.vmtx, || {
+            let num_of_long_ver_metrics = self.vhea()?.numOfLongVerMetrics;
+            self.table
+                .get_vmtx_table(num_of_long_ver_metrics, self.number_of_glyphs()?)
+        })
+    }
+
+    pub fn vorg(&self) -> Result<&VorgTable<'a>> {
+        cached(&self.vorg, || self.table.get_vorg_table())
+    }
+
+    // 縦書きのメトリクス．vmtx が無い場合は一般的なエンジンと同じく，
+    // advance height を OS/2 の sTypoAscender - sTypoDescender とし，原点を sTypoAscender に置く．
+    // 原点は VORG があればその値を，vmtx があれば tsb に glyph の yMax を足した値を使う．
+    pub fn glyph_v_metrics(&self, glyph_id: u16) -> Result<GlyphVMetrics> {
+        let vert_origin_y = self
+            .vorg()
+            .ok()
+            .map(|vorg| vorg.get_vert_origin_y(glyph_id));
+        match self.vmtx() {
+            Ok(vmtx) => {
+                let metric = vmtx
+                    .glyph_v_metrics(glyph_id)
+                    .ok_or(Error::invalid(0).with_field("glyphId"))?;
+                let vert_origin_y = match vert_origin_y {
+                    Some(vert_origin_y) => vert_origin_y,
+                    None => metric
+                        .topSideBearing
+                        .saturating_add(self.glyph_y_max(glyph_id)?),
+                };
+                Ok(GlyphVMetrics {
+                    advance_height: metric.advanceHeight,
+                    top_side_bearing: metric.topSideBearing,
+                    vert_origin_y,
+                })
+            }
+            Err(e) if e.kind == ErrorKind::MissingTable => {
+                let (ascender, descender) = self.typo_ascender_descender();
+                let vert_origin_y = vert_origin_y.unwrap_or(ascender);
+                Ok(GlyphVMetrics {
+                    advance_height: (i32::from(ascender) - i32::from(descender)).clamp(0, 0xFFFF)
+                        as u16,
+                    top_side_bearing: vert_origin_y.saturating_sub(self.glyph_y_max(glyph_id)?),
+                    vert_origin_y,
+                })
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    // glyph の header の yMax．輪郭を持たない glyph と，glyf を持たないフォント (CFF) では 0 とする．
+    fn glyph_y_max(&self, glyph_id: u16) -> Result<i16> {
+        match self.glyph(glyph_id) {
+            Ok(glyph) => Ok(glyph.map_or(0, |glyph| glyph.header.yMax)),
+            Err(e) if e.kind == ErrorKind::MissingTable => Ok(0),
+            Err(e) => Err(e),
+        }
+    }
+
+    // OS/2 の sTypoAscender と sTypoDescender．無ければ hhea などから求めた値を使う．
+    fn typo_ascender_descender(&self) -> (i16, i16) {
+        if let Ok(os2) = self.os2() {
+            if let (Some(ascender), Some(descender)) = (os2.sTypoAscender, os2.sTypoDescender) {
+                return (ascender, descender);
+            }
+        }
+        let (ascender, descender) = self.vertical_metrics();
+        (ascender as i16, descender as i16)
+    }
+
+    pub fn kern(&self) -> Result<&KernTable<'a>> {
+        cached(&self.kern, || self.table.get_kern_table())
+    }
+
+    pub fn mvar(&self) -> Result<&MvarTable<'a>> {
+        cached(&self.mvar, || self.table.get_mvar_table())
+    }
+
+    pub fn os2(&self) -> Result<&OS2Table> {
+        cached(&self.os2, || self.table.get_os2_table())
+    }
+
+    pub fn post(&self) -> Result<&PostTable<'a>> {
+        cached(&self.post, || self.table.get_post_table())
+    }
+
+    pub fn number_of_glyphs(&self) -> Result<u16> {
+        Ok(self.maxp()?.get_number_of_glyphs())
+    }
+
+    pub fn units_per_em(&self) -> Result<u16> {
+        Ok(self.head()?.get_units_per_em())
+    }
+
+    // 最適な Unicode の subtable を選んだ cmap．使える subtable が無い場合は None を返す．
+    pub fn charmap(&self) -> Option<&Charmap<'a>> {
+        self.charmap
+            .get_or_init(|| Charmap::new(self.cmap().ok()?))
+            .as_ref()
+    }
+
+    // 対応する glyph が無い場合 (.notdef) は None を返す．
+    pub fn glyph_index(&self, c: char) -> Option<u16> {
+        self.charmap()?.map(c)
+    }
+
+    // 輪郭を持たない glyph (空白など) の場合は Ok(None) を返す．
+    pub fn glyph(&self, glyph_id: u16) -> Result<Option<Glyph>> {
+        if glyph_id >= self.number_of_glyphs()? {
+            return Err(Error::invalid(0).with_field("glyphId"));
+        }
+        let loca = self.loca()?;
+        let Some(range) = loca.get_glyf_range(glyph_id) else {
+            return Ok(None);
+        };
+        let offset = range.start;
+        let data = self
+            .glyf()?
+            .get_data(range)
+            .ok_or(Error::eof(offset))
+            .table(GLYF)?;
+        Glyph::parse(data).offset_by(offset).map(Some)
+    }
+
+    // composite glyph の component は展開済みの点を返す．
+    pub fn outline(&self, glyph_id: u16) -> Result<(Vec<GlyphPoint>, BBox)> {
+        let resolved = self.resolve_glyph(glyph_id)?;
+        Ok((resolved.points, resolved.bbox))
+    }
+
+    // 点の座標 (off-curve 点を含む) の範囲．輪郭を持たない glyph の場合は None を返す．
+    pub fn control_box(&self, glyph_id: u16) -> Result<Option<BBox>> {
+        let bbox = self.resolve_glyph(glyph_id)?.bbox;
+        Ok((!bbox.is_empty()).then_some(bbox))
+    }
+
+    // 曲線の極値まで求めた，輪郭が実際に通る範囲．輪郭を持たない glyph の場合は None を返す．
+    pub fn tight_bbox(&self, glyph_id: u16) -> Result<Option<BBox>> {
+        let mut pen = BBoxPen::new();
+        self.outline_glyph(glyph_id, &mut pen)?;
+        let bbox = pen.tight_box();
+        Ok((!bbox.is_empty()).then_some(bbox))
+    }
+
+    // 輪郭を pen に渡す．輪郭を持たない glyph の場合は pen を呼ばない．
+    pub fn outline_glyph(&self, glyph_id: u16, pen: &mut impl OutlinePen) -> Result<()> {
+        let resolved = self.resolve_glyph(glyph_id)?;
+        draw_points(&resolved.points, pen);
+        Ok(())
+    }
+
+    pub fn resolve_glyph(&self, glyph_id: u16) -> Result<ResolvedGlyph> {
+        let Some(glyph) = self.glyph(glyph_id)? else {
+            return Ok(ResolvedGlyph::default());
+        };
+        self.composite_resolver()?.resolve(&glyph)
+    }
+
+    // advance width と lsb を使う glyph id．glyf を持たないフォントでは glyph_id をそのまま返す．
+    pub fn metrics_glyph_id(&self, glyph_id: u16) -> Result<u16> {
+        match self.composite_resolver() {
+            Ok(resolver) => resolver.resolve_metrics_glyph_id(glyph_id),
+            Err(e) if e.kind == ErrorKind::MissingTable => Ok(glyph_id),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn composite_resolver(&self) -> Result<CompositeResolver<'a, '_>> {
+        Ok(CompositeResolver::new(self.loca()?, self.glyf()?))
+    }
+
+    pub fn advance_width(&self, glyph_id: u16) -> Result<u16> {
+        Ok(self.glyph_h_metrics(glyph_id)?.advanceWidth)
+    }
+
+    // post テーブルの glyph 名．テーブルが無いか，名前を持たない場合は None を返す．
+    pub fn glyph_name(&self, glyph_id: u16) -> Option<&'a str> {
+        if glyph_id >= self.number_of_glyphs().ok()? {
+            return None;
+        }
+        self.post().ok()?.glyph_name(glyph_id)
+    }
+
+    pub fn glyph_by_name(&self, name: &str) -> Option<u16> {
+        let num_glyphs = self.number_of_glyphs().ok()?;
+        self.post()
+            .ok()?
+            .glyph_by_name(name)
+            .filter(|&glyph_id| glyph_id < num_glyphs)
+    }
+
+    // 'kern' テーブルによる横書きのカーニング値 (フォント単位)．テーブルが無いか読めない場合は 0．
+    pub fn kerning(&self, left: u16, right: u16) -> i32 {
+        self.kern()
+            .map(|kern| kern.get_kerning(left, right))
+            .unwrap_or(0)
+    }
+
+    // hhea の ascender と descender．無ければ OS/2 の sTypo*，head の bbox の順に使う．
+    pub(crate) fn vertical_metrics(&self) -> (f64, f64) {
+        if let Ok(hhea) = self.hhea() {
+            if hhea.ascender != 0 || hhea.descender != 0 {
+                return (f64::from(hhea.ascender), f64::from(hhea.descender));
+            }
+        }
+        if let Ok(os2) = self.os2() {
+            if let (Some(ascender), Some(descender)) = (os2.sTypoAscender, os2.sTypoDescender) {
+                return (f64::from(ascender), f64::from(descender));
+            }
+        }
+        match self.head() {
+            Ok(head) => (f64::from(head.yMax), f64::from(head.yMin)),
+            Err(_) => (800.0, -200.0),
+        }
+    }
+
+    // Typographic Family name (16) があればそれを，無ければ Font Family name (1) を返す．
+    // 英語 (Windows, en-US) の名前を優先する．
+    pub fn family_name(&self) -> Option<String> {
+        let name = self.name().ok()?;
+        let rank = |record: &NameRecord| match record.platformId {
+            PlatformID::Win(_) if record.languageId.to_id() == 0x0409 => 0,
+            PlatformID::Win(_) => 1,
+            PlatformID::Unicode(_) => 2,
+            PlatformID::Mac(_) => 3,
+            PlatformID::Unknown(_) => 4,
+        };
+        [NameID(16), NameID(1)].into_iter().find_map(|name_id| {
+            let mut records: Vec<_> = name
+                .nameRecords
+                .iter()
+                .filter(|record| record.nameId == name_id)
+                .collect();
+            records.sort_by_key(rank);
+            records
+                .iter()
+                .find_map(|record| name.get_string(record).ok())
+        })
+    }
+}
@@ -0,0 +1,85 @@
+// フォントファイルの先頭のタグを見て，単体のフォントか TrueType Collection かを判定する．
+use crate::{
+    data_types::Tag,
+    decoder::Stream,
+    error::{Context, Error, Result},
+    table::{is_sfnt_version, Collection, Table, TTCF},
+};
+
+pub enum FontFile<'a> {
+    Single(Table<'a>),
+    Collection(Collection<'a>),
+}
+
+impl<'a> FontFile<'a> {
+    // WOFF などの未対応のコンテナは UnsupportedFormat を返す．
+    pub fn from_bytes(data: &'a [u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let tag: Tag = s.read().field("sfntVersion")?;
+        if tag == TTCF {
+            Ok(Self::Collection(Collection::new(data)?))
+        } else if is_sfnt_version(&tag) {
+            Ok(Self::Single(Table::new(data)?))
+        } else {
+            Err(Error::unsupported(tag.0, 0).with_field("sfntVersion"))
+        }
+    }
+
+    pub fn is_collection(&self) -> bool {
+        matches!(self, Self::Collection(_))
+    }
+
+    // フォント (face) の数．
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Single(_) => 1,
+            Self::Collection(collection) => collection.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn face(&self, index: usize) -> Result<Table<'a>> {
+        match self {
+            Self::Single(table) if index == 0 => Ok(table.clone()),
+            Self::Single(_) => Err(Error::invalid(0).with_field("faceIndex")),
+            Self::Collection(collection) => collection.get(index),
+        }
+    }
+
+    // (index, face) を順に返す．壊れた face があっても残りの face は読める．
+    pub fn faces(&self) -> Faces<'a, '_> {
+        Faces {
+            file: self,
+            index: 0,
+        }
+    }
+}
+
+impl<'a, 'b> IntoIterator for &'b FontFile<'a> {
+    type Item = (usize, Result<Table<'a>>);
+    type IntoIter = Faces<'a, 'b>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.faces()
+    }
+}
+
+pub struct Faces<'a, 'b> {
+    file: &'b FontFile<'a>,
+    index: usize,
+}
+
+impl<'a, 'b> Iterator for Faces<'a, 'b> {
+    type Item = (usize, Result<Table<'a>>);
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index < self.file.len() {
+            let index = self.index;
+            self.index += 1;
+            Some((index, self.file.face(index)))
+        } else {
+            None
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     data_types::{Fixed, Offset16, Tag},
     decoder::{FromData, Stream},
+    error::{Context, Result},
 };
 
 #[allow(non_snake_case)]
@@ -17,17 +18,17 @@ pub struct FvarHeader {
 
 impl FromData for FvarHeader {
     const SIZE: usize = 2 * 8;
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        Some(Self {
-            majorVersion: s.read()?,
-            minorVersion: s.read()?,
-            axesArrayOffset: s.read()?,
-            reserved: s.read()?,
-            axisCount: s.read()?,
-            axisSize: s.read()?,
-            instanceCount: s.read()?,
-            instanceSize: s.read()?,
+        Ok(Self {
+            majorVersion: s.read().field("majorVersion")?,
+            minorVersion: s.read().field("minorVersion")?,
+            axesArrayOffset: s.read().field("axesArrayOffset")?,
+            reserved: s.read().field("reserved")?,
+            axisCount: s.read().field("axisCount")?,
+            axisSize: s.read().field("axisSize")?,
+            instanceCount: s.read().field("instanceCount")?,
+            instanceSize: s.read().field("instanceSize")?,
         })
     }
 }
@@ -45,15 +46,15 @@ pub struct VariationAxisRecord {
 
 impl FromData for VariationAxisRecord {
     const SIZE: usize = 20;
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        Some(Self {
-            axisTag: s.read()?,
-            minValue: s.read()?,
-            defaultValue: s.read()?,
-            maxValue: s.read()?,
-            flags: s.read()?,
-            axisNameId: s.read()?,
+        Ok(Self {
+            axisTag: s.read().field("axisTag")?,
+            minValue: s.read().field("minValue")?,
+            defaultValue: s.read().field("defaultValue")?,
+            maxValue: s.read().field("maxValue")?,
+            flags: s.read().field("flags")?,
+            axisNameId: s.read().field("axisNameId")?,
         })
     }
 }
@@ -73,16 +74,16 @@ pub struct InstanceRecord {
 }
 
 impl InstanceRecord {
-    pub fn parse(data: &[u8], axis_count: usize) -> Option<Self> {
+    pub fn parse(data: &[u8], axis_count: usize) -> Result<Self> {
         let mut s = Stream::new(data);
-        let subfamily_name_id = s.read()?;
-        let flags = s.read()?;
+        let subfamily_name_id = s.read().field("subfamilyNameId")?;
+        let flags = s.read().field("flags")?;
         let coordinates = UserTuple {
-            coordinates: s.read_array(axis_count)?,
+            coordinates: s.read_array(axis_count).field("coordinates")?,
         };
-        let post_script_name_id = s.read();
+        let post_script_name_id = s.read().ok();
 
-        Some(Self {
+        Ok(Self {
             subfamilyNameId: subfamily_name_id,
             flags,
             coordinates,
@@ -99,25 +100,52 @@ pub struct FvarTable<'a> {
 }
 
 impl<'a> FvarTable<'a> {
-    pub fn parse(data: &'a [u8]) -> Option<FvarTable<'a>> {
+    pub fn parse(data: &'a [u8]) -> Result<FvarTable<'a>> {
         let mut s = Stream::new(data);
-        let header: FvarHeader = s.read()?;
+        let header: FvarHeader = s.read().field("header")?;
         let offset = header.axesArrayOffset as usize;
         s.set_offset(offset);
-        let axes = s.read_array(header.axisCount as usize)?;
+        let axes = s.read_array(header.axisCount as usize).field("axes")?;
         let instance_size = header.instanceSize as usize;
         let instance_count = header.instanceCount as usize;
         let axis_count = header.axisCount as usize;
-        let instances = s.read_unsized_array(
-            instance_count,
-            instance_size,
-            Box::new(move |data| InstanceRecord::parse(data, axis_count)),
-        )?;
-        Some(FvarTable {
+        let instances = s
+            .read_unsized_array(instance_count, instance_size, move |data| {
+                InstanceRecord::parse(data, axis_count)
+            })
+            .field("instances")?;
+        Ok(FvarTable {
             data,
             header,
             axes,
             instances,
         })
     }
+
+    // ユーザー座標 (軸の tag と値) を正規化した座標 (-1.0..=1.0) にする．avar の対応付けは行わない．
+    // 指定されなかった軸は既定値 (0.0) になる．
+    pub fn normalize_coordinates(&self, user_coordinates: &[(Tag, f64)]) -> Vec<f64> {
+        self.axes
+            .iter()
+            .map(|axis| {
+                let Some(&(_, value)) = user_coordinates
+                    .iter()
+                    .find(|(tag, _)| *tag == axis.axisTag)
+                else {
+                    return 0.0;
+                };
+                let min = axis.minValue.to_f64();
+                let default = axis.defaultValue.to_f64();
+                let max = axis.maxValue.to_f64();
+                let value = value.clamp(min.min(default), max.max(default));
+                if value < default {
+                    (value - default) / (default - min)
+                } else if value > default {
+                    (value - default) / (max - default)
+                } else {
+                    0.0
+                }
+            })
+            .collect()
+    }
 }
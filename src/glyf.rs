@@ -8,6 +8,7 @@ use std::ops::Range;
 use crate::{
     data_types::{int16, uint16, uint8, F2DOT14},
     decoder::{FromData, Stream},
+    error::{Context, Error, Result},
     loca::LocaTable,
 };
 
@@ -25,21 +26,23 @@ pub struct Glyph {
 }
 
 impl Glyph {
-    pub fn parse(data: &[u8]) -> Option<Self> {
+    pub fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let header: GlyphHeader = s.read().unwrap();
+        let header: GlyphHeader = s.read().field("header")?;
         match header.get_type() {
             GlyphType::Simple => {
-                let tail = s.get_tail().unwrap();
-                let subtable = SimpleGlyphTable::parse(tail, header.numberOfContours as u16)?;
-                Some(Glyph {
+                let tail = s.get_tail()?;
+                let subtable = SimpleGlyphTable::parse(tail, header.numberOfContours as u16)
+                    .offset_by(GlyphHeader::SIZE)?;
+                Ok(Glyph {
                     header,
                     subtable: GlyphTable::Simple(subtable),
                 })
             }
             GlyphType::Composite => {
-                let subtable = CompositeGlyphTable::parse(s.get_tail()?)?;
-                Some(Glyph {
+                let subtable =
+                    CompositeGlyphTable::parse(s.get_tail()?).offset_by(GlyphHeader::SIZE)?;
+                Ok(Glyph {
                     header,
                     subtable: GlyphTable::Composite(subtable),
                 })
@@ -47,14 +50,20 @@ impl Glyph {
         }
     }
 
-    pub fn get_points(&self, loca: &LocaTable, glyf: &GlyfTable<'_>) -> (Vec<GlyphPoint>, BBox) {
-        match &self.subtable {
-            GlyphTable::Simple(table) => table.get_points(),
-            GlyphTable::Composite(table) => table.get_points(loca, glyf),
-        }
+    pub fn get_points(
+        &self,
+        loca: &LocaTable<'_>,
+        glyf: &GlyfTable<'_>,
+    ) -> Result<(Vec<GlyphPoint>, BBox)> {
+        let resolved = CompositeResolver::new(loca, glyf).resolve(self)?;
+        Ok((resolved.points, resolved.bbox))
     }
 }
 
+// コンポーネントが自分自身を参照するような循環したデータで，無限に再帰しないための上限．
+// maxp の maxComponentDepth は 0 や小さすぎる値が入ったフォントが多く，FreeType も使わないので参照しない．
+const MAX_COMPONENT_DEPTH: usize = 64;
+
 pub enum GlyphTable {
     Simple(SimpleGlyphTable),
     Composite(CompositeGlyphTable),
@@ -72,14 +81,14 @@ pub struct GlyphHeader {
 impl FromData for GlyphHeader {
     const SIZE: usize = 2 * 5;
     #[allow(non_snake_case)]
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let numberOfContours = s.read()?;
-        let xMin = s.read()?;
-        let yMin = s.read()?;
-        let xMax = s.read()?;
-        let yMax = s.read()?;
-        Some(Self {
+        let numberOfContours = s.read().field("numberOfContours")?;
+        let xMin = s.read().field("xMin")?;
+        let yMin = s.read().field("yMin")?;
+        let xMax = s.read().field("xMax")?;
+        let yMax = s.read().field("yMax")?;
+        Ok(Self {
             numberOfContours,
             xMin,
             yMin,
@@ -116,25 +125,37 @@ pub struct SimpleGlyphTable {
 
 impl SimpleGlyphTable {
     #[allow(non_snake_case)]
-    pub fn parse(data: &[u8], number_of_contours: u16) -> Option<Self> {
+    pub fn parse(data: &[u8], number_of_contours: u16) -> Result<Self> {
         let mut s = Stream::new(data);
-        let endPtsOfContours: Vec<u16> = s.read_array(number_of_contours as usize)?;
-        let number_of_points = endPtsOfContours.last()?.checked_add(1)?;
-        let instructionLength = s.read()?;
-        let instructions = s.read_array(instructionLength as usize)?;
+        let endPtsOfContours: Vec<u16> = s
+            .read_array(number_of_contours as usize)
+            .field("endPtsOfContours")?;
+        let number_of_points = match endPtsOfContours.last() {
+            Some(last) => last
+                .checked_add(1)
+                .ok_or(Error::invalid(0))
+                .field("endPtsOfContours")?,
+            None => 0,
+        };
+        let instructionLength = s.read().field("instructionLength")?;
+        let instructions = s
+            .read_array(instructionLength as usize)
+            .field("instructions")?;
 
         // flatten flags -> group by contour.
         let mut flags_left = number_of_points;
         let mut flags = vec![];
         while flags_left > 0 {
-            let flag = s.read::<SimpleGlyphFlags>()?;
+            let flag = s.read::<SimpleGlyphFlags>().field("flags")?;
 
             let repeat = 1 + if flag.repeat_flag() {
-                s.read::<u8>()? as u16
+                s.read::<u8>().field("flags")? as u16
             } else {
                 0
             };
 
+            // 点の数を超えて繰り返す不正なデータは，残りの点の数で打ち切る．
+            let repeat = repeat.min(flags_left);
             for _ in 0..repeat {
                 flags.push(flag);
             }
@@ -142,15 +163,13 @@ impl SimpleGlyphTable {
             flags_left -= repeat;
         }
 
-        assert_eq!(flags.len(), number_of_points as usize);
-
         let mut prev: i16 = 0;
         let mut xCoordinates = vec![];
         for flag in &flags {
             let delta = match flag.get_x_type() {
-                CoordType::Positive8 => i16::from(s.read::<u8>()?),
-                CoordType::Negative8 => -i16::from(s.read::<u8>()?),
-                CoordType::I16 => s.read::<i16>()?,
+                CoordType::Positive8 => i16::from(s.read::<u8>().field("xCoordinates")?),
+                CoordType::Negative8 => -i16::from(s.read::<u8>().field("xCoordinates")?),
+                CoordType::I16 => s.read::<i16>().field("xCoordinates")?,
                 CoordType::SamePrevious => 0,
             };
             prev = prev.wrapping_add(delta);
@@ -161,16 +180,16 @@ impl SimpleGlyphTable {
         let mut yCoordinates = vec![];
         for flag in &flags {
             let delta = match flag.get_y_type() {
-                CoordType::Positive8 => i16::from(s.read::<u8>()?),
-                CoordType::Negative8 => -i16::from(s.read::<u8>()?),
-                CoordType::I16 => s.read::<i16>()?,
+                CoordType::Positive8 => i16::from(s.read::<u8>().field("yCoordinates")?),
+                CoordType::Negative8 => -i16::from(s.read::<u8>().field("yCoordinates")?),
+                CoordType::I16 => s.read::<i16>().field("yCoordinates")?,
                 CoordType::SamePrevious => 0,
             };
             prev = prev.wrapping_add(delta);
             yCoordinates.push(prev);
         }
 
-        Some(Self {
+        Ok(Self {
             endPtsOfContours,
             instructionLength,
             instructions,
@@ -183,7 +202,7 @@ impl SimpleGlyphTable {
     fn get_points(&self) -> (Vec<GlyphPoint>, BBox) {
         let mut points = vec![];
         let mut bbox = BBox::default();
-        for i in 0..self.flags.len() as usize {
+        for i in 0..self.flags.len() {
             let x = f64::from(self.xCoordinates[i]);
             let y = f64::from(self.yCoordinates[i]);
             bbox.update(x, y);
@@ -221,7 +240,7 @@ pub enum CoordType {
 pub struct SimpleGlyphFlags(pub u8);
 impl FromData for SimpleGlyphFlags {
     const SIZE: usize = 1;
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         u8::parse(data).map(Self)
     }
 }
@@ -303,24 +322,24 @@ pub struct CompositeGlyphComponent {
 }
 
 impl CompositeGlyphComponent {
-    fn parse(data: &[u8]) -> Vec<Self> {
+    fn parse(data: &[u8]) -> Result<Vec<Self>> {
         let mut s = Stream::new(data);
         let mut v = vec![];
         while !s.is_end() {
-            let flags = CompositeGlyphFlags(s.read().unwrap());
-            let glyph_id = s.read::<u16>().unwrap();
+            let flags = CompositeGlyphFlags(s.read().field("flags")?);
+            let glyph_id = s.read::<u16>().field("glyphIndex")?;
 
             let args = if flags.args_are_xy_values() {
                 // コンポーネントグリフの各制御点の座標に追加されるオフセットベクトル．
                 // Variable font の場合は， gvar table のデルタによってオフセットベクトルを変更できる．
                 // オフセットベクトルに変換行列を適用するかどうかは，SCALED_COMPONENT_OFFSET と UNSCALED_COMPONENT_OFFSET フラグによって決定する．
                 let (x, y) = if flags.arg_1_and_2_are_16bit() {
-                    let x = s.read::<i16>().unwrap();
-                    let y = s.read::<i16>().unwrap();
+                    let x = s.read::<i16>().field("argument1")?;
+                    let y = s.read::<i16>().field("argument2")?;
                     (x, y)
                 } else {
-                    let x = s.read::<i8>().unwrap() as i16;
-                    let y = s.read::<i8>().unwrap() as i16;
+                    let x = s.read::<i8>().field("argument1")? as i16;
+                    let y = s.read::<i8>().field("argument2")? as i16;
                     (x, y)
                 };
                 CompositeGlyphArgs::Offset { x, y }
@@ -331,12 +350,12 @@ impl CompositeGlyphComponent {
                     // child は子コンポーネントグリフの再番号付け前のポイント番号．
                     // このポイント番号にある制御点を，親グリフのポイント番号にある制御点に一致するように子コンポーネントグリフを配置する．
                     // 変換行列が指定されている場合は，位置合わせの前に，子のグリフに変換が適用される．
-                    let parent = s.read::<u16>().unwrap();
-                    let child = s.read::<u16>().unwrap();
+                    let parent = s.read::<u16>().field("argument1")?;
+                    let child = s.read::<u16>().field("argument2")?;
                     (parent, child)
                 } else {
-                    let parent = s.read::<u8>().unwrap() as u16;
-                    let child = s.read::<u8>().unwrap() as u16;
+                    let parent = s.read::<u8>().field("argument1")? as u16;
+                    let child = s.read::<u8>().field("argument2")? as u16;
                     (parent, child)
                 };
                 CompositeGlyphArgs::Point { parent, child }
@@ -344,15 +363,15 @@ impl CompositeGlyphComponent {
 
             let mut transform = Transform::default();
             if flags.we_have_a_two_by_two() {
-                transform.a = s.read::<F2DOT14>().unwrap().to_f32().into();
-                transform.b = s.read::<F2DOT14>().unwrap().to_f32().into();
-                transform.c = s.read::<F2DOT14>().unwrap().to_f32().into();
-                transform.d = s.read::<F2DOT14>().unwrap().to_f32().into();
+                transform.a = s.read::<F2DOT14>().field("transform")?.to_f32().into();
+                transform.b = s.read::<F2DOT14>().field("transform")?.to_f32().into();
+                transform.c = s.read::<F2DOT14>().field("transform")?.to_f32().into();
+                transform.d = s.read::<F2DOT14>().field("transform")?.to_f32().into();
             } else if flags.we_have_an_x_and_y_scale() {
-                transform.a = s.read::<F2DOT14>().unwrap().to_f32().into();
-                transform.d = s.read::<F2DOT14>().unwrap().to_f32().into();
+                transform.a = s.read::<F2DOT14>().field("transform")?.to_f32().into();
+                transform.d = s.read::<F2DOT14>().field("transform")?.to_f32().into();
             } else if flags.we_have_a_scale() {
-                transform.a = s.read::<F2DOT14>().unwrap().to_f32().into();
+                transform.a = s.read::<F2DOT14>().field("transform")?.to_f32().into();
                 transform.d = transform.a;
             }
 
@@ -368,7 +387,7 @@ impl CompositeGlyphComponent {
                 transform,
             });
         }
-        v
+        Ok(v)
     }
 }
 
@@ -378,54 +397,184 @@ pub struct CompositeGlyphTable {
 }
 
 impl CompositeGlyphTable {
-    pub fn parse<'a>(data: &'a [u8]) -> Option<Self> {
-        let components = CompositeGlyphComponent::parse(data);
-        Some(Self { components })
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let components = CompositeGlyphComponent::parse(data)?;
+        Ok(Self { components })
     }
 
-    pub fn get_points(&self, loca: &LocaTable, glyf: &GlyfTable<'_>) -> (Vec<GlyphPoint>, BBox) {
-        let mut v: Vec<GlyphPoint> = vec![];
-        let mut bbox = BBox::default();
-        for component in &self.components {
+    pub fn get_points(
+        &self,
+        loca: &LocaTable<'_>,
+        glyf: &GlyfTable<'_>,
+    ) -> Result<(Vec<GlyphPoint>, BBox)> {
+        let resolved =
+            CompositeResolver::new(loca, glyf).resolve_composite(self, &mut Vec::new())?;
+        Ok((resolved.points, resolved.bbox))
+    }
+}
+
+// composite glyph のコンポーネントを再帰的に展開した結果．
+#[derive(Debug, Clone, Default)]
+pub struct ResolvedGlyph {
+    pub points: Vec<GlyphPoint>,
+    pub bbox: BBox,
+    pub metrics_glyph_id: Option<u16>, // USE_MY_METRICS が立ったコンポーネントの glyph id．advanceWidth と lsb はこの glyph のものを使う．
+    pub depth: usize, // 入れ子の深さ．simple glyph は 0，simple glyph だけを参照する composite glyph は 1．
+}
+
+// composite glyph を FreeType と同じ規則で展開する．
+// 座標はフォント単位のままで，ヒンティングは行わない．
+pub struct CompositeResolver<'a, 'b> {
+    loca: &'b LocaTable<'a>,
+    glyf: &'b GlyfTable<'a>,
+}
+
+impl<'a, 'b> CompositeResolver<'a, 'b> {
+    pub fn new(loca: &'b LocaTable<'a>, glyf: &'b GlyfTable<'a>) -> Self {
+        Self { loca, glyf }
+    }
+
+    pub fn resolve(&self, glyph: &Glyph) -> Result<ResolvedGlyph> {
+        self.resolve_glyph(glyph, &mut Vec::new())
+    }
+
+    // USE_MY_METRICS をたどって，advance width と lsb を使う glyph id を返す．点は展開しない．
+    // resolve の metrics_glyph_id と同じく，フラグが立った最後のコンポーネントを使う．
+    pub fn resolve_metrics_glyph_id(&self, glyph_id: u16) -> Result<u16> {
+        let mut stack = Vec::new();
+        let mut current = glyph_id;
+        loop {
+            let Some(glyph) = self.load(current)? else {
+                return Ok(current);
+            };
+            let GlyphTable::Composite(table) = &glyph.subtable else {
+                return Ok(current);
+            };
+            let Some(component) = table
+                .components
+                .iter()
+                .rev()
+                .find(|component| component.flags.use_my_metrics())
+            else {
+                return Ok(current);
+            };
+            if stack.len() >= MAX_COMPONENT_DEPTH {
+                return Err(Error::invalid(0).with_field("glyphIndex"));
+            }
+            stack.push(current);
+            if stack.contains(&component.glyph_id) {
+                return Err(Error::invalid(0).with_field("glyphIndex"));
+            }
+            current = component.glyph_id;
+        }
+    }
+
+    // loca の範囲が空の glyph は Ok(None) を返す．
+    fn load(&self, glyph_id: u16) -> Result<Option<Glyph>> {
+        let Some(range) = self.loca.get_glyf_range(glyph_id) else {
+            return Ok(None);
+        };
+        let start = range.start;
+        let data = self
+            .glyf
+            .get_data(range)
+            .ok_or(Error::eof(start))
+            .field("glyphIndex")?;
+        Glyph::parse(data).offset_by(start).map(Some)
+    }
+
+    // stack は展開中の祖先の glyph id．
+    fn resolve_glyph(&self, glyph: &Glyph, stack: &mut Vec<u16>) -> Result<ResolvedGlyph> {
+        match &glyph.subtable {
+            GlyphTable::Simple(table) => {
+                let (points, bbox) = table.get_points();
+                Ok(ResolvedGlyph {
+                    points,
+                    bbox,
+                    metrics_glyph_id: None,
+                    depth: 0,
+                })
+            }
+            GlyphTable::Composite(table) => self.resolve_composite(table, stack),
+        }
+    }
+
+    fn resolve_composite(
+        &self,
+        table: &CompositeGlyphTable,
+        stack: &mut Vec<u16>,
+    ) -> Result<ResolvedGlyph> {
+        if stack.len() >= MAX_COMPONENT_DEPTH {
+            return Err(Error::invalid(0).with_field("glyphIndex"));
+        }
+        let mut resolved = ResolvedGlyph {
+            depth: 1,
+            ..Default::default()
+        };
+        for component in &table.components {
             let glyph_id = component.glyph_id;
-            // Composite glyph を構成する Glyph は必ず存在するので， unwrap() を使う．
-            let range = loca.get_glyf_range(glyph_id).unwrap();
-            let data = glyf.get_data(range).unwrap();
-            let glyph = Glyph::parse(data).unwrap();
-            let (mut points, _) = glyph.get_points(loca, glyf);
+            if stack.contains(&glyph_id) {
+                // 祖先を参照する循環したデータ．
+                return Err(Error::invalid(0).with_field("glyphIndex"));
+            }
+            if component.flags.use_my_metrics() {
+                resolved.metrics_glyph_id = Some(glyph_id);
+            }
+            // 輪郭を持たないグリフ (空白など) は loca の範囲が空になるので，点を追加しない．
+            let Some(glyph) = self.load(glyph_id)? else {
+                continue;
+            };
+            stack.push(glyph_id);
+            let child = self.resolve_glyph(&glyph, stack);
+            stack.pop();
+            let child = child?;
+            resolved.depth = resolved.depth.max(child.depth + 1);
+            if component.flags.use_my_metrics() {
+                // コンポーネント自身も USE_MY_METRICS を持つ場合は，さらにその先の glyph を使う．
+                resolved.metrics_glyph_id = child.metrics_glyph_id.or(Some(glyph_id));
+            }
+
+            let mut points = child.points;
             for point in &mut points {
                 (point.x, point.y) = component.transform.multiply(point.x, point.y);
             }
-            match component.args {
+            let (x, y) = match component.args {
                 CompositeGlyphArgs::Offset { x, y } => {
-                    let (x, y): (f64, f64) = if component.flags.unscaled_component_offset() {
-                        (f64::from(x), f64::from(y))
-                    } else {
-                        // scaled.
-                        component.transform.multiply(f64::from(x), f64::from(y))
-                    };
-
-                    // オフセットを行う
-                    for point in &mut points {
-                        (point.x, point.y) = (point.x + x, point.y + y);
-                        bbox.update(point.x, point.y);
+                    let (mut x, mut y) = (f64::from(x), f64::from(y));
+                    if !component.flags.unscaled_component_offset() {
+                        // FreeType と同様に，行列の各列の長さで拡大する．
+                        let (x_scale, y_scale) = component.transform.get_offset_scale();
+                        (x, y) = (x * x_scale, y * y_scale);
+                    }
+                    if component.flags.round_xy_to_grid() {
+                        // フォント単位の格子に合わせる．
+                        (x, y) = (x.round(), y.round());
                     }
+                    (x, y)
                 }
                 CompositeGlyphArgs::Point { parent, child } => {
                     // 親の parent 番目の point と子の child 番目の point が重なるように 子のグリフ点を移動させる．
                     // 例 child (1, 1), parent (0, 0) -> offset (-1, -1)
-                    let parent = v[parent as usize];
-                    let child = points[child as usize];
-                    let (x, y) = (parent.x - child.x, parent.y - child.y);
-                    for point in &mut points {
-                        (point.x, point.y) = (point.x + x, point.y + y);
-                        bbox.update(point.x, point.y);
-                    }
+                    // parent はそれまでのコンポーネントを合わせた番号で，child は変換後の子の番号．
+                    let parent = *resolved
+                        .points
+                        .get(parent as usize)
+                        .ok_or(Error::invalid(0))
+                        .field("argument1")?;
+                    let child = *points
+                        .get(child as usize)
+                        .ok_or(Error::invalid(0))
+                        .field("argument2")?;
+                    (parent.x - child.x, parent.y - child.y)
                 }
+            };
+            for point in &mut points {
+                (point.x, point.y) = (point.x + x, point.y + y);
+                resolved.bbox.update(point.x, point.y);
             }
-            v.extend(points);
+            resolved.points.extend(points);
         }
-        (v, bbox)
+        Ok(resolved)
     }
 }
 
@@ -489,25 +638,28 @@ impl CompositeGlyphFlags {
         self.0 & Self::MORE_COMPONENTS != 0
     }
 
+    fn round_xy_to_grid(&self) -> bool {
+        self.0 & Self::ROUND_XY_TO_GRID != 0
+    }
+
+    fn use_my_metrics(&self) -> bool {
+        self.0 & Self::USE_MY_METRICS != 0
+    }
+
     fn unscaled_component_offset(&self) -> bool {
         // 両方のフラグが立っているような不正な状態はデフォルトの値が使われる．
         // デフォルトは UNSCALED_COMPONENT_OFFSET である．
         //                                | SCALED_COMPONENT_OFFSET ON | UNSCALED_COMPONENT_OFFSET ON
         //  SCALED_COMPONENT_OFFSET   OFF | true                       | true
         //  UNSCALED_COMPONENT_OFFSET OFF | false                      | true
-        if self.0 & (Self::SCALED_COMPONENT_OFFSET | Self::UNSCALED_COMPONENT_OFFSET)
-            == (Self::SCALED_COMPONENT_OFFSET)
-        {
-            false
-        } else {
-            true
-        }
+        self.0 & (Self::SCALED_COMPONENT_OFFSET | Self::UNSCALED_COMPONENT_OFFSET)
+            != Self::SCALED_COMPONENT_OFFSET
     }
 }
 
 // [a b]
 // [c d]
-/// コンポーネントグリフの点を変形させるための2x2行列．
+/// コンポーネントグリフの点を変形させるための2x2行列．値はファイル中の順 (a, b, c, d) で並ぶ．
 #[derive(Debug, Clone, PartialEq)]
 pub struct Transform {
     pub a: f64,
@@ -518,9 +670,14 @@ pub struct Transform {
 
 impl Transform {
     #[inline]
-    /// (new x, new y) = (ax + by, cx + dy)
+    /// (new x, new y) = (ax + cy, bx + dy)
     fn multiply(&self, x: f64, y: f64) -> (f64, f64) {
-        (self.a * x + self.b * y, self.c * x + self.d * y)
+        (self.a * x + self.c * y, self.b * x + self.d * y)
+    }
+
+    // SCALED_COMPONENT_OFFSET のときにオフセットに掛ける (x の倍率, y の倍率)．
+    fn get_offset_scale(&self) -> (f64, f64) {
+        (self.a.hypot(self.c), self.d.hypot(self.b))
     }
 }
 
@@ -536,7 +693,8 @@ impl Default for Transform {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+// 点を 1 つも含まない bbox は空 (min が +∞，max が -∞) で表す．
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct BBox {
     pub xmin: f64,
     pub ymin: f64,
@@ -545,21 +703,34 @@ pub struct BBox {
 }
 
 impl BBox {
-    fn update(&mut self, x: f64, y: f64) {
+    pub(crate) fn update(&mut self, x: f64, y: f64) {
         self.xmin = self.xmin.min(x);
         self.ymin = self.ymin.min(y);
         self.xmax = self.xmax.max(x);
         self.ymax = self.ymax.max(y);
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.xmin > self.xmax || self.ymin > self.ymax
+    }
+
+    pub fn union(&self, other: &BBox) -> BBox {
+        BBox {
+            xmin: self.xmin.min(other.xmin),
+            ymin: self.ymin.min(other.ymin),
+            xmax: self.xmax.max(other.xmax),
+            ymax: self.ymax.max(other.ymax),
+        }
+    }
 }
 
 impl Default for BBox {
     fn default() -> Self {
         Self {
-            xmin: 0.0,
-            ymin: 0.0,
-            xmax: 0.0,
-            ymax: 0.0,
+            xmin: f64::INFINITY,
+            ymin: f64::INFINITY,
+            xmax: f64::NEG_INFINITY,
+            ymax: f64::NEG_INFINITY,
         }
     }
 }
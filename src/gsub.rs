@@ -1,6 +1,7 @@
 use crate::{
     data_types::{uint16, Offset16, Offset32, Tag},
-    decoder::{FromData, Stream},
+    decoder::{parse_optional_subtable, parse_subtable, FromData, LazyArray, Stream},
+    error::{Context, Error, Result},
 };
 
 #[allow(non_snake_case)]
@@ -16,19 +17,19 @@ pub struct GsubHeader {
 
 impl GsubHeader {
     #[allow(non_snake_case)]
-    pub fn parse(data: &[u8]) -> Option<Self> {
+    pub fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let majorVersion = s.read()?;
-        let minorVersion = s.read()?;
-        let scriptListOffset = s.read()?;
-        let featureListOffset = s.read()?;
-        let lookupListOffset = s.read()?;
+        let majorVersion = s.read().field("majorVersion")?;
+        let minorVersion = s.read().field("minorVersion")?;
+        let scriptListOffset = s.read().field("scriptListOffset")?;
+        let featureListOffset = s.read().field("featureListOffset")?;
+        let lookupListOffset = s.read().field("lookupListOffset")?;
         let featureVariationsOffset = if majorVersion == 1 && minorVersion == 1 {
-            Some(s.read()?)
+            Some(s.read().field("featureVariationsOffset")?)
         } else {
             None
         };
-        Some(Self {
+        Ok(Self {
             majorVersion,
             minorVersion,
             scriptListOffset,
@@ -49,22 +50,25 @@ pub struct ScriptList<'a> {
 
 impl<'a> ScriptList<'a> {
     #[allow(non_snake_case)]
-    pub fn parse(data: &'a [u8]) -> Option<Self> {
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let scriptCount: u16 = s.read()?;
-        let scriptRecords = s.read_array(scriptCount as _)?;
-        Some(Self {
+        let scriptCount: u16 = s.read().field("scriptCount")?;
+        let scriptRecords = s.read_array(scriptCount as _).field("scriptRecords")?;
+        Ok(Self {
             data,
             scriptCount,
             scriptRecords,
         })
     }
 
-    pub fn get(&self, index: usize) -> Option<Script> {
-        self.scriptRecords
+    pub fn get(&self, index: usize) -> Result<Script<'a>> {
+        let offset = self
+            .scriptRecords
             .get(index)
-            .and_then(|x| self.data.get(x.scriptOffset as usize..))
-            .and_then(Script::parse)
+            .ok_or(Error::invalid(2))
+            .field("scriptRecords")?
+            .scriptOffset;
+        parse_subtable(self.data, offset, Script::parse).field("scriptOffset")
     }
 }
 
@@ -77,11 +81,11 @@ pub struct ScriptRecord {
 
 impl FromData for ScriptRecord {
     const SIZE: usize = Tag::SIZE + u16::SIZE;
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        Some(Self {
-            scriptTag: s.read()?,
-            scriptOffset: s.read()?,
+        Ok(Self {
+            scriptTag: s.read().field("scriptTag")?,
+            scriptOffset: s.read().field("scriptOffset")?,
         })
     }
 }
@@ -97,12 +101,12 @@ pub struct Script<'a> {
 
 impl<'a> Script<'a> {
     #[allow(non_snake_case)]
-    pub fn parse(data: &'a [u8]) -> Option<Self> {
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let defaultLangSysOffset = s.read()?;
-        let langSysCount: u16 = s.read()?;
-        let langSysRecords = s.read_array(langSysCount as _)?;
-        Some(Self {
+        let defaultLangSysOffset = s.read().field("defaultLangSysOffset")?;
+        let langSysCount: u16 = s.read().field("langSysCount")?;
+        let langSysRecords = s.read_array(langSysCount as _).field("langSysRecords")?;
+        Ok(Self {
             data,
             defaultLangSysOffset,
             langSysCount,
@@ -110,21 +114,20 @@ impl<'a> Script<'a> {
         })
     }
 
-    pub fn get_default_lang_sys_table(&self) -> Option<LangSys> {
-        if self.defaultLangSysOffset == 0 {
-            None
-        } else {
-            self.data
-                .get(self.defaultLangSysOffset as usize..)
-                .and_then(LangSys::parse)
-        }
+    // defaultLangSysOffset が NULL の場合は Ok(None) を返す．
+    pub fn get_default_lang_sys_table(&self) -> Result<Option<LangSys>> {
+        parse_optional_subtable(self.data, self.defaultLangSysOffset, LangSys::parse)
+            .field("defaultLangSysOffset")
     }
 
-    pub fn get(&self, index: usize) -> Option<LangSys> {
-        self.langSysRecords
+    pub fn get(&self, index: usize) -> Result<LangSys> {
+        let offset = self
+            .langSysRecords
             .get(index)
-            .and_then(|x| self.data.get(x.langSysOffset as usize..))
-            .and_then(LangSys::parse)
+            .ok_or(Error::invalid(4))
+            .field("langSysRecords")?
+            .langSysOffset;
+        parse_subtable(self.data, offset, LangSys::parse).field("langSysOffset")
     }
 }
 
@@ -137,11 +140,11 @@ pub struct LangSysRecord {
 
 impl FromData for LangSysRecord {
     const SIZE: usize = Tag::SIZE + u16::SIZE;
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        Some(Self {
-            langSysTag: s.read()?,
-            langSysOffset: s.read()?,
+        Ok(Self {
+            langSysTag: s.read().field("langSysTag")?,
+            langSysOffset: s.read().field("langSysOffset")?,
         })
     }
 }
@@ -157,13 +160,15 @@ pub struct LangSys {
 
 impl LangSys {
     #[allow(non_snake_case)]
-    pub fn parse(data: &[u8]) -> Option<Self> {
+    pub fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let lookupOrderOffset = s.read()?;
-        let requiredFeatureIndex = s.read()?;
-        let featureIndexCount = s.read()?;
-        let featureIndices = s.read_array(featureIndexCount as _)?;
-        Some(Self {
+        let lookupOrderOffset = s.read().field("lookupOrderOffset")?;
+        let requiredFeatureIndex = s.read().field("requiredFeatureIndex")?;
+        let featureIndexCount = s.read().field("featureIndexCount")?;
+        let featureIndices = s
+            .read_array(featureIndexCount as _)
+            .field("featureIndices")?;
+        Ok(Self {
             lookupOrderOffset,
             requiredFeatureIndex,
             featureIndexCount,
@@ -182,22 +187,25 @@ pub struct FeatureList<'a> {
 
 impl<'a> FeatureList<'a> {
     #[allow(non_snake_case)]
-    pub fn parse(data: &'a [u8]) -> Option<Self> {
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let featureCount = s.read()?;
-        let featureRecords = s.read_array(featureCount as _)?;
-        Some(Self {
+        let featureCount = s.read().field("featureCount")?;
+        let featureRecords = s.read_array(featureCount as _).field("featureRecords")?;
+        Ok(Self {
             data,
             featureCount,
             featureRecords,
         })
     }
 
-    pub fn get(&self, index: usize) -> Option<Feature> {
-        self.featureRecords
+    pub fn get(&self, index: usize) -> Result<Feature> {
+        let offset = self
+            .featureRecords
             .get(index)
-            .and_then(|x| self.data.get(x.featureOffset as usize..))
-            .and_then(Feature::parse)
+            .ok_or(Error::invalid(2))
+            .field("featureRecords")?
+            .featureOffset;
+        parse_subtable(self.data, offset, Feature::parse).field("featureOffset")
     }
 }
 
@@ -210,11 +218,11 @@ pub struct FeatureRecord {
 
 impl FromData for FeatureRecord {
     const SIZE: usize = Tag::SIZE + u16::SIZE;
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        Some(Self {
-            featureTag: s.read()?,
-            featureOffset: s.read()?,
+        Ok(Self {
+            featureTag: s.read().field("featureTag")?,
+            featureOffset: s.read().field("featureOffset")?,
         })
     }
 }
@@ -229,12 +237,14 @@ pub struct Feature {
 
 impl Feature {
     #[allow(non_snake_case)]
-    pub fn parse(data: &[u8]) -> Option<Self> {
+    pub fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let featureParamsOffset = s.read()?;
-        let lookupIndexCount = s.read()?;
-        let lookupListIndices = s.read_array(lookupIndexCount as _)?;
-        Some(Self {
+        let featureParamsOffset = s.read().field("featureParamsOffset")?;
+        let lookupIndexCount = s.read().field("lookupIndexCount")?;
+        let lookupListIndices = s
+            .read_array(lookupIndexCount as _)
+            .field("lookupListIndices")?;
+        Ok(Self {
             featureParamsOffset,
             lookupIndexCount,
             lookupListIndices,
@@ -252,36 +262,38 @@ pub struct LookupList<'a> {
 
 impl<'a> LookupList<'a> {
     #[allow(non_snake_case)]
-    pub fn parse(data: &'a [u8]) -> Option<Self> {
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let lookupCount = s.read()?;
-        let lookupOffsets = s.read_array(lookupCount as _)?;
-        Some(Self {
+        let lookupCount = s.read().field("lookupCount")?;
+        let lookupOffsets = s.read_array(lookupCount as _).field("lookupOffsets")?;
+        Ok(Self {
             data,
             lookupCount,
             lookupOffsets,
         })
     }
 
-    pub fn get(&self, index: usize) -> Option<Lookup> {
-        self.lookupOffsets
+    pub fn get(&self, index: usize) -> Result<Lookup<'a>> {
+        let offset = *self
+            .lookupOffsets
             .get(index)
-            .and_then(|x| self.data.get(*x as usize..))
-            .and_then(Lookup::parse)
+            .ok_or(Error::invalid(2))
+            .field("lookupOffsets")?;
+        parse_subtable(self.data, offset, Lookup::parse).field("lookupOffsets")
     }
 }
 
 #[derive(Debug)]
 pub enum GsubLookupType {
-    Single = 1,                // (format 1.1 1.2) Replace one glyph with one glyph
-    Multiple = 2,              // (format 2.1) Replace one glyph with more than one glyph
-    Alternate = 3,             // (format 3.1) Replace one glyph with one of many glyphs
-    Ligature = 4,              // (format 4.1) Replace multiple glyphs with one glyph
-    Context = 5,               // (format 5.1 5.2 5.3) Replace one or more glyphs in context
-    ChainingContext = 6,       // (format 6.1 6.2 6.3) Replace one or more glyphs in chained context
-    ExtensionSubstitution = 7, // (format 7.1) Extension mechanism for other substitutions (i.e. this excludes the Extension type substitution itself)
-    ReverseChainingContextSingle = 8, // (format 8.1)
-                               // Reserved, For future use (set to zero)
+    Single,                       // 1 (format 1.1 1.2) Replace one glyph with one glyph
+    Multiple,                     // 2 (format 2.1) Replace one glyph with more than one glyph
+    Alternate,                    // 3 (format 3.1) Replace one glyph with one of many glyphs
+    Ligature,                     // 4 (format 4.1) Replace multiple glyphs with one glyph
+    Context,                      // 5 (format 5.1 5.2 5.3) Replace one or more glyphs in context
+    ChainingContext, // 6 (format 6.1 6.2 6.3) Replace one or more glyphs in chained context
+    ExtensionSubstitution, // 7 (format 7.1) Extension mechanism for other substitutions (i.e. this excludes the Extension type substitution itself)
+    ReverseChainingContextSingle, // 8 (format 8.1)
+    Unknown(u16),          // Reserved, For future use (set to zero)
 }
 
 impl GsubLookupType {
@@ -295,7 +307,7 @@ impl GsubLookupType {
             6 => Self::ChainingContext,
             7 => Self::ExtensionSubstitution,
             8 => Self::ReverseChainingContextSingle,
-            _ => panic!("invalid lookup type"),
+            _ => Self::Unknown(lookup_type),
         }
     }
 }
@@ -313,14 +325,14 @@ pub struct Lookup<'a> {
 
 impl<'a> Lookup<'a> {
     #[allow(non_snake_case)]
-    pub fn parse(data: &'a [u8]) -> Option<Self> {
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let lookupType = GsubLookupType::new(s.read()?);
-        let lookupFlag = s.read()?;
-        let subTableCount: u16 = s.read()?;
-        let subTableOffsets = s.read_array(subTableCount as _)?;
-        let markFilteringSet = s.read()?;
-        Some(Self {
+        let lookupType = GsubLookupType::new(s.read().field("lookupType")?);
+        let lookupFlag = s.read().field("lookupFlag")?;
+        let subTableCount: u16 = s.read().field("subTableCount")?;
+        let subTableOffsets = s.read_array(subTableCount as _).field("subTableOffsets")?;
+        let markFilteringSet = s.read().field("markFilteringSet")?;
+        Ok(Self {
             data,
             lookupType,
             lookupFlag,
@@ -362,11 +374,11 @@ pub struct SequenceLookupRecord {
 impl FromData for SequenceLookupRecord {
     const SIZE: usize = uint16::SIZE * 2;
     #[allow(non_snake_case)]
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let sequenceIndex = s.read()?;
-        let lookupListIndex = s.read()?;
-        Some(Self {
+        let sequenceIndex = s.read().field("sequenceIndex")?;
+        let lookupListIndex = s.read().field("lookupListIndex")?;
+        Ok(Self {
             sequenceIndex,
             lookupListIndex,
         })
@@ -390,18 +402,26 @@ pub struct ChainedSequenceContextFormat3<'a> {
 
 impl<'a> ChainedSequenceContextFormat3<'a> {
     #[allow(non_snake_case)]
-    pub fn parse(data: &'a [u8]) -> Option<Self> {
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let format = s.read()?;
-        let backtrackGlyphCount: u16 = s.read()?;
-        let backtrackCoverageOffsets = s.read_array(backtrackGlyphCount as usize)?;
-        let inputGlyphCount: u16 = s.read()?;
-        let inputCoverageOffsets = s.read_array(inputGlyphCount as usize)?;
-        let lookaheadGlyphCount: u16 = s.read()?;
-        let lookaheadCoverageOffsets = s.read_array(lookaheadGlyphCount as usize)?;
-        let seqLookupCount: u16 = s.read()?;
-        let seqLookupRecords = s.read_array(seqLookupCount as usize)?;
-        Some(Self {
+        let format = s.read().field("format")?;
+        let backtrackGlyphCount: u16 = s.read().field("backtrackGlyphCount")?;
+        let backtrackCoverageOffsets = s
+            .read_array(backtrackGlyphCount as usize)
+            .field("backtrackCoverageOffsets")?;
+        let inputGlyphCount: u16 = s.read().field("inputGlyphCount")?;
+        let inputCoverageOffsets = s
+            .read_array(inputGlyphCount as usize)
+            .field("inputCoverageOffsets")?;
+        let lookaheadGlyphCount: u16 = s.read().field("lookaheadGlyphCount")?;
+        let lookaheadCoverageOffsets = s
+            .read_array(lookaheadGlyphCount as usize)
+            .field("lookaheadCoverageOffsets")?;
+        let seqLookupCount: u16 = s.read().field("seqLookupCount")?;
+        let seqLookupRecords = s
+            .read_array(seqLookupCount as usize)
+            .field("seqLookupRecords")?;
+        Ok(Self {
             data,
             format,
             backtrackGlyphCount,
@@ -418,20 +438,20 @@ impl<'a> ChainedSequenceContextFormat3<'a> {
 
 #[derive(Debug)]
 #[allow(non_snake_case)]
-pub struct CoverageFormat1 {
-    pub coverageFormat: uint16,  // Format identifier — format = 1
-    pub glyphCount: uint16,      // Number of glyphs in the glyph array
-    pub glyphArray: Vec<uint16>, // [glyphCount] Array of glyph IDs — in numerical order
+pub struct CoverageFormat1<'a> {
+    pub coverageFormat: uint16,            // Format identifier — format = 1
+    pub glyphCount: uint16,                // Number of glyphs in the glyph array
+    pub glyphArray: LazyArray<'a, uint16>, // [glyphCount] Array of glyph IDs — in numerical order
 }
 
-impl CoverageFormat1 {
+impl<'a> CoverageFormat1<'a> {
     #[allow(non_snake_case)]
-    pub fn parse(data: &[u8]) -> Option<Self> {
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let coverageFormat = s.read()?;
-        let glyphCount: u16 = s.read()?;
-        let glyphArray = s.read_array(glyphCount as usize)?;
-        Some(Self {
+        let coverageFormat = s.read().field("coverageFormat")?;
+        let glyphCount: u16 = s.read().field("glyphCount")?;
+        let glyphArray = s.read_lazy_array(glyphCount as usize).field("glyphArray")?;
+        Ok(Self {
             coverageFormat,
             glyphCount,
             glyphArray,
@@ -441,20 +461,22 @@ impl CoverageFormat1 {
 
 #[derive(Debug)]
 #[allow(non_snake_case)]
-pub struct CoverageFormat2 {
-    pub coverageFormat: uint16,         // Format identifier — format = 2
-    pub rangeCount: uint16,             // Number of RangeRecords
-    pub rangeRecords: Vec<RangeRecord>, // [rangeCount] Array of glyph ranges — ordered by startGlyphID.
+pub struct CoverageFormat2<'a> {
+    pub coverageFormat: uint16, // Format identifier — format = 2
+    pub rangeCount: uint16,     // Number of RangeRecords
+    pub rangeRecords: LazyArray<'a, RangeRecord>, // [rangeCount] Array of glyph ranges — ordered by startGlyphID.
 }
 
-impl CoverageFormat2 {
+impl<'a> CoverageFormat2<'a> {
     #[allow(non_snake_case)]
-    pub fn parse(data: &[u8]) -> Option<Self> {
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let coverageFormat = s.read()?;
-        let rangeCount: u16 = s.read()?;
-        let rangeRecords = s.read_array(rangeCount as usize)?;
-        Some(Self {
+        let coverageFormat = s.read().field("coverageFormat")?;
+        let rangeCount: u16 = s.read().field("rangeCount")?;
+        let rangeRecords = s
+            .read_lazy_array(rangeCount as usize)
+            .field("rangeRecords")?;
+        Ok(Self {
             coverageFormat,
             rangeCount,
             rangeRecords,
@@ -473,12 +495,12 @@ pub struct RangeRecord {
 impl FromData for RangeRecord {
     const SIZE: usize = u16::SIZE * 3;
     #[allow(non_snake_case)]
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let startGlyphID = s.read()?;
-        let endGlyphID = s.read()?;
-        let startCoverageIndex = s.read()?;
-        Some(Self {
+        let startGlyphID = s.read().field("startGlyphID")?;
+        let endGlyphID = s.read().field("endGlyphID")?;
+        let startCoverageIndex = s.read().field("startCoverageIndex")?;
+        Ok(Self {
             startGlyphID,
             endGlyphID,
             startCoverageIndex,
@@ -495,12 +517,15 @@ pub struct GsubTable<'a> {
 }
 
 impl<'a> GsubTable<'a> {
-    pub fn parse(data: &'a [u8]) -> Option<Self> {
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
         let header = GsubHeader::parse(data)?;
-        let script_list = ScriptList::parse(data.get(header.scriptListOffset as _..)?)?;
-        let feature_list = FeatureList::parse(data.get(header.featureListOffset as _..)?)?;
-        let lookup_list = LookupList::parse(data.get(header.lookupListOffset as _..)?)?;
-        Some(Self {
+        let script_list = parse_subtable(data, header.scriptListOffset, ScriptList::parse)
+            .field("scriptListOffset")?;
+        let feature_list = parse_subtable(data, header.featureListOffset, FeatureList::parse)
+            .field("featureListOffset")?;
+        let lookup_list = parse_subtable(data, header.lookupListOffset, LookupList::parse)
+            .field("lookupListOffset")?;
+        Ok(Self {
             header,
             script_list,
             feature_list,
@@ -1,6 +1,7 @@
 use crate::{
-    data_types::{int16, uint16, uint32, Fixed, LONGDATETIME},
+    data_types::{int16, uint16, uint32, Fixed, HEAD, LONGDATETIME},
     decoder::Stream,
+    error::{Context, Error, Result},
     glyf::BBox,
 };
 
@@ -54,27 +55,27 @@ pub struct HeadTable {
 
 impl HeadTable {
     #[allow(non_snake_case)]
-    pub fn parse(data: &[u8]) -> Option<Self> {
+    pub fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let majorVersion = s.read()?;
-        let minorVersion = s.read()?;
-        let fontRevision = s.read()?;
-        let checksumAdjustment = s.read()?;
-        let magicNumber = s.read()?;
-        let flags = s.read()?;
-        let unitsPerEm = s.read()?;
-        let created = s.read()?;
-        let modified = s.read()?;
-        let xMin = s.read()?;
-        let yMin = s.read()?;
-        let xMax = s.read()?;
-        let yMax = s.read()?;
-        let macStyle = s.read()?;
-        let lowestRecPPEM = s.read()?;
-        let fontDirectionHint = s.read()?;
-        let indexToLocFormat = s.read()?;
-        let glyphDataFormat = s.read()?;
-        Some(Self {
+        let majorVersion = s.read().field("majorVersion")?;
+        let minorVersion = s.read().field("minorVersion")?;
+        let fontRevision = s.read().field("fontRevision")?;
+        let checksumAdjustment = s.read().field("checksumAdjustment")?;
+        let magicNumber = s.read().field("magicNumber")?;
+        let flags = s.read().field("flags")?;
+        let unitsPerEm = s.read().field("unitsPerEm")?;
+        let created = s.read().field("created")?;
+        let modified = s.read().field("modified")?;
+        let xMin = s.read().field("xMin")?;
+        let yMin = s.read().field("yMin")?;
+        let xMax = s.read().field("xMax")?;
+        let yMax = s.read().field("yMax")?;
+        let macStyle = s.read().field("macStyle")?;
+        let lowestRecPPEM = s.read().field("lowestRecPPEM")?;
+        let fontDirectionHint = s.read().field("fontDirectionHint")?;
+        let indexToLocFormat = s.read().field("indexToLocFormat")?;
+        let glyphDataFormat = s.read().field("glyphDataFormat")?;
+        Ok(Self {
             majorVersion,
             minorVersion,
             fontRevision,
@@ -96,11 +97,13 @@ impl HeadTable {
         })
     }
 
-    pub fn get_loca_offset_format(&self) -> LocaOffsetFormat {
+    pub fn get_loca_offset_format(&self) -> Result<LocaOffsetFormat> {
         match self.indexToLocFormat {
-            0 => LocaOffsetFormat::Offset16,
-            1 => LocaOffsetFormat::Offset32,
-            _ => panic!("invalid indexToLocFormat {}", self.indexToLocFormat),
+            0 => Ok(LocaOffsetFormat::Offset16),
+            1 => Ok(LocaOffsetFormat::Offset32),
+            _ => Err(Error::invalid(50)
+                .with_tag(HEAD)
+                .with_field("indexToLocFormat")),
         }
     }
 
@@ -0,0 +1,67 @@
+use crate::{
+    data_types::{int16, uint16, FWORD, UFWORD},
+    decoder::Stream,
+    error::{Context, Result},
+};
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone)]
+pub struct HheaTable {
+    pub majorVersion: uint16, //Major version number of the horizontal header table — set to 1.
+    pub minorVersion: uint16, //Minor version number of the horizontal header table — set to 0.
+    pub ascender: FWORD,      //Typographic ascent—see note below.
+    pub descender: FWORD,     //Typographic descent—see note below.
+    pub lineGap: FWORD, //Typographic line gap. Negative lineGap values are treated as zero in some legacy platform implementations.
+    pub advanceWidthMax: UFWORD, //Maximum advance width value in 'hmtx' table.
+    pub minLeftSideBearing: FWORD, //Minimum left sidebearing value in 'hmtx' table for glyphs with contours (empty glyphs should be ignored).
+    pub minRightSideBearing: FWORD, //Minimum right sidebearing value; calculated as min(aw - (lsb + xMax - xMin)) for glyphs with contours (empty glyphs should be ignored).
+    pub xMaxExtent: FWORD,          //Max(lsb + (xMax - xMin)).
+    pub caretSlopeRise: int16, //Used to calculate the slope of the cursor (rise/run); 1 for vertical.
+    pub caretSlopeRun: int16,  //0 for vertical.
+    pub caretOffset: int16, //The amount by which a slanted highlight on a glyph needs to be shifted to produce the best appearance. Set to 0 for non-slanted fonts
+    pub reserved: [int16; 4], //set to 0
+    pub metricDataFormat: int16, //0 for current format.
+    pub numberOfHMetrics: uint16, //Number of hMetric entries in 'hmtx' table
+}
+
+impl HheaTable {
+    #[allow(non_snake_case)]
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let majorVersion = s.read().field("majorVersion")?;
+        let minorVersion = s.read().field("minorVersion")?;
+        let ascender = s.read().field("ascender")?;
+        let descender = s.read().field("descender")?;
+        let lineGap = s.read().field("lineGap")?;
+        let advanceWidthMax = s.read().field("advanceWidthMax")?;
+        let minLeftSideBearing = s.read().field("minLeftSideBearing")?;
+        let minRightSideBearing = s.read().field("minRightSideBearing")?;
+        let xMaxExtent = s.read().field("xMaxExtent")?;
+        let caretSlopeRise = s.read().field("caretSlopeRise")?;
+        let caretSlopeRun = s.read().field("caretSlopeRun")?;
+        let caretOffset = s.read().field("caretOffset")?;
+        let mut reserved = [0; 4];
+        for value in &mut reserved {
+            *value = s.read().field("reserved")?;
+        }
+        let metricDataFormat = s.read().field("metricDataFormat")?;
+        let numberOfHMetrics = s.read().field("numberOfHMetrics")?;
+        Ok(Self {
+            majorVersion,
+            minorVersion,
+            ascender,
+            descender,
+            lineGap,
+            advanceWidthMax,
+            minLeftSideBearing,
+            minRightSideBearing,
+            xMaxExtent,
+            caretSlopeRise,
+            caretSlopeRun,
+            caretOffset,
+            reserved,
+            metricDataFormat,
+            numberOfHMetrics,
+        })
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{
+    data_types::{int16, uint16},
+    decoder::{FromData, LazyArray, Stream},
+    error::{Context, Result},
+};
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LongHorMetric {
+    pub advanceWidth: uint16, //Advance width, in font design units.
+    pub lsb: int16,           //Glyph left side bearing, in font design units.
+}
+
+impl FromData for LongHorMetric {
+    const SIZE: usize = 4;
+    #[allow(non_snake_case)]
+    fn parse(data: &[u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let advanceWidth = s.read().field("advanceWidth")?;
+        let lsb = s.read().field("lsb")?;
+        Ok(Self { advanceWidth, lsb })
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Copy)]
+pub struct HmtxTable<'a> {
+    pub hMetrics: LazyArray<'a, LongHorMetric>, //[numberOfHMetrics] Paired advance width and left side bearing values for each glyph. Records are indexed by glyph ID.
+    pub leftSideBearings: LazyArray<'a, int16>, //[numGlyphs - numberOfHMetrics] Left side bearings for glyph IDs greater than or equal to numberOfHMetrics.
+    len: usize,
+}
+
+impl<'a> HmtxTable<'a> {
+    // numberOfHMetrics は hhea，num_glyphs は maxp から得る．
+    #[allow(non_snake_case)]
+    pub fn parse(data: &'a [u8], number_of_h_metrics: u16, num_glyphs: u16) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let hMetrics = s
+            .read_lazy_array(number_of_h_metrics as usize)
+            .field("hMetrics")?;
+        let count = num_glyphs.saturating_sub(number_of_h_metrics) as usize;
+        // 末尾が足りないフォントも出回っているので，leftSideBearings は実際にあるデータまでにする．
+        let available = (data.len() - s.get_offset()) / 2;
+        let leftSideBearings = s
+            .read_lazy_array(count.min(available))
+            .field("leftSideBearings")?;
+        Ok(Self {
+            hMetrics,
+            leftSideBearings,
+            len: hMetrics.len() + count,
+        })
+    }
+
+    // numberOfHMetrics 以降の glyph は最後の advanceWidth を共有し，lsb は leftSideBearings から取る．
+    // glyph id が範囲外の場合は None を返す．テーブルの末尾が足りずに lsb が無い glyph は lsb を 0 とする．
+    #[allow(non_snake_case)]
+    pub fn glyph_h_metrics(&self, glyph_id: u16) -> Option<LongHorMetric> {
+        let index = glyph_id as usize;
+        if let Some(metric) = self.hMetrics.get(index) {
+            return Some(metric);
+        }
+        if index >= self.len {
+            return None;
+        }
+        let advanceWidth = self.hMetrics.last()?.advanceWidth;
+        let lsb = self
+            .leftSideBearings
+            .get(index - self.hMetrics.len())
+            .unwrap_or(0);
+        Some(LongHorMetric { advanceWidth, lsb })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
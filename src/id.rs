@@ -5,6 +5,7 @@ pub enum PlatformID {
     Unicode(u16),
     Mac(u16),
     Win(u16),
+    Unknown(u16), // ISO (2, deprecated) や Custom (4) を含む，対応していない platform id.
 }
 
 impl PlatformID {
@@ -13,7 +14,7 @@ impl PlatformID {
             0 => Self::Unicode(platform_id),
             1 => Self::Mac(platform_id),
             3 => Self::Win(platform_id),
-            _ => panic!("invalid platform id {}", platform_id),
+            _ => Self::Unknown(platform_id),
         }
     }
 
@@ -22,6 +23,7 @@ impl PlatformID {
             Self::Unicode(id) => *id,
             Self::Mac(id) => *id,
             Self::Win(id) => *id,
+            Self::Unknown(id) => *id,
         }
     }
 
@@ -30,6 +32,7 @@ impl PlatformID {
             Self::Unicode(_) => "Unicode",
             Self::Mac(_) => "Mac",
             Self::Win(_) => "Win",
+            Self::Unknown(_) => "Unknown",
         }
     }
 }
@@ -51,6 +54,7 @@ pub enum EncodingID {
     Unicode(UnicodeEncodingID),
     Mac(MacEncodingID),
     Win(WinEncodingID),
+    Unknown(u16), // platform id が未対応の場合の encoding id.
 }
 
 impl EncodingID {
@@ -59,7 +63,7 @@ impl EncodingID {
             0 => Self::Unicode(UnicodeEncodingID(encoding_id)),
             1 => Self::Mac(MacEncodingID(encoding_id)),
             3 => Self::Win(WinEncodingID(encoding_id)),
-            _ => panic!("invalid platform id {}", platform_id),
+            _ => Self::Unknown(encoding_id),
         }
     }
 }
@@ -70,6 +74,7 @@ impl fmt::Debug for EncodingID {
             Self::Unicode(id) => <UnicodeEncodingID as fmt::Debug>::fmt(id, f),
             Self::Mac(id) => <MacEncodingID as fmt::Debug>::fmt(id, f),
             Self::Win(id) => <WinEncodingID as fmt::Debug>::fmt(id, f),
+            Self::Unknown(id) => write!(f, "{} (Unknown)", id),
         }
     }
 }
@@ -80,6 +85,7 @@ impl fmt::Display for EncodingID {
             Self::Unicode(id) => <UnicodeEncodingID as fmt::Display>::fmt(id, f),
             Self::Mac(id) => <MacEncodingID as fmt::Display>::fmt(id, f),
             Self::Win(id) => <WinEncodingID as fmt::Display>::fmt(id, f),
+            Self::Unknown(_) => write!(f, "Unknown"),
         }
     }
 }
@@ -89,6 +95,7 @@ pub enum LanguageID {
     Unicode,
     Mac(MacLanguageID),
     Win(WinLanguageID),
+    Unknown(u16), // platform id が未対応の場合の language id.
 }
 
 impl LanguageID {
@@ -97,7 +104,16 @@ impl LanguageID {
             0 => Self::Unicode,
             1 => Self::Mac(MacLanguageID(encoding_id)),
             3 => Self::Win(WinLanguageID(encoding_id)),
-            _ => panic!("invalid platform id {}", platform_id),
+            _ => Self::Unknown(encoding_id),
+        }
+    }
+
+    pub fn to_id(&self) -> u16 {
+        match self {
+            Self::Unicode => 0,
+            Self::Mac(id) => id.0,
+            Self::Win(id) => id.0,
+            Self::Unknown(id) => *id,
         }
     }
 }
@@ -108,6 +124,7 @@ impl fmt::Debug for LanguageID {
             Self::Unicode => write!(f, "language id is none"),
             Self::Mac(id) => <MacLanguageID as fmt::Debug>::fmt(id, f),
             Self::Win(id) => <WinLanguageID as fmt::Debug>::fmt(id, f),
+            Self::Unknown(id) => write!(f, "{} (Unknown)", id),
         }
     }
 }
@@ -118,6 +135,7 @@ impl fmt::Display for LanguageID {
             Self::Unicode => write!(f, "language id is none"),
             Self::Mac(id) => <MacLanguageID as fmt::Display>::fmt(id, f),
             Self::Win(id) => <WinLanguageID as fmt::Display>::fmt(id, f),
+            Self::Unknown(_) => write!(f, "Unknown"),
         }
     }
 }
@@ -132,7 +150,7 @@ impl UnicodeEncodingID {
             2 => "ISO/IEC 10646",
             3 => "Unicode 2.0 BMP",
             4 => "Unicode 2.0 full",
-            _ => panic!("invalid encoding id {}", self.0),
+            _ => "Unknown",
         }
     }
 }
@@ -189,7 +207,7 @@ impl MacEncodingID {
             30 => "Vietnamese",           // kTextEncodingMacVietnamese
             31 => "Sindhi",               // kTextEncodingMacExtArabic
             32 => "Uninterpreted",
-            _ => panic!("invalid encoding id {}", self.0),
+            _ => "Unknown",
         }
     }
 }
@@ -330,7 +348,7 @@ impl MacLanguageID {
             148 => "Greek (polytonic)",
             149 => "Greenlandic",
             150 => "Azerbaijani (Roman script)",
-            _ => panic!("invalid language id {}", self.0),
+            _ => "Unknown",
         }
     }
 }
@@ -363,7 +381,7 @@ impl WinEncodingID {
             8 => "Reserved",
             9 => "Reserved",
             10 => "Unicode Full",
-            _ => panic!("invalid encoding id {}", self.0),
+            _ => "Unknown",
         }
     }
 }
@@ -596,7 +614,7 @@ impl WinLanguageID {
             0x0485 => "Yakut Russia",
             0x0478 => "Yi PRC",
             0x046A => "Yoruba Nigeria",
-            _ => panic!("invalid language id {}", self.0),
+            _ => "Unknown",
         }
     }
 
@@ -808,7 +826,7 @@ impl WinLanguageID {
             0x0485 => "sah-RU",
             0x0478 => "ii-CN",
             0x046A => "yo-NG",
-            _ => panic!("invalid language id {}", id),
+            _ => "und", // Undetermined
         }
     }
 }
@@ -0,0 +1,225 @@
+// Item Variation Store．MVAR, HVAR などが可変フォントの値の差分を持つのに使う．
+// 座標は正規化済み (各軸 -1.0..=1.0) のものを受け取る．
+use crate::{
+    data_types::{uint16, Offset32, F2DOT14},
+    decoder::{parse_subtable, FromData, LazyArray, Stream},
+    error::{Context, Error, Result},
+};
+
+#[allow(non_snake_case)]
+#[derive(Debug)]
+pub struct RegionAxisCoordinates {
+    pub startCoord: F2DOT14, //The region start coordinate value for the current axis.
+    pub peakCoord: F2DOT14,  //The region peak coordinate value for the current axis.
+    pub endCoord: F2DOT14,   //The region end coordinate value for the current axis.
+}
+
+impl FromData for RegionAxisCoordinates {
+    const SIZE: usize = 6;
+    fn parse(data: &[u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        Ok(Self {
+            startCoord: s.read().field("startCoord")?,
+            peakCoord: s.read().field("peakCoord")?,
+            endCoord: s.read().field("endCoord")?,
+        })
+    }
+}
+
+impl RegionAxisCoordinates {
+    // この軸についての region の重み．
+    fn scalar(&self, coord: f64) -> f64 {
+        let start = f64::from(self.startCoord.to_f32());
+        let peak = f64::from(self.peakCoord.to_f32());
+        let end = f64::from(self.endCoord.to_f32());
+        // 不正な region と，0 をまたぐ region はこの軸を無視する．
+        if peak == 0.0 || start > peak || peak > end || (start < 0.0 && end > 0.0) {
+            return 1.0;
+        }
+        if coord == peak {
+            1.0
+        } else if coord <= start || coord >= end {
+            0.0
+        } else if coord < peak {
+            (coord - start) / (peak - start)
+        } else {
+            (end - coord) / (end - peak)
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug)]
+pub struct VariationRegionList<'a> {
+    pub axisCount: uint16, //The number of variation axes for this font. This must be the same number as axisCount in the 'fvar' table.
+    pub regionCount: uint16, //The number of variation region tables in the variation region list. Must be less than 32,768.
+    pub variationRegions: LazyArray<'a, RegionAxisCoordinates>, //[regionCount * axisCount] Array of variation regions.
+}
+
+impl<'a> VariationRegionList<'a> {
+    #[allow(non_snake_case)]
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let axisCount: uint16 = s.read().field("axisCount")?;
+        let regionCount: uint16 = s.read().field("regionCount")?;
+        let variationRegions = s
+            .read_lazy_array(axisCount as usize * regionCount as usize)
+            .field("variationRegions")?;
+        Ok(Self {
+            axisCount,
+            regionCount,
+            variationRegions,
+        })
+    }
+
+    // 各軸の重みの積．座標が足りない軸は 0 (既定の位置) とみなす．
+    pub fn scalar(&self, region_index: u16, coords: &[f64]) -> f64 {
+        if region_index >= self.regionCount {
+            return 0.0;
+        }
+        let axis_count = self.axisCount as usize;
+        let start = region_index as usize * axis_count;
+        (0..axis_count)
+            .map(|axis| {
+                let coord = coords.get(axis).copied().unwrap_or(0.0);
+                self.variationRegions
+                    .get(start + axis)
+                    .map_or(0.0, |region| region.scalar(coord))
+            })
+            .product()
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug)]
+pub struct ItemVariationData<'a> {
+    pub itemCount: uint16,        //The number of delta sets for distinct items.
+    pub wordDeltaCount: uint16,   //A packed field: the high bit is a flag—see details below.
+    pub regionIndexCount: uint16, //The number of variation regions referenced.
+    pub regionIndexes: LazyArray<'a, uint16>, //[regionIndexCount] Array of indices into the variation region list for the regions referenced by this item variation data table.
+    pub deltaSets: &'a [u8],                  //[itemCount] Delta-set rows.
+}
+
+impl<'a> ItemVariationData<'a> {
+    const LONG_WORDS: u16 = 0x8000;
+    const WORD_DELTA_COUNT_MASK: u16 = 0x7FFF;
+
+    #[allow(non_snake_case)]
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let itemCount: uint16 = s.read().field("itemCount")?;
+        let wordDeltaCount: uint16 = s.read().field("wordDeltaCount")?;
+        let regionIndexCount: uint16 = s.read().field("regionIndexCount")?;
+        if wordDeltaCount & Self::WORD_DELTA_COUNT_MASK > regionIndexCount {
+            return Err(Error::invalid(2).with_field("wordDeltaCount"));
+        }
+        let regionIndexes = s
+            .read_lazy_array(regionIndexCount as usize)
+            .field("regionIndexes")?;
+        let mut data = Self {
+            itemCount,
+            wordDeltaCount,
+            regionIndexCount,
+            regionIndexes,
+            deltaSets: &[],
+        };
+        data.deltaSets = s
+            .read_bytes(itemCount as usize * data.row_size())
+            .field("deltaSets")?;
+        Ok(data)
+    }
+
+    // 1 行のバイト数．先頭の wordCount 個は 2 (LONG_WORDS なら 4) バイト，残りは 1 (2) バイト．
+    fn row_size(&self) -> usize {
+        let word_count = (self.wordDeltaCount & Self::WORD_DELTA_COUNT_MASK) as usize;
+        let region_count = self.regionIndexCount as usize;
+        let (word_size, short_size) = self.delta_sizes();
+        word_count * word_size + (region_count - word_count) * short_size
+    }
+
+    fn delta_sizes(&self) -> (usize, usize) {
+        if self.wordDeltaCount & Self::LONG_WORDS != 0 {
+            (4, 2)
+        } else {
+            (2, 1)
+        }
+    }
+
+    // item の各 region の差分．
+    pub fn get_deltas(&self, item: u16) -> Option<Vec<i32>> {
+        if item >= self.itemCount {
+            return None;
+        }
+        let row_size = self.row_size();
+        let start = item as usize * row_size;
+        let mut s = Stream::new(self.deltaSets.get(start..start + row_size)?);
+        let word_count = self.wordDeltaCount & Self::WORD_DELTA_COUNT_MASK;
+        let long_words = self.wordDeltaCount & Self::LONG_WORDS != 0;
+        (0..self.regionIndexCount)
+            .map(|i| {
+                let delta = match (i < word_count, long_words) {
+                    (true, true) => s.read::<i32>(),
+                    (true, false) | (false, true) => s.read::<i16>().map(i32::from),
+                    (false, false) => s.read::<i8>().map(i32::from),
+                };
+                delta.ok()
+            })
+            .collect()
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug)]
+pub struct ItemVariationStore<'a> {
+    pub format: uint16,                      //Format — set to 1
+    pub variationRegionListOffset: Offset32, //Offset in bytes from the start of the item variation store to the variation region list.
+    pub itemVariationDataCount: uint16,      //The number of item variation data subtables.
+    pub variationRegionList: VariationRegionList<'a>,
+    pub itemVariationData: Vec<ItemVariationData<'a>>,
+}
+
+impl<'a> ItemVariationStore<'a> {
+    #[allow(non_snake_case)]
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let format: uint16 = s.read().field("format")?;
+        if format != 1 {
+            return Err(Error::unsupported(format.into(), 0).with_field("format"));
+        }
+        let variationRegionListOffset: Offset32 = s.read().field("variationRegionListOffset")?;
+        let itemVariationDataCount: uint16 = s.read().field("itemVariationDataCount")?;
+        let offsets: Vec<Offset32> = s
+            .read_array(itemVariationDataCount as usize)
+            .field("itemVariationDataOffsets")?;
+        let variationRegionList =
+            parse_subtable(data, variationRegionListOffset, VariationRegionList::parse)
+                .field("variationRegionList")?;
+        let itemVariationData = offsets
+            .into_iter()
+            .map(|offset| parse_subtable(data, offset, ItemVariationData::parse))
+            .collect::<Result<_>>()
+            .field("itemVariationData")?;
+        Ok(Self {
+            format,
+            variationRegionListOffset,
+            itemVariationDataCount,
+            variationRegionList,
+            itemVariationData,
+        })
+    }
+
+    // (outer, inner) の delta-set を座標に応じて足し合わせた差分．index が範囲外なら None を返す．
+    pub fn get_delta(&self, outer: u16, inner: u16, coords: &[f64]) -> Option<f64> {
+        let data = self.itemVariationData.get(outer as usize)?;
+        let deltas = data.get_deltas(inner)?;
+        Some(
+            deltas
+                .iter()
+                .zip(data.regionIndexes.iter())
+                .map(|(&delta, region_index)| {
+                    f64::from(delta) * self.variationRegionList.scalar(region_index, coords)
+                })
+                .sum(),
+        )
+    }
+}
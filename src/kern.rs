@@ -0,0 +1,175 @@
+// 'kern' テーブル．GPOS を持たない古いフォントのペアカーニングに使う．
+// Windows 形式 (version 0) と Apple 形式 (version 1.0) のヘッダを読み，ペアを引けるのは format 0 のサブテーブルだけ．
+use crate::{
+    data_types::{int16, uint16, uint32, uint8},
+    decoder::{FromData, LazyArray, Stream},
+    error::{Context, Error, Result},
+};
+
+#[allow(non_snake_case)]
+pub struct KernTable<'a> {
+    pub version: uint32, // 0 (Windows) または 0x00010000 (Apple)．Windows 形式は uint16 で格納されている．
+    pub nTables: uint32, // Number of subtables in the kerning table.
+    pub subtables: Vec<KernSubtable<'a>>,
+}
+
+#[allow(non_snake_case)]
+pub struct KernSubtable<'a> {
+    pub format: uint8,    // coverage から取り出した format．
+    pub coverage: uint16, // What type of information is contained in this table. ビットの意味は Windows と Apple で異なる．
+    pub isApple: bool,
+    pub pairs: LazyArray<'a, KernPair>, // format 0 の kerning pairs．left と right の組で昇順に並ぶ．他の format では空．
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KernPair {
+    pub left: uint16,  // The glyph index for the left-hand glyph in the kerning pair.
+    pub right: uint16, // The glyph index for the right-hand glyph in the kerning pair.
+    pub value: int16, // The kerning value for the above pair, in design units. If this value is greater than zero, the characters will be moved apart. If this value is less than zero, the character will be moved closer together.
+}
+
+impl FromData for KernPair {
+    const SIZE: usize = 6;
+    fn parse(data: &[u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let left = s.read().field("left")?;
+        let right = s.read().field("right")?;
+        let value = s.read().field("value")?;
+        Ok(Self { left, right, value })
+    }
+}
+
+impl<'a> KernTable<'a> {
+    #[allow(non_snake_case)]
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let major: uint16 = s.read().field("version")?;
+        let (version, nTables, isApple) = match major {
+            0 => {
+                let nTables: uint16 = s.read().field("nTables")?;
+                (0, u32::from(nTables), false)
+            }
+            1 => {
+                s.set_offset(0);
+                let version: uint32 = s.read().field("version")?;
+                if version != 0x00010000 {
+                    return Err(Error::unsupported(version, 0).with_field("version"));
+                }
+                let nTables: uint32 = s.read().field("nTables")?;
+                (version, nTables, true)
+            }
+            _ => return Err(Error::unsupported(major.into(), 0).with_field("version")),
+        };
+
+        let mut subtables = Vec::new();
+        for _ in 0..nTables {
+            let start = s.get_offset();
+            // Windows: version, length, coverage．Apple: length (uint32), coverage, tupleIndex．
+            let (length, coverage) = if isApple {
+                let length: uint32 = s.read().field("length")?;
+                let coverage: uint16 = s.read().field("coverage")?;
+                s.read::<uint16>().field("tupleIndex")?;
+                (length as usize, coverage)
+            } else {
+                s.read::<uint16>().field("version")?;
+                let length: uint16 = s.read().field("length")?;
+                let coverage: uint16 = s.read().field("coverage")?;
+                (length as usize, coverage)
+            };
+            let format = if isApple {
+                (coverage & 0xFF) as u8
+            } else {
+                (coverage >> 8) as u8
+            };
+            let pairs = if format == 0 {
+                let nPairs: uint16 = s.read().field("nPairs")?;
+                s.read_bytes(6).field("searchRange")?;
+                s.read_lazy_array(nPairs as usize).field("pairs")?
+            } else {
+                LazyArray::new(&[])
+            };
+            subtables.push(KernSubtable {
+                format,
+                coverage,
+                isApple,
+                pairs,
+            });
+            // 大きな format 0 のサブテーブルは uint16 の length が溢れていることがあるので，ペアの数から求める．
+            let end = if format == 0 {
+                s.get_offset()
+            } else {
+                start
+                    .checked_add(length)
+                    .ok_or(Error::invalid(start))
+                    .field("length")?
+            };
+            s.set_offset(end);
+        }
+        Ok(Self {
+            version,
+            nTables,
+            subtables,
+        })
+    }
+
+    // 横書きのカーニング値の合計 (design units)．値が無ければ 0．
+    pub fn get_kerning(&self, left: u16, right: u16) -> i32 {
+        let mut value = 0;
+        for subtable in &self.subtables {
+            if !subtable.is_horizontal()
+                || subtable.is_cross_stream()
+                || subtable.is_minimum()
+                || subtable.is_variation()
+            {
+                continue;
+            }
+            if let Some(pair) = subtable.get_pair(left, right) {
+                if subtable.is_override() {
+                    value = i32::from(pair.value);
+                } else {
+                    value += i32::from(pair.value);
+                }
+            }
+        }
+        value
+    }
+}
+
+impl KernSubtable<'_> {
+    pub fn is_horizontal(&self) -> bool {
+        if self.isApple {
+            self.coverage & 0x8000 == 0
+        } else {
+            self.coverage & 0x0001 != 0
+        }
+    }
+
+    pub fn is_minimum(&self) -> bool {
+        !self.isApple && self.coverage & 0x0002 != 0
+    }
+
+    pub fn is_cross_stream(&self) -> bool {
+        if self.isApple {
+            self.coverage & 0x4000 != 0
+        } else {
+            self.coverage & 0x0004 != 0
+        }
+    }
+
+    pub fn is_override(&self) -> bool {
+        !self.isApple && self.coverage & 0x0008 != 0
+    }
+
+    pub fn is_variation(&self) -> bool {
+        self.isApple && self.coverage & 0x2000 != 0
+    }
+
+    // ペアは昇順に並んでいるので二分探索する．
+    pub fn get_pair(&self, left: u16, right: u16) -> Option<KernPair> {
+        let key = (left, right);
+        self.pairs
+            .binary_search_by(|pair| (pair.left, pair.right).cmp(&key))
+            .map(|(_, pair)| pair)
+    }
+}
@@ -1,14 +1,42 @@
+pub mod bbox;
 pub mod cmap;
+pub mod coverage;
 pub mod data_types;
 pub mod decoder;
+pub mod encoding;
+pub mod error;
+pub mod font;
+pub mod font_file;
 pub mod fvar;
 pub mod glyf;
 pub mod gsub;
 pub mod head;
+pub mod hhea;
+pub mod hmtx;
 pub mod id;
+pub mod item_variation;
+pub mod kern;
+pub mod line_metrics;
 pub mod loca;
 pub mod maxp;
+pub mod mvar;
 pub mod name;
 pub mod os_2;
+pub mod outline;
+pub mod owned_font;
+pub mod post;
+pub mod raster;
 pub mod stat;
+pub mod svg;
 pub mod table;
+pub mod text;
+mod unicode_data;
+pub mod vhea;
+pub mod vmtx;
+pub mod vorg;
+
+pub use error::{Error, ErrorKind};
+pub use font::Font;
+pub use font_file::FontFile;
+pub use outline::OutlinePen;
+pub use owned_font::OwnedFont;
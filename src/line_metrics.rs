@@ -0,0 +1,174 @@
+// 行の ascender, descender と line gap を決める．
+// hhea, OS/2 の sTypo* と usWin* はフォントによって食い違うので，環境ごとの選び方に合わせる．
+// 可変フォントでは MVAR の差分を足す．
+use crate::{
+    data_types::HHEA,
+    error::{Error, ErrorKind, Result},
+    font::Font,
+    mvar::{
+        HORIZONTAL_ASCENDER, HORIZONTAL_CLIPPING_ASCENT, HORIZONTAL_CLIPPING_DESCENT,
+        HORIZONTAL_DESCENDER, HORIZONTAL_LINE_GAP,
+    },
+};
+
+// どの環境の選び方に合わせるか．
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineMetricsConvention {
+    // GDI / DirectWrite．USE_TYPO_METRICS があれば sTypo*，無ければ usWin* を使う．
+    Windows,
+    // Core Text．hhea を使う．
+    Mac,
+    // ブラウザ．USE_TYPO_METRICS があれば sTypo*，無ければ hhea を使う．
+    Css,
+}
+
+// 値の出どころ．MVAR のどの差分を足すかが変わる．
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineMetricsSource {
+    Typo,
+    Win,
+    Hhea,
+}
+
+// descender はベースラインより下を負で表す．line gap は負にならない．
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineMetrics {
+    pub ascender: f64,
+    pub descender: f64,
+    pub line_gap: f64,
+    pub source: LineMetricsSource,
+}
+
+impl LineMetrics {
+    // ベースラインから次の行のベースラインまでの距離．
+    pub fn line_height(&self) -> f64 {
+        self.ascender - self.descender + self.line_gap
+    }
+
+    // フォント単位の値を pixel_size (1 em の pixel 数) に合わせる．
+    pub fn scale(&self, pixel_size: f64, units_per_em: u16) -> Self {
+        let scale = pixel_size / f64::from(units_per_em.max(1));
+        Self {
+            ascender: self.ascender * scale,
+            descender: self.descender * scale,
+            line_gap: self.line_gap * scale,
+            source: self.source,
+        }
+    }
+}
+
+// 無いテーブルは None にする．読めないテーブルはエラーのまま返す．
+fn optional<T>(result: Result<T>) -> Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(e) if e.kind == ErrorKind::MissingTable => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+// フォント単位の行の値．coords は正規化した座標 (fvar の軸の順) で，空なら既定のインスタンスになる．
+// hhea と OS/2 がどちらも無い場合はエラーを返す．MVAR が無ければ差分は足さない．
+pub fn line_metrics(
+    font: &Font,
+    convention: LineMetricsConvention,
+    coords: &[f64],
+) -> Result<LineMetrics> {
+    let hhea = optional(font.hhea())?;
+    let os2 = optional(font.os2())?;
+    if hhea.is_none() && os2.is_none() {
+        return Err(Error::missing(HHEA));
+    }
+
+    let use_typo = os2.is_some_and(|os2| os2.use_typo_metrics());
+    let typo = os2.and_then(|os2| {
+        Some((
+            f64::from(os2.sTypoAscender?),
+            f64::from(os2.sTypoDescender?),
+            f64::from(os2.sTypoLineGap?),
+        ))
+    });
+    // usWin* の line gap を hhea から求めたかどうか．MVAR の差分の足し方が変わる．
+    let win_gap_from_hhea = hhea.is_some();
+    let win = os2.and_then(|os2| {
+        let (ascent, descent) = (f64::from(os2.usWinAscent?), f64::from(os2.usWinDescent?));
+        // usWin* は line gap を持たないので，hhea の行の高さとの差を gap にする．
+        let gap = hhea.map_or(0.0, |hhea| {
+            f64::from(hhea.ascender) - f64::from(hhea.descender) + f64::from(hhea.lineGap)
+                - (ascent + descent)
+        });
+        (ascent != 0.0 || descent != 0.0).then_some((ascent, -descent, gap))
+    });
+    let hhea = hhea
+        .filter(|hhea| hhea.ascender != 0 || hhea.descender != 0)
+        .map(|hhea| {
+            (
+                f64::from(hhea.ascender),
+                f64::from(hhea.descender),
+                f64::from(hhea.lineGap),
+            )
+        });
+
+    use LineMetricsSource::*;
+    let typo = typo.map(|values| (Typo, values));
+    let win = win.map(|values| (Win, values));
+    let hhea = hhea.map(|values| (Hhea, values));
+    let candidates = match convention {
+        LineMetricsConvention::Windows if use_typo => [typo, win, hhea],
+        LineMetricsConvention::Windows => [win, hhea, typo],
+        LineMetricsConvention::Mac => [hhea, typo, win],
+        LineMetricsConvention::Css if use_typo => [typo, hhea, win],
+        LineMetricsConvention::Css => [hhea, typo, win],
+    };
+    let Some((source, (mut ascender, mut descender, mut line_gap))) =
+        candidates.into_iter().flatten().next()
+    else {
+        return Err(Error::invalid(0).with_field("ascender"));
+    };
+
+    if !coords.is_empty() {
+        match font.mvar() {
+            Ok(mvar) => match source {
+                Typo | Hhea => {
+                    ascender += mvar.get_delta(HORIZONTAL_ASCENDER, coords);
+                    descender += mvar.get_delta(HORIZONTAL_DESCENDER, coords);
+                    line_gap += mvar.get_delta(HORIZONTAL_LINE_GAP, coords);
+                }
+                Win => {
+                    let ascent_delta = mvar.get_delta(HORIZONTAL_CLIPPING_ASCENT, coords);
+                    let descent_delta = mvar.get_delta(HORIZONTAL_CLIPPING_DESCENT, coords);
+                    ascender += ascent_delta;
+                    // usWinDescent は正の値なので，差分の符号を反転する．
+                    descender -= descent_delta;
+                    // gap は hhea の行の高さとの差なので，hhea と同じ hasc, hdsc, hlgp の差分を足してから usWin* の差分を引く．
+                    // hhea が無い場合の gap は 0 のままにする．
+                    if win_gap_from_hhea {
+                        line_gap += mvar.get_delta(HORIZONTAL_ASCENDER, coords)
+                            - mvar.get_delta(HORIZONTAL_DESCENDER, coords)
+                            + mvar.get_delta(HORIZONTAL_LINE_GAP, coords)
+                            - (ascent_delta + descent_delta);
+                    }
+                }
+            },
+            Err(e) if e.kind == ErrorKind::MissingTable => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(LineMetrics {
+        ascender,
+        descender,
+        line_gap: line_gap.max(0.0),
+        source,
+    })
+}
+
+// pixel_size (1 em の pixel 数) に合わせた行の値．
+pub fn scaled_line_metrics(
+    font: &Font,
+    convention: LineMetricsConvention,
+    coords: &[f64],
+    pixel_size: f64,
+) -> Result<LineMetrics> {
+    let metrics = line_metrics(font, convention, coords)?;
+    Ok(metrics.scale(pixel_size, font.units_per_em()?))
+}
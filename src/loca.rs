@@ -2,35 +2,40 @@ use core::ops::Range;
 
 use crate::{
     data_types::{Offset16, Offset32},
-    decoder::Stream,
+    decoder::{LazyArray, Stream},
+    error::{Context, Result},
     head::LocaOffsetFormat,
 };
 
 #[derive(Debug)]
-pub enum LocaTable {
-    Short(Vec<Offset16>), // [n] The actual local offset divided by 2 is stored. The value of n is numGlyphs + 1. The value for numGlyphs is found in the 'maxp' table.
-    Long(Vec<Offset32>), // [n] The actual local offset is stored. The value of n is numGlyphs + 1. The value for numGlyphs is found in the 'maxp' table.
+pub enum LocaTable<'a> {
+    Short(LazyArray<'a, Offset16>), // [n] The actual local offset divided by 2 is stored. The value of n is numGlyphs + 1. The value for numGlyphs is found in the 'maxp' table.
+    Long(LazyArray<'a, Offset32>), // [n] The actual local offset is stored. The value of n is numGlyphs + 1. The value for numGlyphs is found in the 'maxp' table.
 }
 
-impl LocaTable {
-    pub fn parse(data: &[u8], format: LocaOffsetFormat, num_glyphs: u16) -> Option<Self> {
+impl<'a> LocaTable<'a> {
+    pub fn parse(data: &'a [u8], format: LocaOffsetFormat, num_glyphs: u16) -> Result<Self> {
         let mut s = Stream::new(data);
         match format {
             LocaOffsetFormat::Offset16 => {
-                let offsets = s.read_array(num_glyphs as usize + 1)?;
-                Some(Self::Short(offsets))
+                let offsets = s
+                    .read_lazy_array(num_glyphs as usize + 1)
+                    .field("offsets")?;
+                Ok(Self::Short(offsets))
             }
             LocaOffsetFormat::Offset32 => {
-                let offsets = s.read_array(num_glyphs as usize + 1)?;
-                Some(Self::Long(offsets))
+                let offsets = s
+                    .read_lazy_array(num_glyphs as usize + 1)
+                    .field("offsets")?;
+                Ok(Self::Long(offsets))
             }
         }
     }
 
-    pub fn at(&self, index: usize) -> usize {
+    pub fn at(&self, index: usize) -> Option<usize> {
         match self {
-            Self::Short(offsets) => offsets[index] as usize,
-            Self::Long(offsets) => offsets[index] as usize,
+            Self::Short(offsets) => offsets.get(index).map(|x| x as usize),
+            Self::Long(offsets) => offsets.get(index).map(|x| x as usize),
         }
     }
 
@@ -41,6 +46,10 @@ impl LocaTable {
         }
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     pub fn get_glyf_range(&self, glyph_id: u16) -> Option<Range<usize>> {
         let glyph_id = glyph_id as usize;
         let next_glyph_id = glyph_id + 1;
@@ -50,13 +59,13 @@ impl LocaTable {
 
         let range = match self {
             Self::Short(offsets) => {
-                let start = *offsets.get(glyph_id)? as usize * 2;
-                let end = *offsets.get(next_glyph_id)? as usize * 2;
+                let start = offsets.get(glyph_id)? as usize * 2;
+                let end = offsets.get(next_glyph_id)? as usize * 2;
                 start..end
             }
             Self::Long(offsets) => {
-                let start = *offsets.get(glyph_id)? as usize;
-                let end = *offsets.get(next_glyph_id)? as usize;
+                let start = offsets.get(glyph_id)? as usize;
+                let end = offsets.get(next_glyph_id)? as usize;
                 start..end
             }
         };
@@ -1,6 +1,7 @@
 use crate::{
     data_types::{uint16, Version16Dot16},
     decoder::{FromData, Stream},
+    error::{Context, Result},
 };
 
 #[allow(non_snake_case)]
@@ -12,15 +13,15 @@ pub struct MaxpTable {
 
 impl MaxpTable {
     #[allow(non_snake_case)]
-    pub fn parse(data: &[u8]) -> Option<Self> {
+    pub fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let version = s.read()?;
-        let numGlyphs = s.read()?;
+        let version = s.read().field("version")?;
+        let numGlyphs = s.read().field("numGlyphs")?;
         let version1 = match version {
-            Version16Dot16(00010000) => Some(s.read()?),
+            Version16Dot16(0x00010000) => Some(s.read().field("version1")?),
             _ => None,
         };
-        Some(Self {
+        Ok(Self {
             version,
             numGlyphs,
             version1,
@@ -53,22 +54,22 @@ pub struct MaxpTableVersion1Extension {
 impl FromData for MaxpTableVersion1Extension {
     const SIZE: usize = 2 * 13;
     #[allow(non_snake_case)]
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let maxPoints = s.read()?;
-        let maxContours = s.read()?;
-        let maxCompositePoints = s.read()?;
-        let maxCompositeContours = s.read()?;
-        let maxZones = s.read()?;
-        let maxTwilightPoints = s.read()?;
-        let maxStorage = s.read()?;
-        let maxFunctionDefs = s.read()?;
-        let maxInstructionDefs = s.read()?;
-        let maxStackElements = s.read()?;
-        let maxSizeOfInstructions = s.read()?;
-        let maxComponentElements = s.read()?;
-        let maxComponentDepth = s.read()?;
-        Some(Self {
+        let maxPoints = s.read().field("maxPoints")?;
+        let maxContours = s.read().field("maxContours")?;
+        let maxCompositePoints = s.read().field("maxCompositePoints")?;
+        let maxCompositeContours = s.read().field("maxCompositeContours")?;
+        let maxZones = s.read().field("maxZones")?;
+        let maxTwilightPoints = s.read().field("maxTwilightPoints")?;
+        let maxStorage = s.read().field("maxStorage")?;
+        let maxFunctionDefs = s.read().field("maxFunctionDefs")?;
+        let maxInstructionDefs = s.read().field("maxInstructionDefs")?;
+        let maxStackElements = s.read().field("maxStackElements")?;
+        let maxSizeOfInstructions = s.read().field("maxSizeOfInstructions")?;
+        let maxComponentElements = s.read().field("maxComponentElements")?;
+        let maxComponentDepth = s.read().field("maxComponentDepth")?;
+        Ok(Self {
             maxPoints,
             maxContours,
             maxCompositePoints,
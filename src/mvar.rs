@@ -0,0 +1,94 @@
+// 'MVAR' テーブル．OS/2, hhea, post などのフォント全体の値の可変フォントでの差分．
+use crate::{
+    data_types::{uint16, Offset16, Tag},
+    decoder::{parse_optional_subtable, FromData, Stream},
+    error::{Context, Result},
+    item_variation::ItemVariationStore,
+};
+
+// value tag の一部．
+pub const HORIZONTAL_ASCENDER: Tag = Tag::from_be_bytes(*b"hasc"); // OS/2.sTypoAscender
+pub const HORIZONTAL_DESCENDER: Tag = Tag::from_be_bytes(*b"hdsc"); // OS/2.sTypoDescender
+pub const HORIZONTAL_LINE_GAP: Tag = Tag::from_be_bytes(*b"hlgp"); // OS/2.sTypoLineGap
+pub const HORIZONTAL_CLIPPING_ASCENT: Tag = Tag::from_be_bytes(*b"hcla"); // OS/2.usWinAscent
+pub const HORIZONTAL_CLIPPING_DESCENT: Tag = Tag::from_be_bytes(*b"hcld"); // OS/2.usWinDescent
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Copy)]
+pub struct ValueRecord {
+    pub valueTag: Tag,              //Four-byte tag identifying a font-wide measure.
+    pub deltaSetOuterIndex: uint16, //A delta-set outer index — used to select an item variation data subtable within the item variation store.
+    pub deltaSetInnerIndex: uint16, //A delta-set inner index — used to select a delta-set row within an item variation data subtable.
+}
+
+impl FromData for ValueRecord {
+    const SIZE: usize = 8;
+    fn parse(data: &[u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        Ok(Self {
+            valueTag: s.read().field("valueTag")?,
+            deltaSetOuterIndex: s.read().field("deltaSetOuterIndex")?,
+            deltaSetInnerIndex: s.read().field("deltaSetInnerIndex")?,
+        })
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug)]
+pub struct MvarTable<'a> {
+    pub majorVersion: uint16, //Major version number of the metrics variations table — set to 1.
+    pub minorVersion: uint16, //Minor version number of the metrics variations table — set to 0.
+    pub reserved: uint16,     //Not used; set to 0.
+    pub valueRecordSize: uint16, //The size in bytes of each value record — must be greater than zero.
+    pub valueRecordCount: uint16, //The number of value records — may be zero.
+    pub itemVariationStoreOffset: Offset16, //Offset in bytes from the start of this table to the item variation store table. If valueRecordCount is zero, set to zero; if valueRecordCount is greater than zero, must be greater than zero.
+    pub valueRecords: Vec<ValueRecord>, //[valueRecordCount] Array of value records that identify target items and the associated delta-set index for each. The valueTag records must be in binary order of their valueTag field.
+    pub itemVariationStore: Option<ItemVariationStore<'a>>,
+}
+
+impl<'a> MvarTable<'a> {
+    #[allow(non_snake_case)]
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let majorVersion = s.read().field("majorVersion")?;
+        let minorVersion = s.read().field("minorVersion")?;
+        let reserved = s.read().field("reserved")?;
+        let valueRecordSize: uint16 = s.read().field("valueRecordSize")?;
+        let valueRecordCount: uint16 = s.read().field("valueRecordCount")?;
+        let itemVariationStoreOffset: Offset16 = s.read().field("itemVariationStoreOffset")?;
+        // 将来の version で value record が大きくなっても，先頭の 8 バイトだけを読む．
+        let record_size = (valueRecordSize as usize).max(ValueRecord::SIZE);
+        let valueRecords = s
+            .read_unsized_array(valueRecordCount as usize, record_size, |data| {
+                ValueRecord::parse(&data[..ValueRecord::SIZE])
+            })
+            .field("valueRecords")?;
+        let itemVariationStore =
+            parse_optional_subtable(data, itemVariationStoreOffset, ItemVariationStore::parse)
+                .field("itemVariationStore")?;
+        Ok(Self {
+            majorVersion,
+            minorVersion,
+            reserved,
+            valueRecordSize,
+            valueRecordCount,
+            itemVariationStoreOffset,
+            valueRecords,
+            itemVariationStore,
+        })
+    }
+
+    // 正規化した座標での tag の値の差分．tag が無ければ 0．
+    pub fn get_delta(&self, tag: Tag, coords: &[f64]) -> f64 {
+        let Some(store) = &self.itemVariationStore else {
+            return 0.0;
+        };
+        self.valueRecords
+            .iter()
+            .find(|record| record.valueTag == tag)
+            .and_then(|record| {
+                store.get_delta(record.deltaSetOuterIndex, record.deltaSetInnerIndex, coords)
+            })
+            .unwrap_or(0.0)
+    }
+}
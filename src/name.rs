@@ -4,12 +4,13 @@ use std::mem::size_of;
 
 use crate::{
     data_types::Offset16,
-    decoder::{FromData, Stream},
+    decoder::{get_subtable_data, FromData, LazyArray, Stream},
+    error::{Context, Error, Result},
     id::{EncodingID, LanguageID, NameID, PlatformID},
 };
 
 #[allow(non_snake_case)]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct NameRecord {
     pub platformId: PlatformID,
     pub encodingId: EncodingID,
@@ -21,16 +22,16 @@ pub struct NameRecord {
 
 impl FromData for NameRecord {
     const SIZE: usize = 2 * 6;
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let platform_id = s.read()?;
-        Some(Self {
+        let platform_id = s.read().field("platformId")?;
+        Ok(Self {
             platformId: PlatformID::new(platform_id),
-            encodingId: EncodingID::new(s.read()?, platform_id),
-            languageId: LanguageID::new(s.read()?, platform_id),
-            nameId: NameID(s.read()?),
-            length: s.read()?,
-            stringOffset: s.read()?,
+            encodingId: EncodingID::new(s.read().field("encodingId")?, platform_id),
+            languageId: LanguageID::new(s.read().field("languageId")?, platform_id),
+            nameId: NameID(s.read().field("nameId")?),
+            length: s.read().field("length")?,
+            stringOffset: s.read().field("stringOffset")?,
         })
     }
 }
@@ -53,11 +54,11 @@ pub struct LangTagRecord {
 
 impl FromData for LangTagRecord {
     const SIZE: usize = 4;
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        Some(Self {
-            length: s.read()?,
-            langTagOffset: s.read()?,
+        Ok(Self {
+            length: s.read().field("length")?,
+            langTagOffset: s.read().field("langTagOffset")?,
         })
     }
 }
@@ -67,19 +68,19 @@ pub struct NameTable<'a> {
     pub version: u16,
     pub count: u16,
     pub storageOffset: Offset16,
-    pub nameRecords: Vec<NameRecord>,
+    pub nameRecords: LazyArray<'a, NameRecord>,
     pub langTagCount: u16,
     pub langTagRecords: Vec<LangTagRecord>,
     pub storage: &'a [u8],
 }
 
 impl<'a> NameTable<'a> {
-    pub fn parse(data: &'a [u8]) -> Option<Self> {
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let version = s.read()?;
-        let count = s.read()?;
-        let storage_offset = s.read()?;
-        let name_records = s.read_array(count as usize)?;
+        let version = s.read().field("version")?;
+        let count = s.read().field("count")?;
+        let storage_offset = s.read().field("storageOffset")?;
+        let name_records = s.read_lazy_array(count as usize).field("nameRecords")?;
         let (lang_tag_count, lang_tag_records) = match version {
             0 => {
                 let lang_tag_count = 0;
@@ -87,18 +88,19 @@ impl<'a> NameTable<'a> {
                 (lang_tag_count, lang_tag_records)
             }
             1 => {
-                let lang_tag_count = s.read()?;
-                let lang_tag_records = s.read_array(lang_tag_count as usize)?;
+                let lang_tag_count = s.read().field("langTagCount")?;
+                let lang_tag_records = s
+                    .read_array(lang_tag_count as usize)
+                    .field("langTagRecords")?;
                 (lang_tag_count, lang_tag_records)
             }
             _ => {
-                panic!("invalid name table version {}", version);
+                return Err(Error::unsupported(version.into(), 0).with_field("version"));
             }
         };
 
-        let storage = data.get(storage_offset as usize..data.len())?;
-        assert_ne!(storage.len(), 0);
-        Some(Self {
+        let storage = get_subtable_data(data, storage_offset).field("storageOffset")?;
+        Ok(Self {
             version,
             count,
             storageOffset: storage_offset,
@@ -109,16 +111,25 @@ impl<'a> NameTable<'a> {
         })
     }
 
-    pub fn get_string(&self, record: &NameRecord) -> Option<String> {
+    pub fn get_string(&self, record: &NameRecord) -> Result<String> {
         let offset = record.stringOffset as usize;
         let length = record.length as usize;
-        let bytes = self.storage.get(offset..offset + length)?;
+        // エラーの offset は name table の先頭からの位置にする．
+        let base = self.storageOffset as usize + offset;
+        let bytes = self
+            .storage
+            .get(offset..offset + length)
+            .ok_or(Error::eof(base))
+            .field("stringOffset")?;
         match record.platformId {
             PlatformID::Unicode(_) => {
                 // UTF16 BE
                 let mut s = Stream::new(bytes);
-                let bytes: Vec<u16> = s.read_array(length / size_of::<u16>())?;
-                String::from_utf16(&bytes).ok()
+                let bytes: Vec<u16> = s
+                    .read_array(length / size_of::<u16>())
+                    .offset_by(base)
+                    .field("string")?;
+                String::from_utf16(&bytes).map_err(|_| Error::invalid(base).with_field("string"))
             }
             PlatformID::Mac(_) => {
                 //
@@ -128,45 +139,49 @@ impl<'a> NameTable<'a> {
                             0 => {
                                 // Roman is UTF8?
                                 let (cow, _encoding_used, _had_errors) =
-                                    encoding_rs::MACINTOSH.decode(bytes.into());
-                                Some(cow.into())
+                                    encoding_rs::MACINTOSH.decode(bytes);
+                                Ok(cow.into())
                             }
                             1 => {
                                 // Japanese is Shift JIS?
                                 let (cow, _encoding_used, _had_errors) =
-                                    encoding_rs::SHIFT_JIS.decode(bytes.into());
-                                Some(cow.into())
+                                    encoding_rs::SHIFT_JIS.decode(bytes);
+                                Ok(cow.into())
                             }
                             _ => {
                                 // TODO
-                                Some("not implemented".to_owned())
+                                Ok("not implemented".to_owned())
                             }
                         }
                     }
-                    _ => {
-                        panic!("unreachable")
-                    }
+                    _ => Err(Error::invalid(base).with_field("encodingId")),
                 }
             }
             PlatformID::Win(_) => {
                 // UTF16 BE
                 let mut s = Stream::new(bytes);
-                let bytes: Vec<u16> = s.read_array(length / size_of::<u16>())?;
-                String::from_utf16(&bytes).ok()
+                let bytes: Vec<u16> = s
+                    .read_array(length / size_of::<u16>())
+                    .offset_by(base)
+                    .field("string")?;
+                String::from_utf16(&bytes).map_err(|_| Error::invalid(base).with_field("string"))
+            }
+            PlatformID::Unknown(id) => {
+                Err(Error::unsupported(id.into(), base).with_field("platformId"))
             }
         }
     }
 
-    pub fn get_strings_by_name_id(&self, name_id: NameID) -> Vec<LocalizedString> {
+    pub fn get_strings_by_name_id(&self, name_id: NameID) -> Result<Vec<LocalizedString>> {
         let mut v = vec![];
         for name_record in self.nameRecords.iter().filter(|x| x.nameId == name_id) {
-            let string = self.get_string(&name_record).unwrap();
+            let string = self.get_string(&name_record)?;
             v.push(LocalizedString {
                 string,
                 locale: name_record.languageId.to_string(),
             })
         }
-        v
+        Ok(v)
     }
 }
 
@@ -203,7 +218,7 @@ impl<'a, 'b> Iterator for NameTableIter<'a, 'b> {
         if self.index < self.table.count as usize {
             self.index += 1;
             let record = self.table.nameRecords.get(self.index - 1)?;
-            let name = self.table.get_string(&record)?;
+            let name = self.table.get_string(&record).ok()?;
             Some(Self::Item {
                 platformId: record.platformId,
                 encodingId: record.encodingId,
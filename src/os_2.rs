@@ -3,13 +3,14 @@ use core::fmt;
 use crate::{
     data_types::{int16, uint16, uint32, uint8, Tag},
     decoder::{FromData, Stream},
+    error::{Context, Result},
 };
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub struct Weight(pub u16);
 impl FromData for Weight {
     const SIZE: usize = u16::SIZE;
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         u16::parse(data).map(Self)
     }
 }
@@ -83,48 +84,48 @@ pub struct OS2Table {
 
 impl OS2Table {
     #[allow(non_snake_case)]
-    pub fn parse(data: &[u8]) -> Option<Self> {
+    pub fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let version = s.read()?;
-        let xAvgCharWidth = s.read()?;
-        let usWeightClass = s.read()?;
-        let usWidthClass = s.read()?;
-        let fsType = s.read()?;
-        let ySubscriptXSize = s.read()?;
-        let ySubscriptYSize = s.read()?;
-        let ySubscriptXOffset = s.read()?;
-        let ySubscriptYOffset = s.read()?;
-        let ySuperscriptXSize = s.read()?;
-        let ySuperscriptYSize = s.read()?;
-        let ySuperscriptXOffset = s.read()?;
-        let ySuperscriptYOffset = s.read()?;
-        let yStrikeoutSize = s.read()?;
-        let yStrikeoutPosition = s.read()?;
-        let sFamilyClass = s.read()?;
-        let panose = s.read_bytes(10)?.try_into().unwrap();
-        let ulUnicodeRange1 = s.read()?;
-        let ulUnicodeRange2 = s.read()?;
-        let ulUnicodeRange3 = s.read()?;
-        let ulUnicodeRange4 = s.read()?;
-        let achVendID = s.read()?;
-        let fsSelection = s.read()?;
-        let usFirstCharIndex = s.read()?;
-        let usLastCharIndex = s.read()?;
-        let sTypoAscender = s.read();
-        let sTypoDescender = s.read();
-        let sTypoLineGap = s.read();
-        let usWinAscent = s.read();
-        let usWinDescent = s.read();
-        let ulCodePageRange1 = s.read();
-        let ulCodePageRange2 = s.read();
-        let sxHeight = s.read();
-        let sCapHeight = s.read();
-        let usDefaultChar = s.read();
-        let usBreakChar = s.read();
-        let usMaxContext = s.read();
-        let usLowerOpticalPointSize = s.read();
-        let usUpperOpticalPointSize = s.read();
-        Some(Self {
+        let version = s.read().field("version")?;
+        let xAvgCharWidth = s.read().field("xAvgCharWidth")?;
+        let usWeightClass = s.read().field("usWeightClass")?;
+        let usWidthClass = s.read().field("usWidthClass")?;
+        let fsType = s.read().field("fsType")?;
+        let ySubscriptXSize = s.read().field("ySubscriptXSize")?;
+        let ySubscriptYSize = s.read().field("ySubscriptYSize")?;
+        let ySubscriptXOffset = s.read().field("ySubscriptXOffset")?;
+        let ySubscriptYOffset = s.read().field("ySubscriptYOffset")?;
+        let ySuperscriptXSize = s.read().field("ySuperscriptXSize")?;
+        let ySuperscriptYSize = s.read().field("ySuperscriptYSize")?;
+        let ySuperscriptXOffset = s.read().field("ySuperscriptXOffset")?;
+        let ySuperscriptYOffset = s.read().field("ySuperscriptYOffset")?;
+        let yStrikeoutSize = s.read().field("yStrikeoutSize")?;
+        let yStrikeoutPosition = s.read().field("yStrikeoutPosition")?;
+        let sFamilyClass = s.read().field("sFamilyClass")?;
+        let panose = s.read_bytes(10).field("panose")?.try_into().unwrap();
+        let ulUnicodeRange1 = s.read().field("ulUnicodeRange1")?;
+        let ulUnicodeRange2 = s.read().field("ulUnicodeRange2")?;
+        let ulUnicodeRange3 = s.read().field("ulUnicodeRange3")?;
+        let ulUnicodeRange4 = s.read().field("ulUnicodeRange4")?;
+        let achVendID = s.read().field("achVendID")?;
+        let fsSelection = s.read().field("fsSelection")?;
+        let usFirstCharIndex = s.read().field("usFirstCharIndex")?;
+        let usLastCharIndex = s.read().field("usLastCharIndex")?;
+        let sTypoAscender = s.read().ok();
+        let sTypoDescender = s.read().ok();
+        let sTypoLineGap = s.read().ok();
+        let usWinAscent = s.read().ok();
+        let usWinDescent = s.read().ok();
+        let ulCodePageRange1 = s.read().ok();
+        let ulCodePageRange2 = s.read().ok();
+        let sxHeight = s.read().ok();
+        let sCapHeight = s.read().ok();
+        let usDefaultChar = s.read().ok();
+        let usBreakChar = s.read().ok();
+        let usMaxContext = s.read().ok();
+        let usLowerOpticalPointSize = s.read().ok();
+        let usUpperOpticalPointSize = s.read().ok();
+        Ok(Self {
             version,
             xAvgCharWidth,
             usWeightClass,
@@ -171,6 +172,11 @@ impl OS2Table {
         self.fsSelection & (1 << 6) != 0
     }
 
+    // USE_TYPO_METRICS (bit 7)．行の高さに sTypo* を使うべきことを示す．
+    pub fn use_typo_metrics(&self) -> bool {
+        self.fsSelection & (1 << 7) != 0
+    }
+
     pub fn get_weight(&self) -> Weight {
         self.usWeightClass
     }
@@ -0,0 +1,68 @@
+// glyph の輪郭を線分と曲線の命令に変換する．
+// TrueType の輪郭は 2 次ベジエ曲線で，連続する 2 つの off-curve 点の間には，中点に on-curve 点があるとみなす．
+use crate::glyf::GlyphPoint;
+
+// 輪郭を受け取る側が実装する．座標はフォント単位で，y は上向き．
+pub trait OutlinePen {
+    fn move_to(&mut self, x: f64, y: f64);
+    fn line_to(&mut self, x: f64, y: f64);
+    fn quad_to(&mut self, x1: f64, y1: f64, x: f64, y: f64);
+    // TrueType の輪郭からは呼ばれない．
+    fn curve_to(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, x: f64, y: f64);
+    // 現在の点から始点へ直線で戻って輪郭を閉じる．
+    fn close(&mut self);
+}
+
+// Font::outline などが返す点を is_last で輪郭に分けて pen に渡す．
+// 最後の輪郭に is_last が無い場合も，残りの点を一つの輪郭として扱う．
+pub fn draw_points(points: &[GlyphPoint], pen: &mut impl OutlinePen) {
+    let mut start = 0;
+    for (i, point) in points.iter().enumerate() {
+        if point.is_last {
+            draw_contour(&points[start..=i], pen);
+            start = i + 1;
+        }
+    }
+    draw_contour(&points[start..], pen);
+}
+
+fn midpoint(a: &GlyphPoint, b: &GlyphPoint) -> (f64, f64) {
+    ((a.x + b.x) / 2.0, (a.y + b.y) / 2.0)
+}
+
+// FreeType の FT_Outline_Decompose と同じ順に命令を出す．
+fn draw_contour(contour: &[GlyphPoint], pen: &mut impl OutlinePen) {
+    let (Some(first), Some(last)) = (contour.first(), contour.last()) else {
+        return;
+    };
+    // 始点は on-curve でなければならない．
+    // 先頭が off-curve の場合は，末尾が on-curve ならそれを，そうでなければ先頭と末尾の中点を始点にする．
+    let (start, rest) = if first.flags.is_on_curve_point() {
+        ((first.x, first.y), &contour[1..])
+    } else if last.flags.is_on_curve_point() {
+        ((last.x, last.y), &contour[..contour.len() - 1])
+    } else {
+        (midpoint(first, last), contour)
+    };
+    pen.move_to(start.0, start.1);
+
+    let mut control: Option<&GlyphPoint> = None;
+    for point in rest {
+        if point.flags.is_on_curve_point() {
+            match control.take() {
+                Some(control) => pen.quad_to(control.x, control.y, point.x, point.y),
+                None => pen.line_to(point.x, point.y),
+            }
+        } else {
+            if let Some(control) = control {
+                let (x, y) = midpoint(control, point);
+                pen.quad_to(control.x, control.y, x, y);
+            }
+            control = Some(point);
+        }
+    }
+    if let Some(control) = control {
+        pen.quad_to(control.x, control.y, start.0, start.1);
+    }
+    pen.close();
+}
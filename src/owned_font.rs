@@ -0,0 +1,186 @@
+// フォントファイルのバイト列を所有する Font．
+// バイト列は Arc で共有するので，clone は安価で，スレッドをまたいでキャッシュに置いておける．
+// face ごとに Font を 1 つだけ作り，テーブルのパース結果や glyph の解決に使うキャッシュは clone の間で共有する．
+use std::sync::Arc;
+
+use crate::{
+    data_types::Tag,
+    error::Result,
+    font::Font,
+    font_file::FontFile,
+    glyf::{BBox, GlyphPoint},
+    head::HeadTable,
+    hhea::HheaTable,
+    hmtx::LongHorMetric,
+    maxp::MaxpTable,
+    os_2::OS2Table,
+    outline::OutlinePen,
+    table::Table,
+    vmtx::GlyphVMetrics,
+};
+
+// Vec<u8>, Box<[u8]>, Arc<[u8]> のほか，memmap2::Mmap のようなメモリマップも渡せる．
+type Source = Arc<dyn AsRef<[u8]> + Send + Sync>;
+
+// font は source のバイト列を参照する．フィールドは宣言順に drop されるので，font を先に置く．
+struct Face {
+    font: Font<'static>,
+    source: Source,
+}
+
+#[derive(Clone)]
+pub struct OwnedFont {
+    index: usize,
+    face: Arc<Face>,
+}
+
+impl OwnedFont {
+    // collection の場合は index 番目の face を使う．単体のフォントの index は 0．
+    pub fn new<S>(source: S, index: usize) -> Result<Self>
+    where
+        S: AsRef<[u8]> + Send + Sync + 'static,
+    {
+        Self::from_source(Arc::new(source), index)
+    }
+
+    // ファイルをメモリマップして読み込む．ファイルの内容は RAM にコピーしない．
+    // マップしている間にファイルが書き換えられると，読み出す値は不定になる．
+    #[cfg(feature = "memmap2")]
+    pub fn from_path(path: impl AsRef<std::path::Path>, index: usize) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        // SAFETY: マップした領域は読み出すだけで，ファイルの書き換えは呼び出し側が防ぐ．
+        let mmap = unsafe { memmap2::Mmap::map(&file)? };
+        Self::new(mmap, index).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    // collection のすべての face を，同じバイト列を共有したまま読み込む．
+    // FontFile::faces と同じく，壊れた face があっても残りの face は読める．
+    pub fn all_faces<S>(source: S) -> Result<Vec<Result<Self>>>
+    where
+        S: AsRef<[u8]> + Send + Sync + 'static,
+    {
+        let source: Source = Arc::new(source);
+        let len = FontFile::from_bytes((*source).as_ref())?.len();
+        Ok((0..len)
+            .map(|index| Self::from_source(source.clone(), index))
+            .collect())
+    }
+
+    fn from_source(source: Source, index: usize) -> Result<Self> {
+        let data: &[u8] = (*source).as_ref();
+        // SAFETY: data は Arc の中の値が持つバイト列で，Face を move してもアドレスは変わらない．
+        // source は Face の中で font より後に drop されるので，font が参照している間は解放されない．
+        // 'static は外に出さず，font() で self の借用の寿命に縮めてから返す．
+        let data: &'static [u8] = unsafe { &*(data as *const [u8]) };
+        // 壊れた face は作る時点でエラーにする．
+        let table = FontFile::from_bytes(data)?.face(index)?;
+        Ok(Self {
+            index,
+            face: Arc::new(Face {
+                font: Font::from_table(table),
+                source,
+            }),
+        })
+    }
+
+    pub fn data(&self) -> &[u8] {
+        (*self.face.source).as_ref()
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    // 同じファイルの別の face を返す．バイト列はコピーしない．
+    pub fn face(&self, index: usize) -> Result<Self> {
+        Self::from_source(self.face.source.clone(), index)
+    }
+
+    // clone した OwnedFont の間で共有する Font．キャッシュは呼び出しをまたいで使われる．
+    pub fn font(&self) -> &Font<'_> {
+        // SAFETY: Font<'static> の 'static は実際には source の寿命で，self を借用している間は source が生きている．
+        // Font は 'a について不変なので，縮める変換を明示する．Font のキャッシュは自身のテーブルからしか作られないので，
+        // 縮めた寿命のデータが入り込むことはない．
+        unsafe { std::mem::transmute::<&Font<'static>, &Font<'_>>(&self.face.font) }
+    }
+
+    pub fn table(&self) -> &Table<'_> {
+        self.font().table()
+    }
+
+    pub fn table_data(&self, tag: &Tag) -> Result<&[u8]> {
+        self.table().get_table_data(tag)
+    }
+
+    pub fn head(&self) -> Result<&HeadTable> {
+        self.font().head()
+    }
+
+    pub fn maxp(&self) -> Result<&MaxpTable> {
+        self.font().maxp()
+    }
+
+    pub fn hhea(&self) -> Result<&HheaTable> {
+        self.font().hhea()
+    }
+
+    pub fn os2(&self) -> Result<&OS2Table> {
+        self.font().os2()
+    }
+
+    pub fn number_of_glyphs(&self) -> Result<u16> {
+        self.font().number_of_glyphs()
+    }
+
+    pub fn units_per_em(&self) -> Result<u16> {
+        self.font().units_per_em()
+    }
+
+    pub fn family_name(&self) -> Option<String> {
+        self.font().family_name()
+    }
+
+    pub fn glyph_index(&self, c: char) -> Option<u16> {
+        self.font().glyph_index(c)
+    }
+
+    pub fn glyph_name(&self, glyph_id: u16) -> Option<&str> {
+        self.font().glyph_name(glyph_id)
+    }
+
+    pub fn glyph_by_name(&self, name: &str) -> Option<u16> {
+        self.font().glyph_by_name(name)
+    }
+
+    pub fn outline(&self, glyph_id: u16) -> Result<(Vec<GlyphPoint>, BBox)> {
+        self.font().outline(glyph_id)
+    }
+
+    pub fn outline_glyph(&self, glyph_id: u16, pen: &mut impl OutlinePen) -> Result<()> {
+        self.font().outline_glyph(glyph_id, pen)
+    }
+
+    pub fn control_box(&self, glyph_id: u16) -> Result<Option<BBox>> {
+        self.font().control_box(glyph_id)
+    }
+
+    pub fn tight_bbox(&self, glyph_id: u16) -> Result<Option<BBox>> {
+        self.font().tight_bbox(glyph_id)
+    }
+
+    pub fn advance_width(&self, glyph_id: u16) -> Result<u16> {
+        self.font().advance_width(glyph_id)
+    }
+
+    pub fn glyph_h_metrics(&self, glyph_id: u16) -> Result<LongHorMetric> {
+        self.font().glyph_h_metrics(glyph_id)
+    }
+
+    pub fn glyph_v_metrics(&self, glyph_id: u16) -> Result<GlyphVMetrics> {
+        self.font().glyph_v_metrics(glyph_id)
+    }
+
+    pub fn kerning(&self, left: u16, right: u16) -> i32 {
+        self.font().kerning(left, right)
+    }
+}
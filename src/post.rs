@@ -0,0 +1,416 @@
+// 'post' テーブル．PostScript で使う情報と glyph 名を持つ．
+// glyph 名は format 1, 2, 2.5 で引ける．format 3 は名前を持たない．
+use crate::{
+    data_types::{uint16, uint32, Fixed, Version16Dot16, FWORD},
+    decoder::{LazyArray, Stream},
+    error::{Context, Error, Result},
+};
+
+#[allow(non_snake_case)]
+pub struct PostTable<'a> {
+    pub version: Version16Dot16, //0x00010000 for version 1.0, 0x00020000 for version 2.0, 0x00025000 for version 2.5 (deprecated), 0x00030000 for version 3.0
+    pub italicAngle: Fixed, //Italic angle in counter-clockwise degrees from the vertical. Zero for upright text, negative for text that leans to the right (forward).
+    pub underlinePosition: FWORD, //Suggested y-coordinate of the top of the underline.
+    pub underlineThickness: FWORD, //Suggested values for the underline thickness. In general, the underline thickness should match the thickness of the underscore character (U+005F LOW LINE), and should also match the strikeout thickness, which is specified in the OS/2 table.
+    pub isFixedPitch: uint32, //Set to 0 if the font is proportionally spaced, non-zero if the font is not proportionally spaced (i.e. monospaced).
+    pub minMemType42: uint32, //Minimum memory usage when an OpenType font is downloaded.
+    pub maxMemType42: uint32, //Maximum memory usage when an OpenType font is downloaded.
+    pub minMemType1: uint32, //Minimum memory usage when an OpenType font is downloaded as a Type 1 font.
+    pub maxMemType1: uint32, //Maximum memory usage when an OpenType font is downloaded as a Type 1 font.
+    pub glyphNames: PostGlyphNames<'a>,
+}
+
+#[allow(non_snake_case)]
+pub enum PostGlyphNames<'a> {
+    // format 1: 標準の 258 個の Macintosh の名前を glyph id の順に使う．
+    Standard,
+    // format 2: glyphNameIndex が 258 未満なら標準の名前，それ以外は stringData の (index - 258) 番目の Pascal 文字列．
+    Indexed {
+        glyphNameIndex: LazyArray<'a, uint16>, //[numGlyphs] Array of indices into the list of names.
+        names: Vec<&'a [u8]>,                  // stringData の Pascal 文字列．
+    },
+    // format 2.5: glyph id に offset を足した番号の標準の名前を使う．
+    Offsets(LazyArray<'a, i8>), //[numGlyphs] Difference between graphic index and standard order of glyph.
+    // format 3 と，名前の表し方が分からない version．
+    None,
+}
+
+impl<'a> PostTable<'a> {
+    #[allow(non_snake_case)]
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let version: Version16Dot16 = s.read().field("version")?;
+        let italicAngle = s.read().field("italicAngle")?;
+        let underlinePosition = s.read().field("underlinePosition")?;
+        let underlineThickness = s.read().field("underlineThickness")?;
+        let isFixedPitch = s.read().field("isFixedPitch")?;
+        let minMemType42 = s.read().field("minMemType42")?;
+        let maxMemType42 = s.read().field("maxMemType42")?;
+        let minMemType1 = s.read().field("minMemType1")?;
+        let maxMemType1 = s.read().field("maxMemType1")?;
+        let glyphNames = match version.0 {
+            0x00010000 => PostGlyphNames::Standard,
+            0x00020000 => {
+                let numGlyphs: uint16 = s.read().field("numGlyphs")?;
+                let glyphNameIndex = s
+                    .read_lazy_array(numGlyphs as usize)
+                    .field("glyphNameIndex")?;
+                // 参照されていない末尾の文字列も読む．途中で切れている場合はそこまでにする．
+                let mut names = Vec::new();
+                while !s.is_end() {
+                    let len: u8 = s.read().field("stringData")?;
+                    let Ok(name) = s.read_bytes(len as usize) else {
+                        break;
+                    };
+                    names.push(name);
+                }
+                PostGlyphNames::Indexed {
+                    glyphNameIndex,
+                    names,
+                }
+            }
+            0x00025000 => {
+                let numGlyphs: uint16 = s.read().field("numGlyphs")?;
+                let offsets = s.read_lazy_array(numGlyphs as usize).field("offset")?;
+                PostGlyphNames::Offsets(offsets)
+            }
+            0x00030000 | 0x00040000 => PostGlyphNames::None,
+            _ => return Err(Error::unsupported(version.0, 0).with_field("version")),
+        };
+        Ok(Self {
+            version,
+            italicAngle,
+            underlinePosition,
+            underlineThickness,
+            isFixedPitch,
+            minMemType42,
+            maxMemType42,
+            minMemType1,
+            maxMemType1,
+            glyphNames,
+        })
+    }
+
+    pub fn italic_angle(&self) -> f64 {
+        self.italicAngle.to_f64()
+    }
+
+    pub fn is_fixed_pitch(&self) -> bool {
+        self.isFixedPitch != 0
+    }
+
+    // 名前が無いか，ASCII でない場合は None を返す．
+    pub fn glyph_name(&self, glyph_id: u16) -> Option<&'a str> {
+        let index = glyph_id as usize;
+        match &self.glyphNames {
+            PostGlyphNames::Standard => MAC_GLYPH_NAMES.get(index).copied(),
+            PostGlyphNames::Indexed {
+                glyphNameIndex,
+                names,
+            } => {
+                let name_index = glyphNameIndex.get(index)? as usize;
+                match name_index.checked_sub(MAC_GLYPH_NAMES.len()) {
+                    None => Some(MAC_GLYPH_NAMES[name_index]),
+                    Some(i) => {
+                        let name = names.get(i)?;
+                        if name.is_ascii() {
+                            core::str::from_utf8(name).ok()
+                        } else {
+                            None
+                        }
+                    }
+                }
+            }
+            PostGlyphNames::Offsets(offsets) => {
+                let offset = offsets.get(index)?;
+                let name_index = index.checked_add_signed(offset.into())?;
+                MAC_GLYPH_NAMES.get(name_index).copied()
+            }
+            PostGlyphNames::None => None,
+        }
+    }
+
+    // 名前が付いている glyph の数．format 1 は 258 になる．
+    pub fn len(&self) -> usize {
+        match &self.glyphNames {
+            PostGlyphNames::Standard => MAC_GLYPH_NAMES.len(),
+            PostGlyphNames::Indexed { glyphNameIndex, .. } => glyphNameIndex.len(),
+            PostGlyphNames::Offsets(offsets) => offsets.len(),
+            PostGlyphNames::None => 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // 名前から glyph id を引く．同じ名前が複数ある場合は最小の glyph id を返す．
+    // glyph を順にたどるので，多数の名前を引く場合は結果を保持して使う．
+    pub fn glyph_by_name(&self, name: &str) -> Option<u16> {
+        (0..self.len().min(0x10000))
+            .map(|glyph_id| glyph_id as u16)
+            .find(|&glyph_id| self.glyph_name(glyph_id) == Some(name))
+    }
+}
+
+// 標準の Macintosh の glyph 名の並び．format 1 と 2, 2.5 で使う．
+pub const MAC_GLYPH_NAMES: [&str; 258] = [
+    ".notdef",
+    ".null",
+    "nonmarkingreturn",
+    "space",
+    "exclam",
+    "quotedbl",
+    "numbersign",
+    "dollar",
+    "percent",
+    "ampersand",
+    "quotesingle",
+    "parenleft",
+    "parenright",
+    "asterisk",
+    "plus",
+    "comma",
+    "hyphen",
+    "period",
+    "slash",
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "colon",
+    "semicolon",
+    "less",
+    "equal",
+    "greater",
+    "question",
+    "at",
+    "A",
+    "B",
+    "C",
+    "D",
+    "E",
+    "F",
+    "G",
+    "H",
+    "I",
+    "J",
+    "K",
+    "L",
+    "M",
+    "N",
+    "O",
+    "P",
+    "Q",
+    "R",
+    "S",
+    "T",
+    "U",
+    "V",
+    "W",
+    "X",
+    "Y",
+    "Z",
+    "bracketleft",
+    "backslash",
+    "bracketright",
+    "asciicircum",
+    "underscore",
+    "grave",
+    "a",
+    "b",
+    "c",
+    "d",
+    "e",
+    "f",
+    "g",
+    "h",
+    "i",
+    "j",
+    "k",
+    "l",
+    "m",
+    "n",
+    "o",
+    "p",
+    "q",
+    "r",
+    "s",
+    "t",
+    "u",
+    "v",
+    "w",
+    "x",
+    "y",
+    "z",
+    "braceleft",
+    "bar",
+    "braceright",
+    "asciitilde",
+    "Adieresis",
+    "Aring",
+    "Ccedilla",
+    "Eacute",
+    "Ntilde",
+    "Odieresis",
+    "Udieresis",
+    "aacute",
+    "agrave",
+    "acircumflex",
+    "adieresis",
+    "atilde",
+    "aring",
+    "ccedilla",
+    "eacute",
+    "egrave",
+    "ecircumflex",
+    "edieresis",
+    "iacute",
+    "igrave",
+    "icircumflex",
+    "idieresis",
+    "ntilde",
+    "oacute",
+    "ograve",
+    "ocircumflex",
+    "odieresis",
+    "otilde",
+    "uacute",
+    "ugrave",
+    "ucircumflex",
+    "udieresis",
+    "dagger",
+    "degree",
+    "cent",
+    "sterling",
+    "section",
+    "bullet",
+    "paragraph",
+    "germandbls",
+    "registered",
+    "copyright",
+    "trademark",
+    "acute",
+    "dieresis",
+    "notequal",
+    "AE",
+    "Oslash",
+    "infinity",
+    "plusminus",
+    "lessequal",
+    "greaterequal",
+    "yen",
+    "mu",
+    "partialdiff",
+    "summation",
+    "product",
+    "pi",
+    "integral",
+    "ordfeminine",
+    "ordmasculine",
+    "Omega",
+    "ae",
+    "oslash",
+    "questiondown",
+    "exclamdown",
+    "logicalnot",
+    "radical",
+    "florin",
+    "approxequal",
+    "Delta",
+    "guillemotleft",
+    "guillemotright",
+    "ellipsis",
+    "nonbreakingspace",
+    "Agrave",
+    "Atilde",
+    "Otilde",
+    "OE",
+    "oe",
+    "endash",
+    "emdash",
+    "quotedblleft",
+    "quotedblright",
+    "quoteleft",
+    "quoteright",
+    "divide",
+    "lozenge",
+    "ydieresis",
+    "Ydieresis",
+    "fraction",
+    "currency",
+    "guilsinglleft",
+    "guilsinglright",
+    "fi",
+    "fl",
+    "daggerdbl",
+    "periodcentered",
+    "quotesinglbase",
+    "quotedblbase",
+    "perthousand",
+    "Acircumflex",
+    "Ecircumflex",
+    "Aacute",
+    "Edieresis",
+    "Egrave",
+    "Iacute",
+    "Icircumflex",
+    "Idieresis",
+    "Igrave",
+    "Oacute",
+    "Ocircumflex",
+    "apple",
+    "Ograve",
+    "Uacute",
+    "Ucircumflex",
+    "Ugrave",
+    "dotlessi",
+    "circumflex",
+    "tilde",
+    "macron",
+    "breve",
+    "dotaccent",
+    "ring",
+    "cedilla",
+    "hungarumlaut",
+    "ogonek",
+    "caron",
+    "Lslash",
+    "lslash",
+    "Scaron",
+    "scaron",
+    "Zcaron",
+    "zcaron",
+    "brokenbar",
+    "Eth",
+    "eth",
+    "Yacute",
+    "yacute",
+    "Thorn",
+    "thorn",
+    "minus",
+    "multiply",
+    "onesuperior",
+    "twosuperior",
+    "threesuperior",
+    "onehalf",
+    "onequarter",
+    "threequarters",
+    "franc",
+    "Gbreve",
+    "gbreve",
+    "Idotaccent",
+    "Scedilla",
+    "scedilla",
+    "Cacute",
+    "cacute",
+    "Ccaron",
+    "ccaron",
+    "dcroat",
+];
@@ -0,0 +1,364 @@
+// glyph の輪郭をアンチエイリアスした 8 ビットのアルファ値のビットマップにする．
+// 各画素を覆う面積を符号付きで足し込み，最後に行の左から累積して被覆率を求める．ヒンティングは行わない．
+use std::io::{self, Write};
+
+use crate::{
+    error::{Context, Error, Result},
+    font::Font,
+    outline::{draw_points, OutlinePen},
+};
+
+// 輪郭の線分と曲線を受け取って被覆率を求める．座標は画素単位で，y は下向き．
+pub struct Rasterizer {
+    width: usize,
+    height: usize,
+    accumulation: Vec<f32>, // 右端の線分は右隣の 2 画素まで書き込むので，2 つ余分に持つ．
+}
+
+impl Rasterizer {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            accumulation: vec![0.0; width * height + 2],
+        }
+    }
+
+    pub fn draw_line(&mut self, (x0, y0): (f32, f32), (x1, y1): (f32, f32)) {
+        if y0 == y1 || !(y0.is_finite() && y1.is_finite()) {
+            return;
+        }
+        // 上から下へたどる．向きは符号で表す．
+        let (direction, (x0, y0), (x1, y1)) = if y0 < y1 {
+            (1.0, (x0, y0), (x1, y1))
+        } else {
+            (-1.0, (x1, y1), (x0, y0))
+        };
+        // 範囲外の x は端に寄せる．面積の合計は変わらない．
+        let clamp = |x: f32| x.clamp(0.0, self.width as f32);
+        let dxdy = (x1 - x0) / (y1 - y0);
+        let mut x = x0;
+        if y0 < 0.0 {
+            x -= y0 * dxdy;
+        }
+        let y_start = y0.max(0.0) as usize;
+        let y_end = (y1.ceil().max(0.0) as usize).min(self.height);
+        for y in y_start..y_end {
+            let line_start = y * self.width;
+            let dy = ((y + 1) as f32).min(y1) - (y as f32).max(y0);
+            let x_next = x + dxdy * dy;
+            let d = dy * direction;
+            let (left, right) = if x < x_next {
+                (clamp(x), clamp(x_next))
+            } else {
+                (clamp(x_next), clamp(x))
+            };
+            let left_floor = left.floor();
+            let left_index = left_floor as usize;
+            let right_ceil = right.ceil();
+            let right_index = right_ceil as usize;
+            let cell = &mut self.accumulation[line_start..];
+            if right_index <= left_index + 1 {
+                // 1 つの画素の中を通る．
+                let middle = 0.5 * (left + right) - left_floor;
+                cell[left_index] += d - d * middle;
+                cell[left_index + 1] += d * middle;
+            } else {
+                let s = (right - left).recip();
+                let left_fraction = left - left_floor;
+                let a0 = 0.5 * s * (1.0 - left_fraction) * (1.0 - left_fraction);
+                let right_fraction = right - right_ceil + 1.0;
+                let am = 0.5 * s * right_fraction * right_fraction;
+                cell[left_index] += d * a0;
+                if right_index == left_index + 2 {
+                    cell[left_index + 1] += d * (1.0 - a0 - am);
+                } else {
+                    let a1 = s * (1.5 - left_fraction);
+                    cell[left_index + 1] += d * (a1 - a0);
+                    for value in &mut cell[left_index + 2..right_index - 1] {
+                        *value += d * s;
+                    }
+                    let a2 = a1 + (right_index - left_index - 3) as f32 * s;
+                    cell[right_index - 1] += d * (1.0 - a2 - am);
+                }
+                cell[right_index] += d * am;
+            }
+            x = x_next;
+        }
+    }
+
+    // 曲線は制御点のずれの大きさに応じた数の線分に分ける．
+    pub fn draw_quad(&mut self, p0: (f32, f32), p1: (f32, f32), p2: (f32, f32)) {
+        let deviation = hypot((p0.0 - 2.0 * p1.0 + p2.0, p0.1 - 2.0 * p1.1 + p2.1));
+        let n = segment_count(deviation);
+        let mut previous = p0;
+        for i in 1..=n {
+            let t = i as f32 / n as f32;
+            let u = 1.0 - t;
+            let point = (
+                u * u * p0.0 + 2.0 * u * t * p1.0 + t * t * p2.0,
+                u * u * p0.1 + 2.0 * u * t * p1.1 + t * t * p2.1,
+            );
+            self.draw_line(previous, point);
+            previous = point;
+        }
+    }
+
+    pub fn draw_cubic(&mut self, p0: (f32, f32), p1: (f32, f32), p2: (f32, f32), p3: (f32, f32)) {
+        let deviation = hypot((p0.0 - 2.0 * p1.0 + p2.0, p0.1 - 2.0 * p1.1 + p2.1))
+            .max(hypot((p1.0 - 2.0 * p2.0 + p3.0, p1.1 - 2.0 * p2.1 + p3.1)));
+        let n = segment_count(deviation * 1.5);
+        let mut previous = p0;
+        for i in 1..=n {
+            let t = i as f32 / n as f32;
+            let u = 1.0 - t;
+            let point = (
+                u * u * u * p0.0
+                    + 3.0 * u * u * t * p1.0
+                    + 3.0 * u * t * t * p2.0
+                    + t * t * t * p3.0,
+                u * u * u * p0.1
+                    + 3.0 * u * u * t * p1.1
+                    + 3.0 * u * t * t * p2.1
+                    + t * t * t * p3.1,
+            );
+            self.draw_line(previous, point);
+            previous = point;
+        }
+    }
+
+    // 行ごとに左から累積した被覆率を 0..=255 にする．重なった輪郭は 255 で打ち切る．
+    pub fn into_alpha(self) -> Vec<u8> {
+        let mut sum = 0.0f32;
+        self.accumulation[..self.width * self.height]
+            .iter()
+            .map(|value| {
+                sum += value;
+                (sum.abs().min(1.0) * 255.0).round() as u8
+            })
+            .collect()
+    }
+}
+
+fn hypot((x, y): (f32, f32)) -> f32 {
+    x.hypot(y)
+}
+
+// 画素の 1/3 程度の誤差に収まる線分の数．
+fn segment_count(deviation: f32) -> usize {
+    if deviation.is_nan() || deviation <= 0.333 {
+        return 1;
+    }
+    (1.0 + (3.0 * deviation).sqrt().floor()).min(1000.0) as usize
+}
+
+// フォント単位の座標を拡大し，上下を反転して rasterizer に渡す．
+struct ScaledPen<'r> {
+    rasterizer: &'r mut Rasterizer,
+    scale: f64,
+    origin: (f64, f64), // ビットマップの左上から見た原点の位置 (画素単位)．
+    start: (f32, f32),
+    current: (f32, f32),
+}
+
+impl ScaledPen<'_> {
+    fn map(&self, x: f64, y: f64) -> (f32, f32) {
+        (
+            (self.origin.0 + x * self.scale) as f32,
+            (self.origin.1 - y * self.scale) as f32,
+        )
+    }
+}
+
+impl OutlinePen for ScaledPen<'_> {
+    fn move_to(&mut self, x: f64, y: f64) {
+        self.start = self.map(x, y);
+        self.current = self.start;
+    }
+
+    fn line_to(&mut self, x: f64, y: f64) {
+        let point = self.map(x, y);
+        self.rasterizer.draw_line(self.current, point);
+        self.current = point;
+    }
+
+    fn quad_to(&mut self, x1: f64, y1: f64, x: f64, y: f64) {
+        let point = self.map(x, y);
+        self.rasterizer
+            .draw_quad(self.current, self.map(x1, y1), point);
+        self.current = point;
+    }
+
+    fn curve_to(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, x: f64, y: f64) {
+        let point = self.map(x, y);
+        self.rasterizer
+            .draw_cubic(self.current, self.map(x1, y1), self.map(x2, y2), point);
+        self.current = point;
+    }
+
+    fn close(&mut self) {
+        self.rasterizer.draw_line(self.current, self.start);
+        self.current = self.start;
+    }
+}
+
+// 8 ビットのアルファ値のビットマップ．
+// left は原点から左端までの画素数，top はベースラインから上端までの画素数 (上向きが正)．
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bitmap {
+    pub width: usize,
+    pub height: usize,
+    pub left: i32,
+    pub top: i32,
+    pub data: Vec<u8>, // 上の行から順に width * height 個．
+}
+
+// ビットマップの大きさの上限．壊れた座標で巨大なメモリを確保しないようにする．
+// 幅と高さはそれぞれ MAX_BITMAP_SIZE まで，画素数は MAX_BITMAP_AREA (4M 画素) までにする．
+pub(crate) const MAX_BITMAP_SIZE: usize = 1 << 14;
+pub(crate) const MAX_BITMAP_AREA: usize = 1 << 22;
+
+// pixel_size は 1 em の画素数．offset は原点を右と上にずらす量 (画素単位) で，サブピクセルの位置合わせに使う．
+// 輪郭を持たない glyph は大きさ 0 のビットマップを返す．
+pub fn rasterize_glyph(
+    font: &Font,
+    glyph_id: u16,
+    pixel_size: f64,
+    offset: (f64, f64),
+) -> Result<Bitmap> {
+    let units_per_em = font.units_per_em()?;
+    if units_per_em == 0 {
+        return Err(Error::invalid(18).with_field("unitsPerEm"));
+    }
+    let scale = pixel_size / f64::from(units_per_em);
+    let resolved = font.resolve_glyph(glyph_id)?;
+    let Some(first) = resolved.points.first() else {
+        return Ok(Bitmap::default());
+    };
+    let (mut x_min, mut y_min, mut x_max, mut y_max) = (first.x, first.y, first.x, first.y);
+    for point in &resolved.points {
+        (x_min, x_max) = (x_min.min(point.x), x_max.max(point.x));
+        (y_min, y_max) = (y_min.min(point.y), y_max.max(point.y));
+    }
+    let left = (x_min * scale + offset.0).floor();
+    let right = (x_max * scale + offset.0).ceil();
+    let bottom = (y_min * scale + offset.1).floor();
+    let top = (y_max * scale + offset.1).ceil();
+    let (width, height) = (right - left, top - bottom);
+    if !(width >= 0.0 && height >= 0.0)
+        || width > MAX_BITMAP_SIZE as f64
+        || height > MAX_BITMAP_SIZE as f64
+        || width * height > MAX_BITMAP_AREA as f64
+    {
+        return Err(Error::invalid(0)).field("pixelSize");
+    }
+    let (width, height) = (width as usize, height as usize);
+
+    let mut rasterizer = Rasterizer::new(width, height);
+    let mut pen = ScaledPen {
+        rasterizer: &mut rasterizer,
+        scale,
+        origin: (offset.0 - left, top - offset.1),
+        start: (0.0, 0.0),
+        current: (0.0, 0.0),
+    };
+    draw_points(&resolved.points, &mut pen);
+    Ok(Bitmap {
+        width,
+        height,
+        left: left as i32,
+        top: top as i32,
+        data: rasterizer.into_alpha(),
+    })
+}
+
+impl Bitmap {
+    pub fn get(&self, x: usize, y: usize) -> Option<u8> {
+        if x < self.width {
+            self.data.get(y * self.width + x).copied()
+        } else {
+            None
+        }
+    }
+
+    // 白地に黒の binary PGM (P5) を書き出す．
+    pub fn write_pgm(&self, w: &mut impl Write) -> io::Result<()> {
+        write!(w, "P5\n{} {}\n255\n", self.width, self.height)?;
+        let pixels: Vec<u8> = self.data.iter().map(|alpha| 255 - alpha).collect();
+        w.write_all(&pixels)
+    }
+
+    // 黒のグレースケールにアルファ値を付けた PNG を書き出す．背景は透明になる．
+    // 圧縮は行わず，deflate の無圧縮ブロックに入れる．
+    pub fn write_png(&self, w: &mut impl Write) -> io::Result<()> {
+        let mut raw = Vec::with_capacity((self.width * 2 + 1) * self.height);
+        for row in self.data.chunks(self.width.max(1)).take(self.height) {
+            raw.push(0); // filter type: None
+            for &alpha in row {
+                raw.extend_from_slice(&[0, alpha]);
+            }
+        }
+
+        let mut header = Vec::new();
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        // bit depth 8, color type 4 (gray + alpha), compression, filter, interlace.
+        header.extend_from_slice(&[8, 4, 0, 0, 0]);
+
+        w.write_all(b"\x89PNG\r\n\x1a\n")?;
+        write_png_chunk(w, b"IHDR", &header)?;
+        write_png_chunk(w, b"IDAT", &zlib_stored(&raw))?;
+        write_png_chunk(w, b"IEND", &[])
+    }
+}
+
+fn write_png_chunk(w: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    w.write_all(&(data.len() as u32).to_be_bytes())?;
+    w.write_all(kind)?;
+    w.write_all(data)?;
+    let crc = crc32(&[kind, data]);
+    w.write_all(&crc.to_be_bytes())
+}
+
+// 無圧縮の deflate ブロックを並べた zlib ストリーム．
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(0xFFFF).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        let is_final = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(is_final as u8);
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(parts: &[&[u8]]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in parts.iter().flat_map(|part| part.iter()) {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB88320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + u32::from(byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    b << 16 | a
+}
@@ -1,6 +1,7 @@
 use crate::{
     data_types::{uint16, Fixed, Offset16, Offset32, Tag},
-    decoder::{FromData, Stream},
+    decoder::{get_subtable_data, parse_subtable, FromData, Stream},
+    error::{Context, Error, Result},
 };
 
 #[allow(non_snake_case)]
@@ -19,39 +20,38 @@ pub struct StatHeader {
 #[allow(non_snake_case)]
 impl FromData for StatHeader {
     const SIZE: usize = 4 * 2 + 4 + 2 + 4 + 2;
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let majorVersion = s.read()?;
-        assert_eq!(majorVersion, 1);
+        let majorVersion: uint16 = s.read().field("majorVersion")?;
+        if majorVersion != 1 {
+            return Err(Error::unsupported(majorVersion.into(), 0).with_field("majorVersion"));
+        }
 
-        let minorVersion = s.read()?;
-        assert!([1_u16, 2].contains(&minorVersion)); // 0 is deprecated.
+        let minorVersion: uint16 = s.read().field("minorVersion")?;
+        if ![1_u16, 2].contains(&minorVersion) {
+            // 0 is deprecated.
+            return Err(Error::unsupported(minorVersion.into(), 2).with_field("minorVersion"));
+        }
 
-        let designAxisSize = s.read()?;
-        let designAxisCount = s.read()?;
+        let designAxisSize = s.read().field("designAxisSize")?;
+        let designAxisCount = s.read().field("designAxisCount")?;
 
-        let designAxesOffset = s.read()?;
-        assert!(if designAxisCount > 0 {
-            designAxesOffset > 0
-        } else {
-            designAxesOffset == 0
-        });
+        let designAxesOffset: Offset32 = s.read().field("designAxesOffset")?;
+        if designAxisCount > 0 && designAxesOffset == 0 {
+            return Err(Error::invalid(8).with_field("designAxesOffset"));
+        }
 
-        let axisValueCount = s.read()?;
-        assert!(if axisValueCount > 0 {
-            designAxisCount > 0
-        } else {
-            designAxisCount == 0
-        });
+        let axisValueCount: uint16 = s.read().field("axisValueCount")?;
+        if axisValueCount > 0 && designAxisCount == 0 {
+            return Err(Error::invalid(6).with_field("designAxisCount"));
+        }
 
-        let offsetToAxisValueOffsets = s.read()?;
-        assert!(if axisValueCount > 0 {
-            offsetToAxisValueOffsets > 0
-        } else {
-            offsetToAxisValueOffsets == 0
-        });
-        let elidedFallbackNameID = s.read()?;
-        Some(Self {
+        let offsetToAxisValueOffsets: Offset32 = s.read().field("offsetToAxisValueOffsets")?;
+        if axisValueCount > 0 && offsetToAxisValueOffsets == 0 {
+            return Err(Error::invalid(14).with_field("offsetToAxisValueOffsets"));
+        }
+        let elidedFallbackNameID = s.read().field("elidedFallbackNameID")?;
+        Ok(Self {
             majorVersion,
             minorVersion,
             designAxisSize,
@@ -75,12 +75,12 @@ pub struct AxisRecord {
 impl FromData for AxisRecord {
     const SIZE: usize = 4 + 2 + 2;
     #[allow(non_snake_case)]
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let axisTag = s.read()?;
-        let axisNameID = s.read()?;
-        let axisOrdering = s.read()?;
-        Some(Self {
+        let axisTag = s.read().field("axisTag")?;
+        let axisNameID = s.read().field("axisNameID")?;
+        let axisOrdering = s.read().field("axisOrdering")?;
+        Ok(Self {
             axisTag,
             axisNameID,
             axisOrdering,
@@ -98,19 +98,24 @@ pub struct StatTable<'a> {
 
 impl<'a> StatTable<'a> {
     #[allow(non_snake_case)]
-    pub fn parse(data: &'a [u8]) -> Option<Self> {
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let header: StatHeader = s.read()?;
+        let header: StatHeader = s.read().field("header")?;
         s.set_offset(header.designAxesOffset as usize);
-        let designAxes = s.read_unsized_array(
-            header.designAxisCount as usize,
-            header.designAxisSize as usize,
-            Box::new(|x| AxisRecord::parse(x)),
-        )?;
+        let designAxes = s
+            .read_unsized_array(
+                header.designAxisCount as usize,
+                header.designAxisSize as usize,
+                AxisRecord::parse,
+            )
+            .field("designAxes")?;
         s.set_offset(header.offsetToAxisValueOffsets as usize);
-        let axisValueOffsets = s.read_array(header.axisValueCount as usize)?;
-        let axisValueTables = data.get(header.offsetToAxisValueOffsets as usize..)?;
-        Some(Self {
+        let axisValueOffsets = s
+            .read_array(header.axisValueCount as usize)
+            .field("axisValueOffsets")?;
+        let axisValueTables = get_subtable_data(data, header.offsetToAxisValueOffsets)
+            .field("offsetToAxisValueOffsets")?;
+        Ok(Self {
             header,
             designAxes,
             axisValueOffsets,
@@ -118,9 +123,16 @@ impl<'a> StatTable<'a> {
         })
     }
 
-    pub fn get_axis_value_table(&self, index: usize) -> Option<AxisValueTable> {
-        let offset = self.axisValueOffsets.get(index)?;
-        AxisValueTable::parse(self.axisValueTables.get(*offset as usize..)?)
+    pub fn get_axis_value_table(&self, index: usize) -> Result<AxisValueTable> {
+        let base = self.header.offsetToAxisValueOffsets as usize;
+        let offset = *self
+            .axisValueOffsets
+            .get(index)
+            .ok_or(Error::invalid(base))
+            .field("axisValueOffsets")?;
+        parse_subtable(self.axisValueTables, offset, AxisValueTable::parse)
+            .offset_by(base)
+            .field("axisValueOffsets")
     }
 
     pub fn get_axis_value_table_iter<'b>(&'b self) -> AxisValueTableIter<'b, 'a> {
@@ -137,16 +149,15 @@ pub enum AxisValueTable {
 }
 
 impl AxisValueTable {
-    pub fn parse(data: &[u8]) -> Option<Self> {
+    pub fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let format: u16 = s.read()?;
+        let format: u16 = s.read().field("format")?;
         match format {
-            1 => Some(Self::Format1(AxisValueFormat1::parse(data)?)),
-            2 => Some(Self::Format2(AxisValueFormat2::parse(data)?)),
-            3 => Some(Self::Format3(AxisValueFormat3::parse(data)?)),
-            _ => {
-                panic!("invalid format {}", format)
-            }
+            1 => Ok(Self::Format1(AxisValueFormat1::parse(data)?)),
+            2 => Ok(Self::Format2(AxisValueFormat2::parse(data)?)),
+            3 => Ok(Self::Format3(AxisValueFormat3::parse(data)?)),
+            4 => Ok(Self::Format4(AxisValueFormat4::parse(data)?)),
+            _ => Err(Error::unsupported(format.into(), 0).with_field("format")),
         }
     }
 
@@ -182,15 +193,17 @@ pub struct AxisValueFormat1 {
 impl FromData for AxisValueFormat1 {
     const SIZE: usize = 2 * 4 + 4;
     #[allow(non_snake_case)]
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let format = s.read()?;
-        assert_eq!(format, 1);
-        let axisIndex = s.read()?;
-        let flags = s.read()?;
-        let valueNameID = s.read()?;
-        let value = s.read()?;
-        Some(Self {
+        let format: uint16 = s.read().field("format")?;
+        if format != 1 {
+            return Err(Error::invalid(0).with_field("format"));
+        }
+        let axisIndex = s.read().field("axisIndex")?;
+        let flags = s.read().field("flags")?;
+        let valueNameID = s.read().field("valueNameID")?;
+        let value = s.read().field("value")?;
+        Ok(Self {
             format,
             axisIndex,
             flags,
@@ -215,17 +228,19 @@ pub struct AxisValueFormat2 {
 impl FromData for AxisValueFormat2 {
     const SIZE: usize = 4 * 2 + 4 * 3;
     #[allow(non_snake_case)]
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let format = s.read()?;
-        assert_eq!(format, 2);
-        let axisIndex = s.read()?;
-        let flags = s.read()?;
-        let valueNameID = s.read()?;
-        let nominalValue = s.read()?;
-        let rangeMinValue = s.read()?;
-        let rangeMaxValue = s.read()?;
-        Some(Self {
+        let format: uint16 = s.read().field("format")?;
+        if format != 2 {
+            return Err(Error::invalid(0).with_field("format"));
+        }
+        let axisIndex = s.read().field("axisIndex")?;
+        let flags = s.read().field("flags")?;
+        let valueNameID = s.read().field("valueNameID")?;
+        let nominalValue = s.read().field("nominalValue")?;
+        let rangeMinValue = s.read().field("rangeMinValue")?;
+        let rangeMaxValue = s.read().field("rangeMaxValue")?;
+        Ok(Self {
             format,
             axisIndex,
             flags,
@@ -251,16 +266,18 @@ pub struct AxisValueFormat3 {
 impl FromData for AxisValueFormat3 {
     const SIZE: usize = 2 * 4 + 4 * 2;
     #[allow(non_snake_case)]
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let format = s.read()?;
-        assert_eq!(format, 3);
-        let axisIndex = s.read()?;
-        let flags = s.read()?;
-        let valueNameID = s.read()?;
-        let value = s.read()?;
-        let linkedValue = s.read()?;
-        Some(Self {
+        let format: uint16 = s.read().field("format")?;
+        if format != 3 {
+            return Err(Error::invalid(0).with_field("format"));
+        }
+        let axisIndex = s.read().field("axisIndex")?;
+        let flags = s.read().field("flags")?;
+        let valueNameID = s.read().field("valueNameID")?;
+        let value = s.read().field("value")?;
+        let linkedValue = s.read().field("linkedValue")?;
+        Ok(Self {
             format,
             axisIndex,
             flags,
@@ -281,6 +298,28 @@ pub struct AxisValueFormat4 {
     pub axisValues: Vec<AxisValue>, // [axisCount]	Array of AxisValue records that provide the combination of axis values, one for each contributing axis.
 }
 
+impl AxisValueFormat4 {
+    #[allow(non_snake_case)]
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let format: uint16 = s.read().field("format")?;
+        if format != 4 {
+            return Err(Error::invalid(0).with_field("format"));
+        }
+        let axisCount = s.read().field("axisCount")?;
+        let flags = s.read().field("flags")?;
+        let valueNameID = s.read().field("valueNameID")?;
+        let axisValues = s.read_array(axisCount as usize).field("axisValues")?;
+        Ok(Self {
+            format,
+            axisCount,
+            flags,
+            valueNameID,
+            axisValues,
+        })
+    }
+}
+
 #[derive(Debug)]
 #[allow(non_snake_case)]
 pub struct AxisValue {
@@ -291,11 +330,11 @@ pub struct AxisValue {
 impl FromData for AxisValue {
     const SIZE: usize = 2 + 4;
     #[allow(non_snake_case)]
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let axisIndex = s.read()?;
-        let value = s.read()?;
-        Some(Self { axisIndex, value })
+        let axisIndex = s.read().field("axisIndex")?;
+        let value = s.read().field("value")?;
+        Ok(Self { axisIndex, value })
     }
 }
 
@@ -314,11 +353,11 @@ impl<'a, 'b> AxisValueTableIter<'a, 'b> {
 }
 
 impl<'a, 'b> Iterator for AxisValueTableIter<'a, 'b> {
-    type Item = AxisValueTable;
+    type Item = Result<AxisValueTable>;
     fn next(&mut self) -> Option<Self::Item> {
         if self.index < self.len() {
             self.index += 1;
-            self.stat_table.get_axis_value_table(self.index - 1)
+            Some(self.stat_table.get_axis_value_table(self.index - 1))
         } else {
             None
         }
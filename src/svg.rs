@@ -0,0 +1,294 @@
+// glyph の輪郭を SVG に書き出す．ブラウザで確認するためのもの．
+// path の座標はフォント単位のままで，y は上向き．文書では transform で上下を反転する．
+use core::fmt::Write;
+
+use crate::{
+    error::Result,
+    font::Font,
+    glyf::GlyphPoint,
+    outline::{draw_points, OutlinePen},
+};
+
+// path の d 属性を組み立てる pen．
+#[derive(Debug, Default, Clone)]
+pub struct SvgPathPen {
+    d: String,
+}
+
+impl SvgPathPen {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.d
+    }
+
+    pub fn into_string(self) -> String {
+        self.d
+    }
+
+    fn push(&mut self, command: char, values: &[f64]) {
+        self.d.push(command);
+        for (i, value) in values.iter().enumerate() {
+            if i > 0 {
+                self.d.push(' ');
+            }
+            write!(self.d, "{}", value).unwrap();
+        }
+    }
+}
+
+impl OutlinePen for SvgPathPen {
+    fn move_to(&mut self, x: f64, y: f64) {
+        self.push('M', &[x, y]);
+    }
+
+    fn line_to(&mut self, x: f64, y: f64) {
+        self.push('L', &[x, y]);
+    }
+
+    fn quad_to(&mut self, x1: f64, y1: f64, x: f64, y: f64) {
+        self.push('Q', &[x1, y1, x, y]);
+    }
+
+    fn curve_to(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, x: f64, y: f64) {
+        self.push('C', &[x1, y1, x2, y2, x, y]);
+    }
+
+    fn close(&mut self) {
+        self.d.push('Z');
+    }
+}
+
+// 輪郭以外に描くもの．
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SvgOptions {
+    pub points: bool, // on-curve 点 (塗りつぶした円)，off-curve 点 (白抜きの円) と制御線．
+    pub bbox: bool,   // 点から求めた bbox．
+    pub metrics: bool, // ベースライン，ascender, descender と advance width．
+}
+
+// glyph を描く範囲と線．座標はフォント単位．
+struct Frame {
+    x_min: f64,
+    x_max: f64,
+    ascender: f64,
+    descender: f64,
+}
+
+impl Frame {
+    // 上下を反転した座標での viewBox．
+    fn view_box(&self) -> String {
+        format!(
+            "{} {} {} {}",
+            self.x_min,
+            -self.ascender,
+            self.x_max - self.x_min,
+            self.ascender - self.descender
+        )
+    }
+}
+
+// 輪郭の path の d 属性を返す．
+pub fn glyph_path(font: &Font, glyph_id: u16) -> Result<String> {
+    let mut pen = SvgPathPen::new();
+    font.outline_glyph(glyph_id, &mut pen)?;
+    Ok(pen.into_string())
+}
+
+// 上下を反転した座標系に置く glyph の要素と，それを収める範囲を返す．
+fn glyph_elements(font: &Font, glyph_id: u16, options: &SvgOptions) -> Result<(String, Frame)> {
+    let resolved = font.resolve_glyph(glyph_id)?;
+    let mut pen = SvgPathPen::new();
+    draw_points(&resolved.points, &mut pen);
+    let advance_width = font.advance_width(glyph_id).map(f64::from).unwrap_or(0.0);
+    let (ascender, descender) = font.vertical_metrics();
+    let control_box = Some(resolved.bbox).filter(|bbox| !bbox.is_empty());
+
+    let (mut x_min, mut x_max) = (0.0f64, advance_width);
+    let (mut y_max, mut y_min) = (ascender, descender);
+    if let Some(bbox) = control_box {
+        (x_min, x_max) = (x_min.min(bbox.xmin), x_max.max(bbox.xmax));
+        (y_min, y_max) = (y_min.min(bbox.ymin), y_max.max(bbox.ymax));
+    }
+    // 線や点が端で切れないように余白を付ける．
+    // viewBox は整数にそろえる．
+    let margin = (f64::from(font.units_per_em().unwrap_or(1000)) / 20.0).round();
+    let frame = Frame {
+        x_min: (x_min - margin).floor(),
+        x_max: (x_max + margin).ceil(),
+        ascender: (y_max + margin).ceil(),
+        descender: (y_min - margin).floor(),
+    };
+
+    let mut body = String::new();
+    writeln!(body, r#"<g transform="scale(1 -1)">"#).unwrap();
+    if options.metrics {
+        let (left, right) = (frame.x_min, frame.x_max);
+        for (y, class) in [
+            (0.0, "baseline"),
+            (ascender, "ascender"),
+            (descender, "descender"),
+        ] {
+            writeln!(
+                body,
+                r##"<line class="{}" x1="{}" y1="{}" x2="{}" y2="{}" stroke="#09c" stroke-width="{}"/>"##,
+                class,
+                left,
+                y,
+                right,
+                y,
+                margin / 10.0
+            )
+            .unwrap();
+        }
+        for x in [0.0, advance_width] {
+            writeln!(
+                body,
+                r##"<line class="advance" x1="{}" y1="{}" x2="{}" y2="{}" stroke="#09c" stroke-width="{}"/>"##,
+                x,
+                frame.descender,
+                x,
+                frame.ascender,
+                margin / 10.0
+            )
+            .unwrap();
+        }
+    }
+    if let (true, Some(bbox)) = (options.bbox, control_box) {
+        writeln!(
+            body,
+            r##"<rect class="bbox" x="{}" y="{}" width="{}" height="{}" fill="none" stroke="#c60" stroke-width="{}"/>"##,
+            bbox.xmin,
+            bbox.ymin,
+            bbox.xmax - bbox.xmin,
+            bbox.ymax - bbox.ymin,
+            margin / 10.0
+        )
+        .unwrap();
+    }
+    writeln!(body, r##"<path d="{}" fill="#000"/>"##, pen.as_str()).unwrap();
+    if options.points {
+        write_points(&mut body, &resolved.points, margin);
+    }
+    writeln!(body, "</g>").unwrap();
+    Ok((body, frame))
+}
+
+// 輪郭ごとに点を結んだ制御線と，点の印を描く．
+fn write_points(body: &mut String, points: &[GlyphPoint], margin: f64) {
+    let radius = margin / 4.0;
+    for contour in points.split_inclusive(|point| point.is_last) {
+        let polygon: Vec<_> = contour
+            .iter()
+            .map(|point| format!("{},{}", point.x, point.y))
+            .collect();
+        writeln!(
+            body,
+            r##"<polygon class="handles" points="{}" fill="none" stroke="#999" stroke-width="{}"/>"##,
+            polygon.join(" "),
+            radius / 4.0
+        )
+        .unwrap();
+        for point in contour {
+            let (class, fill) = if point.flags.is_on_curve_point() {
+                ("on-curve", "#e00")
+            } else {
+                ("off-curve", "#fff")
+            };
+            writeln!(
+                body,
+                r##"<circle class="{}" cx="{}" cy="{}" r="{}" fill="{}" stroke="#e00" stroke-width="{}"/>"##,
+                class,
+                point.x,
+                point.y,
+                radius,
+                fill,
+                radius / 4.0
+            )
+            .unwrap();
+        }
+    }
+}
+
+// 1 つの glyph を描いた SVG 文書を返す．幅と高さはフォント単位の大きさをそのまま使う．
+pub fn glyph_to_svg(font: &Font, glyph_id: u16, options: &SvgOptions) -> Result<String> {
+    let (body, frame) = glyph_elements(font, glyph_id, options)?;
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{}" width="{}" height="{}">"#,
+        frame.view_box(),
+        frame.x_max - frame.x_min,
+        frame.ascender - frame.descender
+    )
+    .unwrap();
+    svg.push_str(&body);
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
+
+// glyph を columns 列に並べた一覧を返す．各マスは cell_size 四方で，下に glyph id を書く．
+// 輪郭を読めない glyph は，glyph id だけを書いた空のマスにする．
+pub fn glyph_sheet_svg(
+    font: &Font,
+    glyph_ids: impl IntoIterator<Item = u16>,
+    columns: usize,
+    cell_size: f64,
+    options: &SvgOptions,
+) -> String {
+    let columns = columns.max(1);
+    let label_height = cell_size / 5.0;
+    let mut cells = String::new();
+    let mut count = 0;
+    for glyph_id in glyph_ids {
+        let (x, y) = (
+            (count % columns) as f64 * cell_size,
+            (count / columns) as f64 * (cell_size + label_height),
+        );
+        count += 1;
+        writeln!(
+            cells,
+            r##"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="#ccc"/>"##,
+            x, y, cell_size, cell_size
+        )
+        .unwrap();
+        if let Ok((body, frame)) = glyph_elements(font, glyph_id, options) {
+            writeln!(
+                cells,
+                r#"<svg class="glyph" x="{}" y="{}" width="{}" height="{}" viewBox="{}">"#,
+                x,
+                y,
+                cell_size,
+                cell_size,
+                frame.view_box()
+            )
+            .unwrap();
+            cells.push_str(&body);
+            cells.push_str("</svg>\n");
+        }
+        writeln!(
+            cells,
+            r#"<text x="{}" y="{}" font-size="{}" text-anchor="middle">{}</text>"#,
+            x + cell_size / 2.0,
+            y + cell_size + label_height * 0.8,
+            label_height * 0.6,
+            glyph_id
+        )
+        .unwrap();
+    }
+    let rows = count.div_ceil(columns);
+    let width = columns.min(count.max(1)) as f64 * cell_size;
+    let height = rows as f64 * (cell_size + label_height);
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {} {}" width="{}" height="{}">"#,
+        width, height, width, height
+    )
+    .unwrap();
+    svg.push_str(&cells);
+    svg.push_str("</svg>\n");
+    svg
+}
@@ -1,18 +1,31 @@
+use core::fmt;
+use std::collections::HashSet;
+
 use crate::{
     cmap::CmapTable,
     data_types::{
-        Offset32, TableTag, Tag, CMAP, FVAR, GLYF, GSUB, HEAD, LOCA, MAXP, NAME, OS_2, STAT,
+        Offset32, TableTag, Tag, CMAP, DSIG, FVAR, GLYF, GSUB, HEAD, HHEA, HMTX, KERN, LOCA, MAXP,
+        MVAR, NAME, OS_2, POST, STAT, VHEA, VMTX, VORG,
     },
-    decoder::{FromData, Stream},
+    decoder::{parse_subtable, FromData, LazyArray, Stream},
+    error::{Context, Error, Result},
     fvar::FvarTable,
     glyf::GlyfTable,
     gsub::GsubTable,
     head::{HeadTable, LocaOffsetFormat},
+    hhea::HheaTable,
+    hmtx::HmtxTable,
+    kern::KernTable,
     loca::LocaTable,
     maxp::MaxpTable,
+    mvar::MvarTable,
     name::NameTable,
     os_2::OS2Table,
+    post::PostTable,
     stat::StatTable,
+    vhea::VheaTable,
+    vmtx::VmtxTable,
+    vorg::VorgTable,
 };
 
 #[allow(non_snake_case)]
@@ -22,22 +35,45 @@ pub struct TTCHeader {
     pub minorVersion: u16, // Minor version of the TTC Header, = 0.
     pub numFonts: u32, // Number of fonts in TTC
     pub tableDirectoryOffsets: Vec<Offset32>, // Array of offsets to the TableDirectory for each font from the beginning of the file
+    pub dsigTag: Option<Tag>, // Tag indicating that a DSIG table exists, 0x44534947 ('DSIG') (null if no signature). version 2.0 only.
+    pub dsigLength: Option<u32>, // The length (in bytes) of the DSIG table (null if no signature). version 2.0 only.
+    pub dsigOffset: Option<Offset32>, // The offset (in bytes) of the DSIG table from the beginning of the TTC file (null if no signature). version 2.0 only.
 }
 
 impl TTCHeader {
-    pub fn parse(data: &[u8]) -> Option<Self> {
+    pub fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let ttc_tag = s.read()?;
-        let major_version = s.read()?;
-        let minor_version = s.read()?;
-        let num_fonts = s.read()?;
-        let table_directory_offsets = s.read_array(num_fonts as usize)?;
-        Some(Self {
+        let ttc_tag: Tag = s.read().field("ttcTag")?;
+        if ttc_tag != TTCF {
+            return Err(Error::unsupported(ttc_tag.0, 0).with_field("ttcTag"));
+        }
+        let major_version: u16 = s.read().field("majorVersion")?;
+        if !(1..=2).contains(&major_version) {
+            return Err(Error::unsupported(major_version.into(), 4).with_field("majorVersion"));
+        }
+        let minor_version = s.read().field("minorVersion")?;
+        let num_fonts = s.read().field("numFonts")?;
+        let table_directory_offsets = s
+            .read_array(num_fonts as usize)
+            .field("tableDirectoryOffsets")?;
+        let (dsig_tag, dsig_length, dsig_offset) = if major_version == 2 {
+            (
+                Some(s.read().field("ulDsigTag")?),
+                Some(s.read().field("ulDsigLength")?),
+                Some(s.read().field("ulDsigOffset")?),
+            )
+        } else {
+            (None, None, None)
+        };
+        Ok(Self {
             ttcTag: ttc_tag,
             majorVersion: major_version,
             minorVersion: minor_version,
             numFonts: num_fonts,
             tableDirectoryOffsets: table_directory_offsets,
+            dsigTag: dsig_tag,
+            dsigLength: dsig_length,
+            dsigOffset: dsig_offset,
         })
     }
 }
@@ -48,35 +84,67 @@ pub struct Collection<'a> {
 }
 
 impl<'a> Collection<'a> {
-    pub fn new(data: &'a [u8]) -> Option<Self> {
+    pub fn new(data: &'a [u8]) -> Result<Self> {
         let header = TTCHeader::parse(data)?;
-        Some(Self { data, header })
+        Ok(Self { data, header })
     }
 
-    pub fn get(&self, index: usize) -> Option<Table<'a>> {
-        let offset = *self.header.tableDirectoryOffsets.get(index)? as usize;
-        let table_record_data = self.data.get(offset..self.data.len())?;
-        let table_directory = TableDirectory::parse(table_record_data)?;
-        Some(Table {
-            data: self.data,
-            table_directory,
-        })
+    pub fn len(&self) -> usize {
+        self.header.tableDirectoryOffsets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.header.tableDirectoryOffsets.is_empty()
+    }
+
+    // version 2.0 で DSIG table がある場合だけ，そのバイト列を返す．
+    pub fn get_dsig_data(&self) -> Result<Option<&'a [u8]>> {
+        let (Some(tag), Some(length), Some(offset)) = (
+            self.header.dsigTag,
+            self.header.dsigLength,
+            self.header.dsigOffset,
+        ) else {
+            return Ok(None);
+        };
+        if tag != DSIG || offset == 0 {
+            return Ok(None);
+        }
+        let offset = offset as usize;
+        let end = offset
+            .checked_add(length as usize)
+            .ok_or(Error::invalid(offset))?;
+        self.data
+            .get(offset..end)
+            .map(Some)
+            .ok_or(Error::eof(offset).with_tag(DSIG))
+    }
+
+    pub fn get(&self, index: usize) -> Result<Table<'a>> {
+        let offset = *self
+            .header
+            .tableDirectoryOffsets
+            .get(index)
+            .ok_or(Error::invalid(12))
+            .field("tableDirectoryOffsets")?;
+        Table::at_offset(self.data, offset as usize).field("tableDirectoryOffsets")
     }
 }
 
+pub(crate) const TTCF: Tag = Tag::from_be_bytes(*b"ttcf");
+
 pub fn is_ttc(data: &[u8]) -> bool {
-    const COLLECTION: Tag = Tag::from_be_bytes(*b"ttcf");
     let mut s = Stream::new(data);
     match s.read::<Tag>() {
-        Some(tag) => tag == COLLECTION,
-        None => false,
+        Ok(tag) => tag == TTCF,
+        Err(_) => false,
     }
 }
 
-fn is_sfnt_version(sfnt_version: &Tag) -> bool {
+pub(crate) fn is_sfnt_version(sfnt_version: &Tag) -> bool {
     const TRUETYPE: Tag = Tag(0x00010000);
     const CFF: Tag = Tag::from_be_bytes(*b"OTTO");
-    sfnt_version == &TRUETYPE || sfnt_version == &CFF
+    const APPLE_TRUETYPE: Tag = Tag::from_be_bytes(*b"true"); // 古い Mac の TrueType フォント．
+    sfnt_version == &TRUETYPE || sfnt_version == &CFF || sfnt_version == &APPLE_TRUETYPE
 }
 
 #[allow(non_snake_case)]
@@ -90,142 +158,506 @@ pub struct TableRecord {
 
 impl FromData for TableRecord {
     const SIZE: usize = 4 * 4;
-    fn parse(data: &[u8]) -> Option<Self> {
+    fn parse(data: &[u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        Some(Self {
-            tableTag: Tag(s.read()?),
-            checksum: s.read()?,
-            offset: s.read()?,
-            length: s.read()?,
+        Ok(Self {
+            tableTag: s.read().field("tableTag")?,
+            checksum: s.read().field("checksum")?,
+            offset: s.read().field("offset")?,
+            length: s.read().field("length")?,
         })
     }
 }
 
 #[allow(non_snake_case)]
-pub struct TableDirectory {
+#[derive(Clone)]
+pub struct TableDirectory<'a> {
     pub sfntVersion: Tag,
     pub numTables: u16,
     pub searchRange: u16,
     pub entrySelector: u16,
     pub rangeShift: u16,
-    pub tableRecords: Vec<TableRecord>,
+    pub tableRecords: LazyArray<'a, TableRecord>,
+    sorted: bool, // tableRecords がタグの昇順に並んでいるか．
 }
 
-impl TableDirectory {
-    pub fn parse(data: &[u8]) -> Option<Self> {
+impl<'a> TableDirectory<'a> {
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
         let mut s = Stream::new(data);
-        let sfnt_version: Tag = s.read()?;
+        let sfnt_version: Tag = s.read().field("sfntVersion")?;
         if !is_sfnt_version(&sfnt_version) {
-            return None;
+            return Err(Error::unsupported(sfnt_version.0, 0).with_field("sfntVersion"));
         }
-        let num_tables = s.read()?;
-        let search_range = s.read()?;
-        let entry_selector = s.read()?;
-        let range_shift = s.read()?;
-        let table_records = s.read_array(num_tables as usize)?;
-        Some(Self {
+        let num_tables = s.read().field("numTables")?;
+        let search_range = s.read().field("searchRange")?;
+        let entry_selector = s.read().field("entrySelector")?;
+        let range_shift = s.read().field("rangeShift")?;
+        let table_records: LazyArray<TableRecord> = s
+            .read_lazy_array(num_tables as usize)
+            .field("tableRecords")?;
+        let sorted = table_records
+            .iter()
+            .zip(table_records.iter().skip(1))
+            .all(|(a, b)| a.tableTag < b.tableTag);
+        Ok(Self {
             sfntVersion: sfnt_version,
             numTables: num_tables,
             searchRange: search_range,
             entrySelector: entry_selector,
             rangeShift: range_shift,
             tableRecords: table_records,
+            sorted,
         })
     }
+
+    pub fn is_sorted(&self) -> bool {
+        self.sorted
+    }
+
+    // 壊れたフォントでは並んでいないことがあるので，その場合は先頭から順に探す．
+    pub fn find(&self, tag: &Tag) -> Option<TableRecord> {
+        if self.sorted {
+            self.tableRecords
+                .binary_search_by(|record| record.tableTag.cmp(tag))
+                .map(|(_, record)| record)
+        } else {
+            self.tableRecords
+                .iter()
+                .find(|record| record.tableTag == *tag)
+        }
+    }
+
+    // numTables から決まる (searchRange, entrySelector, rangeShift)．
+    // numTables が大きいと u16 に収まらないので u32 で返す．numTables = 0 のときは 1 として計算する．
+    pub fn expected_search_params(&self) -> (u32, u32, u32) {
+        let num_tables = self.numTables as u32;
+        let entry_selector = num_tables.max(1).ilog2();
+        let search_range = (1 << entry_selector) * 16;
+        (
+            search_range,
+            entry_selector,
+            (num_tables * 16).saturating_sub(search_range),
+        )
+    }
 }
 
+// table directory の不備．
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirectoryIssue {
+    Unsorted {
+        tag: TableTag,
+        previous: TableTag,
+    }, // 直前のレコードよりタグが小さい．
+    Duplicate {
+        tag: TableTag,
+    },
+    OutOfRange {
+        tag: TableTag,
+        offset: u32,
+        length: u32,
+    }, // ファイルの範囲外．
+    Misaligned {
+        tag: TableTag,
+        offset: u32,
+    }, // 4 バイト境界に揃っていない．
+    OverlapsDirectory {
+        tag: TableTag,
+    }, // table directory と重なっている．
+    Overlap {
+        tag: TableTag,
+        other: TableTag,
+    },
+    SearchRange {
+        stored: u16,
+        expected: u32,
+    },
+    EntrySelector {
+        stored: u16,
+        expected: u32,
+    },
+    RangeShift {
+        stored: u16,
+        expected: u32,
+    },
+}
+
+impl fmt::Display for DirectoryIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsorted { tag, previous } => {
+                write!(f, "'{}' is not sorted (after '{}')", tag, previous)
+            }
+            Self::Duplicate { tag } => write!(f, "'{}' is duplicated", tag),
+            Self::OutOfRange {
+                tag,
+                offset,
+                length,
+            } => write!(f, "'{}' [{}; {}] is out of range", tag, offset, length),
+            Self::Misaligned { tag, offset } => {
+                write!(f, "'{}' offset {} is not 4-byte aligned", tag, offset)
+            }
+            Self::OverlapsDirectory { tag } => {
+                write!(f, "'{}' overlaps the table directory", tag)
+            }
+            Self::Overlap { tag, other } => write!(f, "'{}' overlaps '{}'", tag, other),
+            Self::SearchRange { stored, expected } => {
+                write!(f, "searchRange is {} (expected {})", stored, expected)
+            }
+            Self::EntrySelector { stored, expected } => {
+                write!(f, "entrySelector is {} (expected {})", stored, expected)
+            }
+            Self::RangeShift { stored, expected } => {
+                write!(f, "rangeShift is {} (expected {})", stored, expected)
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct Table<'a> {
     data: &'a [u8], // all data.
-    pub table_directory: TableDirectory,
+    offset: usize,  // table directory の位置．collection の場合は 0 ではない．
+    pub table_directory: TableDirectory<'a>,
 }
 
 impl<'a> Table<'a> {
-    pub fn new(data: &'a [u8]) -> Option<Self> {
+    pub fn new(data: &'a [u8]) -> Result<Self> {
         let table_directory = TableDirectory::parse(data)?;
-        Some(Self {
+        Ok(Self {
             data,
+            offset: 0,
             table_directory,
         })
     }
 
-    pub fn get_table_record(&self, tag: &Tag) -> Option<TableRecord> {
-        let index = self
-            .table_directory
-            .tableRecords
-            .binary_search_by(|record| record.tableTag.cmp(tag))
-            .ok()?;
-        let table_record = self.table_directory.tableRecords[index];
-        Some(table_record)
-    }
-
-    pub fn get_table_data(&self, tag: &Tag) -> Option<&'a [u8]> {
-        let table_record = self.get_table_record(tag)?;
-        let offset = table_record.offset as usize;
-        let end = {
-            let length = table_record.length as usize;
-            offset.checked_add(length)?
-        };
-        self.data.get(offset..end)
+    // table directory が offset にあるフォント．collection の face で使う．
+    pub fn at_offset(data: &'a [u8], offset: usize) -> Result<Self> {
+        let offset32 = Offset32::try_from(offset).map_err(|_| Error::invalid(0))?;
+        let table_directory = parse_subtable(data, offset32, TableDirectory::parse)?;
+        Ok(Self {
+            data,
+            offset,
+            table_directory,
+        })
     }
 
-    pub fn get_name_table(&self) -> NameTable<'a> {
-        // Required Tables なので， unwrap する．
-        self.get_table_data(&NAME)
-            .and_then(NameTable::parse)
-            .unwrap()
+    // table directory のファイル先頭からの位置．単体のフォントでは 0．
+    pub fn directory_offset(&self) -> usize {
+        self.offset
     }
 
-    pub fn get_fvar_table(&self) -> Option<FvarTable<'a>> {
-        let data = self.get_table_data(&FVAR)?;
-        let fvar = FvarTable::parse(data);
-        fvar
+    pub fn get_table_record(&self, tag: &Tag) -> Result<TableRecord> {
+        self.table_directory.find(tag).ok_or(Error::missing(*tag))
     }
 
-    pub fn get_stat_table(&self) -> Option<StatTable<'a>> {
-        let data = self.get_table_data(&STAT)?;
-        let stat = StatTable::parse(data);
-        stat
+    // table directory の不備をすべて返す．問題が無ければ空になる．
+    pub fn validate_directory(&self) -> Vec<DirectoryIssue> {
+        let directory = &self.table_directory;
+        let mut issues = Vec::new();
+
+        let (search_range, entry_selector, range_shift) = directory.expected_search_params();
+        if directory.searchRange as u32 != search_range {
+            issues.push(DirectoryIssue::SearchRange {
+                stored: directory.searchRange,
+                expected: search_range,
+            });
+        }
+        if directory.entrySelector as u32 != entry_selector {
+            issues.push(DirectoryIssue::EntrySelector {
+                stored: directory.entrySelector,
+                expected: entry_selector,
+            });
+        }
+        if directory.rangeShift as u32 != range_shift {
+            issues.push(DirectoryIssue::RangeShift {
+                stored: directory.rangeShift,
+                expected: range_shift,
+            });
+        }
+
+        let records = directory.tableRecords.to_vec();
+        for (previous, record) in records.iter().zip(records.iter().skip(1)) {
+            if record.tableTag < previous.tableTag {
+                issues.push(DirectoryIssue::Unsorted {
+                    tag: record.tableTag,
+                    previous: previous.tableTag,
+                });
+            }
+        }
+        // 並んでいない directory では重複が離れた位置にあることもある．
+        let mut seen = HashSet::new();
+        for record in &records {
+            if !seen.insert(record.tableTag) {
+                issues.push(DirectoryIssue::Duplicate {
+                    tag: record.tableTag,
+                });
+            }
+        }
+
+        let directory_end = self.offset + 12 + TableRecord::SIZE * records.len();
+        let mut ranges = Vec::new();
+        for record in &records {
+            let tag = record.tableTag;
+            let start = record.offset as usize;
+            let end = start.saturating_add(record.length as usize);
+            if end > self.data.len() {
+                issues.push(DirectoryIssue::OutOfRange {
+                    tag,
+                    offset: record.offset,
+                    length: record.length,
+                });
+            }
+            if record.offset % 4 != 0 {
+                issues.push(DirectoryIssue::Misaligned {
+                    tag,
+                    offset: record.offset,
+                });
+            }
+            if record.length > 0 {
+                if start < directory_end && self.offset < end {
+                    issues.push(DirectoryIssue::OverlapsDirectory { tag });
+                }
+                ranges.push((start, end, tag));
+            }
+        }
+
+        // 開始位置の順に並べ，それまでで最も後ろまで伸びているテーブルと比べる．
+        ranges.sort_unstable();
+        let mut furthest: Option<(usize, TableTag)> = None;
+        for (start, end, tag) in ranges {
+            if let Some((furthest_end, other)) = furthest {
+                if start < furthest_end {
+                    issues.push(DirectoryIssue::Overlap { tag, other });
+                }
+            }
+            if furthest.map_or(true, |(furthest_end, _)| furthest_end < end) {
+                furthest = Some((end, tag));
+            }
+        }
+        issues
+    }
+
+    pub fn get_table_data(&self, tag: &Tag) -> Result<&'a [u8]> {
+        self.get_record_data(&self.get_table_record(tag)?)
     }
 
-    pub fn get_cmap_table(&self) -> CmapTable<'a> {
-        // Required Tables なので， unwrap する．
-        self.get_table_data(&CMAP)
-            .and_then(CmapTable::parse)
-            .unwrap()
+    // レコード自身の offset と length の範囲．タグが重複していても，そのレコードの範囲を返す．
+    pub fn get_record_data(&self, record: &TableRecord) -> Result<&'a [u8]> {
+        let tag = record.tableTag;
+        let offset = record.offset as usize;
+        let end = offset
+            .checked_add(record.length as usize)
+            .ok_or(Error::invalid(offset).with_tag(tag))?;
+        self.data
+            .get(offset..end)
+            .ok_or(Error::eof(offset).with_tag(tag))
     }
 
-    pub fn get_os2_table(&self) -> OS2Table {
-        // Required Tables なので， unwrap する．
-        self.get_table_data(&OS_2)
-            .and_then(OS2Table::parse)
-            .unwrap()
+    // テーブルのパースで発生したエラーに，テーブルのタグとファイル先頭からのオフセットを付け足す．
+    pub(crate) fn parse_table<T>(
+        &self,
+        tag: &Tag,
+        parse: impl FnOnce(&'a [u8]) -> Result<T>,
+    ) -> Result<T> {
+        let data = self.get_table_data(tag)?;
+        let offset = self.get_table_record(tag)?.offset as usize;
+        parse(data).offset_by(offset).table(*tag)
     }
 
-    pub fn get_head_table(&self) -> HeadTable {
-        // Required Tables なので， unwrap する．
-        self.get_table_data(&HEAD)
-            .and_then(HeadTable::parse)
-            .unwrap()
+    pub fn get_name_table(&self) -> Result<NameTable<'a>> {
+        self.parse_table(&NAME, NameTable::parse)
     }
 
-    pub fn get_maxp_table(&self) -> MaxpTable {
-        // Required Tables なので， unwrap する．
-        self.get_table_data(&MAXP)
-            .and_then(MaxpTable::parse)
-            .unwrap()
+    pub fn get_fvar_table(&self) -> Result<FvarTable<'a>> {
+        self.parse_table(&FVAR, FvarTable::parse)
     }
 
-    pub fn get_loca_table(&self, format: LocaOffsetFormat, num_glyphs: u16) -> Option<LocaTable> {
-        self.get_table_data(&LOCA)
-            .and_then(|data| LocaTable::parse(data, format, num_glyphs))
+    pub fn get_stat_table(&self) -> Result<StatTable<'a>> {
+        self.parse_table(&STAT, StatTable::parse)
     }
 
-    pub fn get_glyf_table(&self) -> Option<GlyfTable<'a>> {
+    pub fn get_cmap_table(&self) -> Result<CmapTable<'a>> {
+        self.parse_table(&CMAP, CmapTable::parse)
+    }
+
+    pub fn get_os2_table(&self) -> Result<OS2Table> {
+        self.parse_table(&OS_2, OS2Table::parse)
+    }
+
+    pub fn get_head_table(&self) -> Result<HeadTable> {
+        self.parse_table(&HEAD, HeadTable::parse)
+    }
+
+    pub fn get_maxp_table(&self) -> Result<MaxpTable> {
+        self.parse_table(&MAXP, MaxpTable::parse)
+    }
+
+    pub fn get_hhea_table(&self) -> Result<HheaTable> {
+        self.parse_table(&HHEA, HheaTable::parse)
+    }
+
+    pub fn get_hmtx_table(
+        &self,
+        number_of_h_metrics: u16,
+        num_glyphs: u16,
+    ) -> Result<HmtxTable<'a>> {
+        self.parse_table(&HMTX, |data| {
+            HmtxTable::parse(data, number_of_h_metrics, num_glyphs)
+        })
+    }
+
+    pub fn get_post_table(&self) -> Result<PostTable<'a>> {
+        self.parse_table(&POST, PostTable::parse)
+    }
+
+    pub fn get_vhea_table(&self) -> Result<VheaTable> {
+        self.parse_table(&VHEA, VheaTable::parse)
+    }
+
+    pub fn get_vmtx_table(
+        &self,
+        num_of_long_ver_metrics: u16,
+        num_glyphs: u16,
+    ) -> Result<VmtxTable<'a>> {
+        self.parse_table(&VMTX, |data| {
+            VmtxTable::parse(data, num_of_long_ver_metrics, num_glyphs)
+        })
+    }
+
+    pub fn get_vorg_table(&self) -> Result<VorgTable<'a>> {
+        self.parse_table(&VORG, VorgTable::parse)
+    }
+
+    pub fn get_mvar_table(&self) -> Result<MvarTable<'a>> {
+        self.parse_table(&MVAR, MvarTable::parse)
+    }
+
+    pub fn get_loca_table(
+        &self,
+        format: LocaOffsetFormat,
+        num_glyphs: u16,
+    ) -> Result<LocaTable<'a>> {
+        self.parse_table(&LOCA, |data| LocaTable::parse(data, format, num_glyphs))
+    }
+
+    pub fn get_glyf_table(&self) -> Result<GlyfTable<'a>> {
         self.get_table_data(&GLYF).map(GlyfTable)
     }
 
-    pub fn get_gsub_table(&self) -> Option<GsubTable<'a>> {
-        self.get_table_data(&GSUB)
-            .and_then(|data| GsubTable::parse(data))
+    pub fn get_gsub_table(&self) -> Result<GsubTable<'a>> {
+        self.parse_table(&GSUB, GsubTable::parse)
+    }
+
+    pub fn get_kern_table(&self) -> Result<KernTable<'a>> {
+        self.parse_table(&KERN, KernTable::parse)
+    }
+
+    // 各テーブルの checksum と head.checksumAdjustment を検証する．
+    pub fn verify_checksums(&self) -> ChecksumReport {
+        let tables: Vec<_> = self
+            .table_directory
+            .tableRecords
+            .iter()
+            .map(|record| TableChecksum {
+                tag: record.tableTag,
+                stored: record.checksum,
+                computed: self.calc_table_checksum(&record),
+            })
+            .collect();
+        let adjustment = self.calc_checksum_adjustment();
+        ChecksumReport { tables, adjustment }
+    }
+
+    // head の checksum は checksumAdjustment を 0 として計算する．
+    fn calc_table_checksum(&self, record: &TableRecord) -> Result<u32> {
+        let data = self.get_record_data(record)?;
+        let checksum = calc_checksum(data);
+        if record.tableTag != HEAD {
+            return Ok(checksum);
+        }
+        let mut s = Stream::new(data);
+        s.set_offset(8);
+        let adjustment: u32 = s.read().field("checksumAdjustment").table(HEAD)?;
+        Ok(checksum.wrapping_sub(adjustment))
+    }
+
+    // ファイル全体を checksumAdjustment を 0 として足し合わせる．テーブルの間の padding や隙間も含める．
+    // collection の face では，仕様上 checksumAdjustment は無視するので Ok(None) を返す．
+    fn calc_checksum_adjustment(&self) -> Result<Option<ChecksumAdjustment>> {
+        if is_ttc(self.data) {
+            return Ok(None);
+        }
+        let stored = self.get_head_table()?.checksumAdjustment;
+        // head が 4 バイト境界に無い場合も，checksumAdjustment の各バイトが寄与した分を引く．
+        let at = self.get_table_record(&HEAD)?.offset as usize + 8;
+        let bytes = at
+            .checked_add(4)
+            .and_then(|end| self.data.get(at..end))
+            .ok_or(
+                Error::eof(at)
+                    .with_tag(HEAD)
+                    .with_field("checksumAdjustment"),
+            )?;
+        let contribution = bytes.iter().enumerate().fold(0u32, |sum, (i, &byte)| {
+            sum.wrapping_add(u32::from(byte) << (8 * (3 - (at + i) % 4)))
+        });
+        let sum = calc_checksum(self.data).wrapping_sub(contribution);
+        Ok(Some(ChecksumAdjustment {
+            stored,
+            computed: 0xB1B0AFBA_u32.wrapping_sub(sum),
+        }))
+    }
+}
+
+// 4 バイトごとに big endian の u32 として足し合わせる．末尾の半端なバイトは 0 で埋める．
+pub fn calc_checksum(data: &[u8]) -> u32 {
+    data.chunks(4).fold(0u32, |sum, chunk| {
+        let mut word = [0; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        sum.wrapping_add(u32::from_be_bytes(word))
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableChecksum {
+    pub tag: TableTag,
+    pub stored: u32,
+    pub computed: Result<u32>, // テーブルがファイルの範囲外にある場合はエラー．
+}
+
+impl TableChecksum {
+    pub fn is_valid(&self) -> bool {
+        self.computed == Ok(self.stored)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChecksumAdjustment {
+    pub stored: u32,
+    pub computed: u32,
+}
+
+impl ChecksumAdjustment {
+    pub fn is_valid(&self) -> bool {
+        self.stored == self.computed
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ChecksumReport {
+    pub tables: Vec<TableChecksum>,
+    // head が無い場合や，head が読めない場合はエラー．
+    // collection の face では，仕様上 checksumAdjustment を無視するので Ok(None) になる．
+    pub adjustment: Result<Option<ChecksumAdjustment>>,
+}
+
+impl ChecksumReport {
+    pub fn is_valid(&self) -> bool {
+        let adjustment = match self.adjustment {
+            Ok(Some(adjustment)) => adjustment.is_valid(),
+            Ok(None) => true,
+            Err(_) => false,
+        };
+        self.tables.iter().all(TableChecksum::is_valid) && adjustment
     }
 }
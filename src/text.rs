@@ -0,0 +1,116 @@
+// 文字列を 1 つのビットマップに描く．見本画像やゴールデンイメージによる回帰テストに使う．
+// 文字は cmap で glyph に変換し，hmtx の advance width と kern テーブルのカーニングで並べる．
+// GSUB/GPOS によるシェーピングや双方向テキストの処理は行わない．
+use crate::{
+    error::{Context, Error, Result},
+    font::Font,
+    raster::{rasterize_glyph, Bitmap, MAX_BITMAP_AREA, MAX_BITMAP_SIZE},
+};
+
+// 並べた glyph．x, y は原点の位置で，単位はフォント単位，y は上向き．
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionedGlyph {
+    pub glyph_id: u16,
+    pub x: f64,
+    pub y: f64,
+    pub advance: f64,
+}
+
+// 1 行目のベースラインを y = 0 とし，改行ごとに ascender - descender だけ下げる．
+// cmap に無い文字は .notdef (glyph 0) にする．
+pub fn layout_text(font: &Font, text: &str) -> Result<Vec<PositionedGlyph>> {
+    let (ascender, descender) = font.vertical_metrics();
+    let line_height = ascender - descender;
+    let mut glyphs = Vec::new();
+    for (line, chars) in text.lines().enumerate() {
+        let y = -(line as f64) * line_height;
+        let mut x = 0.0;
+        let mut previous = None;
+        for c in chars.chars() {
+            let glyph_id = font.glyph_index(c).unwrap_or(0);
+            if let Some(previous) = previous {
+                x += f64::from(font.kerning(previous, glyph_id));
+            }
+            // Font::advance_width は USE_MY_METRICS を持つ composite glyph にコンポーネントの値を返す．
+            let advance = f64::from(font.advance_width(glyph_id)?);
+            glyphs.push(PositionedGlyph {
+                glyph_id,
+                x,
+                y,
+                advance,
+            });
+            x += advance;
+            previous = Some(glyph_id);
+        }
+    }
+    Ok(glyphs)
+}
+
+// 文字列を pixel_size (1 em の画素数) で描いたビットマップを返す．
+// 範囲は各行の advance width と ascender から descender までを含み，はみ出した glyph があれば広げる．
+// left と top は 1 行目の原点から見たビットマップの左上の位置になる．
+// 大きさが glyph のビットマップと同じ上限 (幅と高さ，画素数) を超える場合はエラーにする．
+pub fn render_text(font: &Font, text: &str, pixel_size: f64) -> Result<Bitmap> {
+    let units_per_em = font.units_per_em()?;
+    let scale = pixel_size / f64::from(units_per_em.max(1));
+    let glyphs = layout_text(font, text)?;
+    let (ascender, descender) = font.vertical_metrics();
+    let lines = text.lines().count().max(1);
+    let last_baseline = -((lines - 1) as f64) * (ascender - descender);
+
+    let mut left = 0;
+    let mut right = 0;
+    let mut top = (ascender * scale).ceil() as i32;
+    let mut bottom = ((last_baseline + descender) * scale).floor() as i32;
+    // 原点を整数の画素と端数に分け，端数はサブピクセルのずれとして rasterizer に渡す．
+    let mut placed = Vec::with_capacity(glyphs.len());
+    for glyph in &glyphs {
+        let (x, y) = (glyph.x * scale, glyph.y * scale);
+        let (origin_x, origin_y) = (x.floor(), y.floor());
+        let bitmap = rasterize_glyph(
+            font,
+            glyph.glyph_id,
+            pixel_size,
+            (x - origin_x, y - origin_y),
+        )?;
+        let (bitmap_left, bitmap_top) =
+            (origin_x as i32 + bitmap.left, origin_y as i32 + bitmap.top);
+        right = right.max(((glyph.x + glyph.advance) * scale).ceil() as i32);
+        if bitmap.width > 0 && bitmap.height > 0 {
+            left = left.min(bitmap_left);
+            right = right.max(bitmap_left + bitmap.width as i32);
+            top = top.max(bitmap_top);
+            bottom = bottom.min(bitmap_top - bitmap.height as i32);
+        }
+        placed.push((bitmap, bitmap_left, bitmap_top));
+    }
+
+    let width = (i64::from(right) - i64::from(left)).max(0) as usize;
+    let height = (i64::from(top) - i64::from(bottom)).max(0) as usize;
+    let size = width
+        .checked_mul(height)
+        .filter(|&size| {
+            width <= MAX_BITMAP_SIZE && height <= MAX_BITMAP_SIZE && size <= MAX_BITMAP_AREA
+        })
+        .ok_or(Error::invalid(0))
+        .field("pixelSize")?;
+    let mut data = vec![0u8; size];
+    for (bitmap, bitmap_left, bitmap_top) in placed {
+        let x0 = (bitmap_left - left) as usize;
+        let y0 = (top - bitmap_top) as usize;
+        for (row, src) in bitmap.data.chunks(bitmap.width.max(1)).enumerate() {
+            let start = (y0 + row) * width + x0;
+            for (dst, &src) in data[start..start + src.len()].iter_mut().zip(src) {
+                // 重なった部分は source-over で合成する．
+                *dst = src + ((u16::from(*dst) * u16::from(255 - src) + 127) / 255) as u8;
+            }
+        }
+    }
+    Ok(Bitmap {
+        width,
+        height,
+        left,
+        top,
+        data,
+    })
+}
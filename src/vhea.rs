@@ -0,0 +1,65 @@
+use crate::{
+    data_types::{int16, uint16, Version16Dot16, FWORD, UFWORD},
+    decoder::Stream,
+    error::{Context, Result},
+};
+
+// version 1.0 と 1.1 はフィールドの並びが同じで，1.1 で先頭の 3 つが typographic な値として定義し直された．
+#[allow(non_snake_case)]
+#[derive(Debug)]
+pub struct VheaTable {
+    pub version: Version16Dot16, //Version number of the vertical header table; 0x00010000 for version 1.0, 0x00011000 for version 1.1.
+    pub vertTypoAscender: FWORD, //The vertical typographic ascender for this font. It is the distance in font design units from the vertical center baseline to the right of the design space. (version 1.0: ascent)
+    pub vertTypoDescender: FWORD, //The vertical typographic descender for this font. It is the distance in font design units from the vertical center baseline to the left of the design space. (version 1.0: descent)
+    pub vertTypoLineGap: FWORD, //The vertical typographic line gap for this font. (version 1.0: lineGap)
+    pub advanceHeightMax: UFWORD, //The maximum advance height measurement in font design units found in the font.
+    pub minTopSideBearing: FWORD, //The minimum top side bearing measured in font design units.
+    pub minBottomSideBearing: FWORD, //The minimum bottom side bearing measured in font design units.
+    pub yMaxExtent: FWORD, //This is defined as the value of the minTopSideBearing field added to the result of the value of the yMin field subtracted from the value of the yMax field.
+    pub caretSlopeRise: int16, //The value of the caretSlopeRise field divided by the value of the caretSlopeRun field determines the slope of the caret.
+    pub caretSlopeRun: int16, //See the caretSlopeRise field. Value = 1 for nonslanted vertical fonts.
+    pub caretOffset: int16, //The amount by which the highlight on a slanted glyph needs to be shifted away from the glyph in order to produce the best appearance. Set value equal to 0 for nonslanted fonts.
+    pub reserved: [int16; 4], //Set to 0.
+    pub metricDataFormat: int16, //Set to 0.
+    pub numOfLongVerMetrics: uint16, //Number of advance heights in the Vertical Metrics table.
+}
+
+impl VheaTable {
+    #[allow(non_snake_case)]
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let version = s.read().field("version")?;
+        let vertTypoAscender = s.read().field("vertTypoAscender")?;
+        let vertTypoDescender = s.read().field("vertTypoDescender")?;
+        let vertTypoLineGap = s.read().field("vertTypoLineGap")?;
+        let advanceHeightMax = s.read().field("advanceHeightMax")?;
+        let minTopSideBearing = s.read().field("minTopSideBearing")?;
+        let minBottomSideBearing = s.read().field("minBottomSideBearing")?;
+        let yMaxExtent = s.read().field("yMaxExtent")?;
+        let caretSlopeRise = s.read().field("caretSlopeRise")?;
+        let caretSlopeRun = s.read().field("caretSlopeRun")?;
+        let caretOffset = s.read().field("caretOffset")?;
+        let mut reserved = [0; 4];
+        for value in &mut reserved {
+            *value = s.read().field("reserved")?;
+        }
+        let metricDataFormat = s.read().field("metricDataFormat")?;
+        let numOfLongVerMetrics = s.read().field("numOfLongVerMetrics")?;
+        Ok(Self {
+            version,
+            vertTypoAscender,
+            vertTypoDescender,
+            vertTypoLineGap,
+            advanceHeightMax,
+            minTopSideBearing,
+            minBottomSideBearing,
+            yMaxExtent,
+            caretSlopeRise,
+            caretSlopeRun,
+            caretOffset,
+            reserved,
+            metricDataFormat,
+            numOfLongVerMetrics,
+        })
+    }
+}
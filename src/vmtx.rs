@@ -0,0 +1,95 @@
+use crate::{
+    data_types::{int16, uint16},
+    decoder::{FromData, LazyArray, Stream},
+    error::{Context, Result},
+};
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LongVerMetric {
+    pub advanceHeight: uint16, //The advance height of the glyph. Unsigned integer in font design units
+    pub topSideBearing: int16, //The top sidebearing of the glyph. Signed integer in font design units.
+}
+
+impl FromData for LongVerMetric {
+    const SIZE: usize = 4;
+    #[allow(non_snake_case)]
+    fn parse(data: &[u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let advanceHeight = s.read().field("advanceHeight")?;
+        let topSideBearing = s.read().field("topSideBearing")?;
+        Ok(Self {
+            advanceHeight,
+            topSideBearing,
+        })
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Copy)]
+pub struct VmtxTable<'a> {
+    pub vMetrics: LazyArray<'a, LongVerMetric>, //[numOfLongVerMetrics] Paired advance height and top side bearing values for each glyph. Records are indexed by glyph ID.
+    pub topSideBearings: LazyArray<'a, int16>, //[numGlyphs - numOfLongVerMetrics] Top side bearings for glyph IDs greater than or equal to numOfLongVerMetrics.
+    len: usize,
+}
+
+impl<'a> VmtxTable<'a> {
+    // numOfLongVerMetrics は vhea，num_glyphs は maxp から得る．
+    #[allow(non_snake_case)]
+    pub fn parse(data: &'a [u8], num_of_long_ver_metrics: u16, num_glyphs: u16) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let vMetrics = s
+            .read_lazy_array(num_of_long_ver_metrics as usize)
+            .field("vMetrics")?;
+        let count = num_glyphs.saturating_sub(num_of_long_ver_metrics) as usize;
+        // 末尾が足りないフォントも出回っているので，topSideBearings は実際にあるデータまでにする．
+        let available = (data.len() - s.get_offset()) / 2;
+        let topSideBearings = s
+            .read_lazy_array(count.min(available))
+            .field("topSideBearings")?;
+        Ok(Self {
+            vMetrics,
+            topSideBearings,
+            len: vMetrics.len() + count,
+        })
+    }
+
+    // numOfLongVerMetrics 以降の glyph は最後の advanceHeight を共有し，tsb は topSideBearings から取る．
+    // glyph id が範囲外の場合は None を返す．テーブルの末尾が足りずに tsb が無い glyph は tsb を 0 とする．
+    #[allow(non_snake_case)]
+    pub fn glyph_v_metrics(&self, glyph_id: u16) -> Option<LongVerMetric> {
+        let index = glyph_id as usize;
+        if let Some(metric) = self.vMetrics.get(index) {
+            return Some(metric);
+        }
+        if index >= self.len {
+            return None;
+        }
+        let advanceHeight = self.vMetrics.last()?.advanceHeight;
+        let topSideBearing = self
+            .topSideBearings
+            .get(index - self.vMetrics.len())
+            .unwrap_or(0);
+        Some(LongVerMetric {
+            advanceHeight,
+            topSideBearing,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// 縦書きに使う glyph のメトリクス．単位はフォント単位．
+// vert_origin_y は縦書きの原点の y 座標で，glyph はこの点から advance_height だけ下へ進む．
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlyphVMetrics {
+    pub advance_height: u16,
+    pub top_side_bearing: i16,
+    pub vert_origin_y: i16,
+}
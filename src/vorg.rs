@@ -0,0 +1,68 @@
+// 'VORG' テーブル．CFF の glyph の縦書きの原点の y 座標を持つ．
+use crate::{
+    data_types::{int16, uint16},
+    decoder::{FromData, LazyArray, Stream},
+    error::{Context, Error, Result},
+};
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VertOriginYMetrics {
+    pub glyphIndex: uint16, //Glyph index.
+    pub vertOriginY: int16, //Y coordinate, in the font’s design coordinate system, of the vertical origin of glyph with index glyphIndex.
+}
+
+impl FromData for VertOriginYMetrics {
+    const SIZE: usize = 4;
+    #[allow(non_snake_case)]
+    fn parse(data: &[u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let glyphIndex = s.read().field("glyphIndex")?;
+        let vertOriginY = s.read().field("vertOriginY")?;
+        Ok(Self {
+            glyphIndex,
+            vertOriginY,
+        })
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Copy)]
+pub struct VorgTable<'a> {
+    pub majorVersion: uint16,          //Major version (starting at 1). Set to 1.
+    pub minorVersion: uint16,          //Minor version (starting at 0). Set to 0.
+    pub defaultVertOriginY: int16, //The y coordinate of a glyph’s vertical origin, in the font’s design coordinate system, to be used if no entry is present for the glyph in the vertOriginYMetrics array.
+    pub numVertOriginYMetrics: uint16, //Number of elements in the vertOriginYMetrics array.
+    pub vertOriginYMetrics: LazyArray<'a, VertOriginYMetrics>, //[numVertOriginYMetrics] Array of VertOriginYMetrics records, sorted by glyph ID.
+}
+
+impl<'a> VorgTable<'a> {
+    #[allow(non_snake_case)]
+    pub fn parse(data: &'a [u8]) -> Result<Self> {
+        let mut s = Stream::new(data);
+        let majorVersion: uint16 = s.read().field("majorVersion")?;
+        if majorVersion != 1 {
+            return Err(Error::unsupported(majorVersion.into(), 0).with_field("majorVersion"));
+        }
+        let minorVersion = s.read().field("minorVersion")?;
+        let defaultVertOriginY = s.read().field("defaultVertOriginY")?;
+        let numVertOriginYMetrics: uint16 = s.read().field("numVertOriginYMetrics")?;
+        let vertOriginYMetrics = s
+            .read_lazy_array(numVertOriginYMetrics as usize)
+            .field("vertOriginYMetrics")?;
+        Ok(Self {
+            majorVersion,
+            minorVersion,
+            defaultVertOriginY,
+            numVertOriginYMetrics,
+            vertOriginYMetrics,
+        })
+    }
+
+    // 配列は glyph id の昇順なので二分探索する．無ければ defaultVertOriginY を返す．
+    pub fn get_vert_origin_y(&self, glyph_id: u16) -> i16 {
+        self.vertOriginYMetrics
+            .binary_search_by(|metric| metric.glyphIndex.cmp(&glyph_id))
+            .map_or(self.defaultVertOriginY, |(_, metric)| metric.vertOriginY)
+    }
+}
@@ -0,0 +1,156 @@
+mod common;
+
+use font_decoder::{
+    bbox::{verify_bboxes, BBoxPen},
+    glyf::BBox,
+    ErrorKind, Font, OutlinePen,
+};
+
+fn bbox(xmin: f64, ymin: f64, xmax: f64, ymax: f64) -> BBox {
+    BBox {
+        xmin,
+        ymin,
+        xmax,
+        ymax,
+    }
+}
+
+// glyph 0 は (250, 500) を off-curve 点に持つ山形，glyph 3 は glyph 1 を (100, 100) だけずらした composite glyph．
+// header の bbox はすべて正しい値にする．
+fn glyphs() -> Vec<Vec<u8>> {
+    let mut glyphs = common::glyphs();
+    glyphs[0] = common::curved_glyph(&[(0, 0, true), (250, 500, false), (500, 0, true)]);
+    // glyph 2 の xMax を実際の 500 に直す．
+    common::set_bbox(&mut glyphs[2], 2, [0, 0, 500, 500]);
+    glyphs[3] = common::composite_glyph(&[(common::ARGS_ARE_XY_VALUES, 1, 100, 100, &[])]);
+    common::set_bbox(&mut glyphs[3], 2, [100, 100, 600, 600]);
+    glyphs
+}
+
+#[test]
+fn pen() {
+    let mut pen = BBoxPen::new();
+    assert!(pen.control_box().is_empty());
+    assert!(pen.tight_box().is_empty());
+
+    // 2 次ベジエ曲線の頂点は t = 0.5 で，制御点の高さの半分になる．
+    pen.move_to(0.0, 0.0);
+    pen.quad_to(50.0, 100.0, 100.0, 0.0);
+    pen.close();
+    assert_eq!(pen.control_box(), bbox(0.0, 0.0, 100.0, 100.0));
+    assert_eq!(pen.tight_box(), bbox(0.0, 0.0, 100.0, 50.0));
+
+    // 3 次ベジエ曲線の頂点は制御点の高さの 3/4 になる．
+    let mut pen = BBoxPen::new();
+    pen.move_to(0.0, 0.0);
+    pen.curve_to(0.0, 100.0, 100.0, 100.0, 100.0, 0.0);
+    assert_eq!(pen.control_box(), bbox(0.0, 0.0, 100.0, 100.0));
+    assert_eq!(pen.tight_box(), bbox(0.0, 0.0, 100.0, 75.0));
+
+    // x が始点と終点の外へはみ出す S 字の曲線は，細かく分けた点の範囲と一致する．
+    let (p0, p1, p2, p3) = ((0.0, 0.0), (-100.0, 50.0), (200.0, 50.0), (100.0, 100.0));
+    let mut pen = BBoxPen::new();
+    pen.move_to(p0.0, p0.1);
+    pen.curve_to(p1.0, p1.1, p2.0, p2.1, p3.0, p3.1);
+    let mut sampled = BBox::default();
+    for i in 0..=10000 {
+        let t = f64::from(i) / 10000.0;
+        let u = 1.0 - t;
+        let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+        let x = a * p0.0 + b * p1.0 + c * p2.0 + d * p3.0;
+        let y = a * p0.1 + b * p1.1 + c * p2.1 + d * p3.1;
+        sampled = sampled.union(&bbox(x, y, x, y));
+    }
+    let tight = pen.tight_box();
+    for (computed, expected) in [
+        (tight.xmin, sampled.xmin),
+        (tight.ymin, sampled.ymin),
+        (tight.xmax, sampled.xmax),
+        (tight.ymax, sampled.ymax),
+    ] {
+        assert!((computed - expected).abs() < 1e-3, "{computed} {expected}");
+    }
+    assert!(tight.xmin < 0.0 && tight.xmax > 100.0);
+}
+
+#[test]
+fn glyph_bboxes() {
+    let data = common::font_with_glyphs(&glyphs(), 0).build();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(
+        font.control_box(0).unwrap(),
+        Some(bbox(0.0, 0.0, 500.0, 500.0))
+    );
+    assert_eq!(
+        font.tight_bbox(0).unwrap(),
+        Some(bbox(0.0, 0.0, 500.0, 250.0))
+    );
+    assert_eq!(
+        font.tight_bbox(2).unwrap(),
+        Some(bbox(0.0, 0.0, 500.0, 500.0))
+    );
+    // 原点から離れた composite glyph の bbox は原点を含まない．
+    let expected = bbox(100.0, 100.0, 600.0, 600.0);
+    assert_eq!(font.control_box(3).unwrap(), Some(expected));
+    assert_eq!(font.tight_bbox(3).unwrap(), Some(expected));
+    assert_eq!(font.outline(3).unwrap().1, expected);
+    assert_eq!(
+        font.control_box(4).unwrap_err().kind,
+        ErrorKind::InvalidValue
+    );
+
+    // 輪郭を持たない glyph は None になる．
+    let data = common::test_font();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(font.control_box(0).unwrap(), None);
+    assert_eq!(font.tight_bbox(0).unwrap(), None);
+    assert!(font.outline(0).unwrap().1.is_empty());
+    assert!(font.tight_bbox(3).is_err());
+}
+
+#[test]
+fn verify() {
+    let data = common::font_with_glyphs(&glyphs(), 0).build();
+    let font = Font::new(&data).unwrap();
+    let report = verify_bboxes(&font).unwrap();
+    assert_eq!(report.glyphs.len(), 4);
+    assert!(report.is_valid());
+
+    // head(1) の bbox (0, 0, 1000, 500) は glyph の範囲と合わない．
+    let data = common::font_with_glyphs(&glyphs(), 0)
+        .table(b"head", common::head(1))
+        .build();
+    let font = Font::new(&data).unwrap();
+    let report = verify_bboxes(&font).unwrap();
+    assert_eq!(report.mismatched_glyphs().count(), 0);
+    let head = report.head.unwrap();
+    assert_eq!(head.stored, bbox(0.0, 0.0, 1000.0, 500.0));
+    assert_eq!(head.computed, bbox(0.0, 0.0, 600.0, 600.0));
+    assert!(!report.is_valid());
+
+    // テスト用のフォントは glyph 2 の xMax が 1000 になっていて，glyph 3 は循環している．
+    let data = common::test_font();
+    let font = Font::new(&data).unwrap();
+    let report = verify_bboxes(&font).unwrap();
+    // glyph 0 は輪郭のデータを持たない．
+    assert_eq!(report.glyphs.len(), 3);
+    let mismatched: Vec<_> = report.mismatched_glyphs().collect();
+    assert_eq!(mismatched.len(), 2);
+    assert_eq!(mismatched[0].glyph_id, 2);
+    assert_eq!(mismatched[0].stored, Ok(bbox(0.0, 0.0, 1000.0, 500.0)));
+    assert_eq!(mismatched[0].computed, Ok(bbox(0.0, 0.0, 500.0, 500.0)));
+    assert_eq!(mismatched[1].glyph_id, 3);
+    assert!(mismatched[1].stored.is_ok());
+    assert!(mismatched[1].computed.is_err());
+    // 展開できない glyph があるので head の bbox は求められない．
+    assert!(report.head.is_err());
+
+    let data = common::FontBuilder::new()
+        .table(b"head", common::head(0))
+        .build();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(
+        verify_bboxes(&font).unwrap_err().kind,
+        ErrorKind::MissingTable
+    );
+}
@@ -0,0 +1,170 @@
+mod common;
+
+use common::{push_u16, push_u32};
+use font_decoder::{
+    cmap::{Charmap, ReverseCmap},
+    table::Table,
+    Font,
+};
+
+// (1, 0) 用の format 6．文字コード 0x61..=0x62 と 0x80 に glyph を割り当てる．
+fn mac_roman_subtable() -> Vec<u8> {
+    let mut sub = Vec::new();
+    for v in [6, 22, 0, 0x61, 0x20, 1, 2] {
+        push_u16(&mut sub, v); // format, length, language, firstCode, entryCount, glyphIdArray
+    }
+    sub.resize(10 + 2 * 0x20, 0);
+    sub[10 + 2 * 0x1F..].copy_from_slice(&3u16.to_be_bytes());
+    let length = sub.len() as u16;
+    sub[2..4].copy_from_slice(&length.to_be_bytes());
+    sub
+}
+
+fn charmap_encoding(data: &[u8]) -> Option<(u16, u16)> {
+    let cmap = Table::new(data).unwrap().get_cmap_table().unwrap();
+    Charmap::new(&cmap).map(|charmap| charmap.get_encoding())
+}
+
+#[test]
+fn prefers_full_unicode_subtable() {
+    let data = common::test_font();
+    let font = Font::new(&data).unwrap();
+    let charmap = font.charmap().unwrap();
+    assert_eq!(charmap.get_encoding(), (3, 10));
+    assert_eq!(charmap.map('A'), Some(1));
+    assert_eq!(charmap.map('\u{20000}'), Some(3));
+    assert_eq!(charmap.map('z'), None);
+    assert_eq!(font.glyph_index('\u{20000}'), Some(3));
+
+    let chars: String = charmap.chars().filter(char::is_ascii).collect();
+    assert_eq!(chars, "ABCab");
+    assert_eq!(charmap.iter().last(), Some(('\u{20000}', 3)));
+}
+
+#[test]
+fn map_with_selector() {
+    let data = common::test_font();
+    let font = Font::new(&data).unwrap();
+    let charmap = font.charmap().unwrap();
+    assert!(charmap.get_variation_subtable().is_some());
+    assert_eq!(charmap.map_with_selector('A', '\u{FE00}'), Some(1));
+    assert_eq!(charmap.map_with_selector('B', '\u{FE00}'), Some(3));
+    // 登録されていないシーケンスは基底文字の glyph になる．
+    assert_eq!(charmap.map_with_selector('C', '\u{FE00}'), Some(3));
+    assert_eq!(charmap.map_with_selector('z', '\u{FE00}'), None);
+}
+
+// (0, 6) 用の format 13．ASCII の印字可能な文字をすべて glyph 5 に対応させる．
+fn full_repertoire_subtable() -> Vec<u8> {
+    let mut sub = Vec::new();
+    push_u16(&mut sub, 13); // format
+    push_u16(&mut sub, 0); // reserved
+    for v in [16 + 12, 0, 1, 0x20, 0x7E, 5] {
+        push_u32(&mut sub, v); // length, language, numGroups, startCharCode, endCharCode, glyphID
+    }
+    sub
+}
+
+#[test]
+fn priority_and_fallback() {
+    let data = common::FontBuilder::new()
+        .table(
+            b"cmap",
+            common::cmap_table(&[
+                (1, 0, mac_roman_subtable()),
+                (3, 1, common::cmap_format12()),
+            ]),
+        )
+        .build();
+    assert_eq!(charmap_encoding(&data), Some((3, 1)));
+
+    // 壊れた subtable は飛ばす．
+    let broken = vec![0, 99];
+    let data = common::FontBuilder::new()
+        .table(
+            b"cmap",
+            common::cmap_table(&[(1, 0, mac_roman_subtable()), (3, 10, broken)]),
+        )
+        .build();
+    assert_eq!(charmap_encoding(&data), Some((1, 0)));
+
+    let data = common::FontBuilder::new()
+        .table(
+            b"cmap",
+            common::cmap_table(&[(0, 5, common::cmap_format14())]),
+        )
+        .build();
+    assert_eq!(charmap_encoding(&data), None);
+
+    // format 13 の (0, 6) は (3, 10) と (0, 4) の次に優先する．
+    let data = common::FontBuilder::new()
+        .table(
+            b"cmap",
+            common::cmap_table(&[
+                (0, 6, full_repertoire_subtable()),
+                (1, 0, mac_roman_subtable()),
+                (3, 1, common::cmap_format12()),
+            ]),
+        )
+        .build();
+    assert_eq!(charmap_encoding(&data), Some((0, 6)));
+    let cmap = Table::new(&data).unwrap().get_cmap_table().unwrap();
+    let charmap = Charmap::new(&cmap).unwrap();
+    assert_eq!(charmap.map('x'), Some(5));
+    assert_eq!(charmap.map('\u{20000}'), None);
+
+    // (0, 4) と (3, 10) は (0, 6) より優先する．
+    for (platform_id, encoding_id) in [(0, 4), (3, 10)] {
+        let data = common::FontBuilder::new()
+            .table(
+                b"cmap",
+                common::cmap_table(&[
+                    (0, 6, full_repertoire_subtable()),
+                    (platform_id, encoding_id, common::cmap_format12()),
+                ]),
+            )
+            .build();
+        assert_eq!(charmap_encoding(&data), Some((platform_id, encoding_id)));
+    }
+}
+
+#[test]
+fn mac_roman() {
+    let data = common::FontBuilder::new()
+        .table(b"cmap", common::cmap_table(&[(1, 0, mac_roman_subtable())]))
+        .build();
+    let cmap = Table::new(&data).unwrap().get_cmap_table().unwrap();
+    let charmap = Charmap::new(&cmap).unwrap();
+    assert_eq!(charmap.map('a'), Some(1));
+    assert_eq!(charmap.map('b'), Some(2));
+    // Mac Roman の 0x80 は 'Ä'．
+    assert_eq!(charmap.map('Ä'), Some(3));
+    assert_eq!(charmap.map('\u{80}'), None);
+    assert_eq!(
+        charmap.iter().collect::<Vec<_>>(),
+        vec![('a', 1), ('b', 2), ('Ä', 3)]
+    );
+}
+
+#[test]
+fn reverse_cmap() {
+    let data = common::test_font();
+    let cmap = Table::new(&data).unwrap().get_cmap_table().unwrap();
+    let reverse = ReverseCmap::new(&cmap).unwrap();
+    assert_eq!(reverse.get_chars(1).collect::<String>(), "Aa");
+    assert_eq!(reverse.get_char(2), Some('B'));
+    assert_eq!(reverse.get_chars(3).collect::<String>(), "C\u{20000}");
+    assert_eq!(reverse.get_char(0), None);
+    assert_eq!(reverse.get_char(4), None);
+
+    assert_eq!(
+        reverse.get_variants(1).collect::<Vec<_>>(),
+        vec![('A', '\u{FE00}')]
+    );
+    assert_eq!(
+        reverse.get_variants(3).collect::<Vec<_>>(),
+        vec![('B', '\u{FE00}')]
+    );
+    assert_eq!(reverse.get_variants(2).count(), 0);
+    assert_eq!(reverse.iter().count(), 6);
+}
@@ -0,0 +1,136 @@
+mod common;
+
+use font_decoder::{
+    data_types::{HEAD, HHEA, HMTX, NAME},
+    table::{calc_checksum, Collection, Table},
+    ErrorKind,
+};
+
+// head.checksumAdjustment を正しい値に書き換える．
+fn fix_adjustment(data: &mut [u8]) {
+    let table = Table::new(data).unwrap();
+    let computed = table
+        .verify_checksums()
+        .adjustment
+        .unwrap()
+        .unwrap()
+        .computed;
+    let head = table.get_table_record(&HEAD).unwrap();
+    let at = head.offset as usize + 8;
+    data[at..at + 4].copy_from_slice(&computed.to_be_bytes());
+}
+
+#[test]
+fn calc_checksum_pads_tail() {
+    assert_eq!(calc_checksum(&[]), 0);
+    assert_eq!(calc_checksum(&[0, 0, 0, 1, 0x80]), 0x80000001);
+    assert_eq!(calc_checksum(&[0xFF; 8]), 0xFFFFFFFE);
+}
+
+#[test]
+fn valid_font() {
+    let mut data = common::test_font();
+    fix_adjustment(&mut data);
+    let report = Table::new(&data).unwrap().verify_checksums();
+    assert!(report.tables.iter().all(|table| table.is_valid()));
+    assert!(report.is_valid());
+    // ファイル全体の checksum は 0xB1B0AFBA になる．
+    assert_eq!(calc_checksum(&data), 0xB1B0AFBA);
+}
+
+#[test]
+fn corrupted_table_is_reported() {
+    let mut data = common::test_font();
+    fix_adjustment(&mut data);
+    let table = Table::new(&data).unwrap();
+    let name = table.get_table_record(&NAME).unwrap();
+    data[name.offset as usize] ^= 1;
+
+    let report = Table::new(&data).unwrap().verify_checksums();
+    let invalid: Vec<_> = report
+        .tables
+        .iter()
+        .filter(|table| !table.is_valid())
+        .map(|table| table.tag)
+        .collect();
+    assert_eq!(invalid, vec![NAME]);
+    assert!(!report.adjustment.unwrap().unwrap().is_valid());
+    assert!(!report.is_valid());
+}
+
+#[test]
+fn padding_is_part_of_adjustment() {
+    let mut data = common::test_font();
+    fix_adjustment(&mut data);
+    assert!(Table::new(&data).unwrap().verify_checksums().is_valid());
+    // 末尾のテーブルの後ろの padding はどのテーブルにも含まれないが，ファイルの checksum は変わる．
+    let table = Table::new(&data).unwrap();
+    let last = table
+        .table_directory
+        .tableRecords
+        .iter()
+        .max_by_key(|record| record.offset + record.length)
+        .unwrap();
+    let padding = (last.offset + last.length) as usize;
+    assert!(padding < data.len());
+    data[padding] = 0xFF;
+    let report = Table::new(&data).unwrap().verify_checksums();
+    assert!(report.tables.iter().all(|table| table.is_valid()));
+    assert!(!report.adjustment.unwrap().unwrap().is_valid());
+}
+
+#[test]
+fn duplicate_tags_use_their_own_range() {
+    let mut data = common::test_font();
+    let table = Table::new(&data).unwrap();
+    let (index, _) = table
+        .table_directory
+        .tableRecords
+        .iter()
+        .enumerate()
+        .find(|(_, record)| record.tableTag == HMTX)
+        .unwrap();
+    // hmtx のレコードのタグを hhea にして，hhea を 2 つにする．
+    let at = 12 + 16 * index;
+    data[at..at + 4].copy_from_slice(b"hhea");
+    let report = Table::new(&data).unwrap().verify_checksums();
+    assert_eq!(
+        report
+            .tables
+            .iter()
+            .filter(|table| table.tag == HHEA)
+            .count(),
+        2
+    );
+    assert!(report.tables.iter().all(|table| table.is_valid()));
+}
+
+#[test]
+fn missing_head_and_truncated_table() {
+    let data = common::FontBuilder::new()
+        .table(b"maxp", common::maxp())
+        .build();
+    let report = Table::new(&data).unwrap().verify_checksums();
+    assert!(report.tables[0].is_valid());
+    assert_eq!(report.adjustment.unwrap_err().kind, ErrorKind::MissingTable);
+
+    let data = common::test_font();
+    let report = Table::new(&data[..data.len() - 8])
+        .unwrap()
+        .verify_checksums();
+    assert!(report.tables.iter().any(|table| table.computed.is_err()));
+    assert!(!report.is_valid());
+}
+
+#[test]
+fn collection_faces() {
+    let data = common::test_collection();
+    let collection = Collection::new(&data).unwrap();
+    for i in 0..collection.len() {
+        let report = collection.get(i).unwrap().verify_checksums();
+        assert!(report.tables.iter().all(|table| table.is_valid()));
+        // collection では checksumAdjustment を検証しない．
+        assert_eq!(report.adjustment, Ok(None));
+        assert!(report.is_valid());
+    }
+}
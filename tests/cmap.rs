@@ -0,0 +1,245 @@
+mod common;
+
+use common::{push_i16, push_u16, push_u32};
+use font_decoder::{
+    cmap::{CmapSubtable, VariantGlyph},
+    table::Table,
+};
+
+fn subtables(data: &[u8]) -> Vec<CmapSubtable<'_>> {
+    let cmap = Table::new(data).unwrap().get_cmap_table().unwrap();
+    cmap.header
+        .encodingRecords
+        .iter()
+        .map(|record| cmap.get_subtable(record).unwrap())
+        .collect()
+}
+
+// 16 ビットの length を埋める．
+fn fill_length(mut sub: Vec<u8>) -> Vec<u8> {
+    let length = sub.len() as u16;
+    sub[2..4].copy_from_slice(&length.to_be_bytes());
+    sub
+}
+
+#[test]
+fn format4_glyph_id_array_adds_id_delta() {
+    let mut sub = Vec::new();
+    for v in [4, 0, 0, 4, 2, 0, 0] {
+        push_u16(&mut sub, v); // format, length, language, segCountX2, searchRange, entrySelector, rangeShift
+    }
+    for v in [0x31, 0xFFFF] {
+        push_u16(&mut sub, v); // endCode
+    }
+    push_u16(&mut sub, 0); // reservedPad
+    for v in [0x30, 0xFFFF] {
+        push_u16(&mut sub, v); // startCode
+    }
+    for v in [5, 1] {
+        push_i16(&mut sub, v); // idDelta
+    }
+    for v in [4, 0] {
+        push_u16(&mut sub, v); // idRangeOffset
+    }
+    for v in [10, 0] {
+        push_u16(&mut sub, v); // glyphIdArray
+    }
+    let sub = fill_length(sub);
+    let subtable = CmapSubtable::parse(&sub).unwrap();
+    assert_eq!(subtable.get_glyph_id('0'), Some(15));
+    // glyphIdArray の 0 には idDelta を足さない．
+    assert_eq!(subtable.get_glyph_id('1'), Some(0));
+}
+
+#[test]
+fn format4_ignores_length_and_header_errors() {
+    let mut sub = Vec::new();
+    // length は glyphIdArray の手前までしか無く，最後の endCode は 0xFFFF でない．
+    for v in [4, 16, 0, 2, 2, 0, 0] {
+        push_u16(&mut sub, v); // format, length, language, segCountX2, searchRange, entrySelector, rangeShift
+    }
+    push_u16(&mut sub, 0x31); // endCode
+    push_u16(&mut sub, 1); // reservedPad
+    push_u16(&mut sub, 0x30); // startCode
+    push_i16(&mut sub, 0); // idDelta
+    push_u16(&mut sub, 2); // idRangeOffset
+    for v in [10, 11] {
+        push_u16(&mut sub, v); // glyphIdArray
+    }
+    let subtable = CmapSubtable::parse(&sub).unwrap();
+    assert_eq!(subtable.get_glyph_id('0'), Some(10));
+    assert_eq!(subtable.get_glyph_id('1'), Some(11));
+    assert_eq!(subtable.get_glyph_id('2'), Some(0));
+}
+
+#[test]
+fn format0_and_6() {
+    let mut sub = Vec::new();
+    for v in [0, 0, 0] {
+        push_u16(&mut sub, v); // format, length, language
+    }
+    sub.extend((0..=255).map(|code: u8| code / 2));
+    let sub = fill_length(sub);
+    let subtable = CmapSubtable::parse(&sub).unwrap();
+    assert_eq!(subtable.get_format(), 0);
+    assert_eq!(subtable.get_glyph_id('A'), Some(0x41 / 2));
+    assert_eq!(subtable.get_glyph_id('\u{100}'), None);
+    // glyph 0 (.notdef) は返さない．
+    assert_eq!(subtable.iter().count(), 254);
+
+    let mut sub = Vec::new();
+    for v in [6, 0, 0, 0x61, 2, 7, 8] {
+        push_u16(&mut sub, v); // format, length, language, firstCode, entryCount, glyphIdArray
+    }
+    let sub = fill_length(sub);
+    let subtable = CmapSubtable::parse(&sub).unwrap();
+    assert_eq!(subtable.get_glyph_id('b'), Some(8));
+    assert_eq!(subtable.get_glyph_id('c'), Some(0));
+    assert_eq!(
+        subtable.get_code_point_glyph_id_map(),
+        vec![('a', 7), ('b', 8)]
+    );
+}
+
+#[test]
+fn format2() {
+    let sub = common::cmap_format2();
+    let subtable = CmapSubtable::parse(&sub).unwrap();
+    assert_eq!(subtable.get_glyph_id_by_code(0x20), Some(1));
+    assert_eq!(subtable.get_glyph_id_by_code(0x21), Some(2));
+    assert_eq!(subtable.get_glyph_id_by_code(0x22), Some(0));
+    assert_eq!(subtable.get_glyph_id_by_code(0x81), Some(0));
+    assert_eq!(subtable.get_glyph_id_by_code(0x8140), Some(20));
+    assert_eq!(subtable.get_glyph_id_by_code(0x8141), Some(21));
+    assert_eq!(subtable.get_glyph_id_by_code(0x8142), Some(0));
+    assert_eq!(subtable.get_glyph_id_by_code(0x8240), Some(0));
+    assert_eq!(subtable.get_code_point_glyph_id_map().len(), 4);
+}
+
+#[test]
+fn format8_10_13() {
+    let mut sub = Vec::new();
+    push_u16(&mut sub, 8); // format
+    push_u16(&mut sub, 0); // reserved
+    push_u32(&mut sub, 12 + 8192 + 4 + 12 * 3); // length
+    push_u32(&mut sub, 0); // language
+                           // 0x0001 だけを 32 ビットの文字コードの上位 16 ビットにする．
+    let mut is32 = [0; 8192];
+    is32[0] = 0x40;
+    sub.extend_from_slice(&is32);
+    push_u32(&mut sub, 3); // numGroups
+    for v in [0, 2, 10, 0x10000, 0x10002, 5, 0x20000, 0x20001, 20] {
+        push_u32(&mut sub, v); // startCharCode, endCharCode, startGlyphID
+    }
+    let subtable = CmapSubtable::parse(&sub).unwrap();
+    assert_eq!(subtable.get_glyph_id('\u{10001}'), Some(6));
+    assert_eq!(subtable.get_glyph_id('\u{10003}'), Some(0));
+    assert_eq!(subtable.get_glyph_id('\u{2}'), Some(12));
+    // 0x0001 は 32 ビットの文字コードの前半なので，16 ビットの文字コードとしては引かない．
+    assert_eq!(subtable.get_glyph_id('\u{1}'), Some(0));
+    // 上位 16 ビットの 0x0002 は is32 が立っていない．
+    assert_eq!(subtable.get_glyph_id('\u{20000}'), Some(0));
+    let map = subtable.get_code_point_glyph_id_map();
+    assert!(map.contains(&('\u{2}', 12)));
+    assert!(map.contains(&('\u{10001}', 6)));
+    assert!(!map.iter().any(|&(c, _)| c == '\u{1}' || c == '\u{20000}'));
+
+    let mut sub = Vec::new();
+    push_u16(&mut sub, 10); // format
+    push_u16(&mut sub, 0); // reserved
+    push_u32(&mut sub, 24); // length
+    for v in [0, 0x1F600, 2] {
+        push_u32(&mut sub, v); // language, startCharCode, numChars
+    }
+    for v in [3, 4] {
+        push_u16(&mut sub, v); // glyphIdArray
+    }
+    let subtable = CmapSubtable::parse(&sub).unwrap();
+    assert_eq!(subtable.get_glyph_id('\u{1F601}'), Some(4));
+    assert_eq!(subtable.get_glyph_id('\u{1F602}'), Some(0));
+
+    let mut sub = Vec::new();
+    push_u16(&mut sub, 13); // format
+    push_u16(&mut sub, 0); // reserved
+    push_u32(&mut sub, 16 + 24); // length
+    for v in [0, 2, 0x20, 0x30, 1, 0x28, 0x40, 2] {
+        push_u32(&mut sub, v); // language, numGroups, groups
+    }
+    let subtable = CmapSubtable::parse(&sub).unwrap();
+    assert_eq!(subtable.get_glyph_id('\u{25}'), Some(1));
+    assert_eq!(subtable.get_glyph_id('\u{35}'), Some(2));
+    assert_eq!(subtable.get_glyph_id('\u{10}'), Some(0));
+    // 前の group と重なる部分は map では無視する．
+    let map = subtable.get_code_point_glyph_id_map();
+    assert_eq!(map.len(), 0x41 - 0x20);
+    assert_eq!(map[0x10], ('\u{30}', 1));
+    assert_eq!(map[0x11], ('\u{31}', 2));
+}
+
+#[test]
+fn format12_beyond_bmp() {
+    let data = common::test_font();
+    let subtables = subtables(&data);
+    let subtable = &subtables[2];
+    assert_eq!(subtable.get_format(), 12);
+    assert_eq!(subtable.get_glyph_id('B'), Some(2));
+    assert_eq!(subtable.get_glyph_id('\u{20000}'), Some(3));
+    assert_eq!(subtable.get_glyph_id('\u{20001}'), Some(0));
+    assert_eq!(subtable.get_code_point_glyph_id_map().len(), 6);
+}
+
+#[test]
+fn format14_variation_sequences() {
+    let data = common::test_font();
+    let subtables = subtables(&data);
+    let subtable = &subtables[0];
+    assert_eq!(subtable.get_format(), 14);
+    assert_eq!(subtable.get_glyph_id('A'), None);
+    assert_eq!(
+        subtable.get_variant_glyph_id('A', '\u{FE00}'),
+        Some(VariantGlyph::Default)
+    );
+    assert_eq!(
+        subtable.get_variant_glyph_id('B', '\u{FE00}'),
+        Some(VariantGlyph::Glyph(3))
+    );
+    assert_eq!(subtable.get_variant_glyph_id('C', '\u{FE00}'), None);
+    assert_eq!(subtable.get_variant_glyph_id('A', '\u{FE01}'), None);
+    assert_eq!(subtables[1].get_variant_glyph_id('A', '\u{FE00}'), None);
+}
+
+#[test]
+fn unsupported_format() {
+    let mut sub = Vec::new();
+    push_u16(&mut sub, 7);
+    assert!(CmapSubtable::parse(&sub).is_err());
+}
+
+#[test]
+fn iter_and_ranges() {
+    let data = common::test_font();
+    let subtables = subtables(&data);
+    let expected = vec![(0x41, 1), (0x42, 2), (0x43, 3), (0x61, 1), (0x62, 2)];
+    // format 4 と format 12 で同じ列になる．
+    assert_eq!(subtables[1].iter().collect::<Vec<_>>(), expected);
+    assert_eq!(subtables[2].iter().take(5).collect::<Vec<_>>(), expected);
+    assert_eq!(
+        subtables[2].ranges().collect::<Vec<_>>(),
+        vec![(0x41..=0x43, 1), (0x61..=0x62, 1), (0x20000..=0x20000, 3)]
+    );
+    assert_eq!(subtables[0].iter().count(), 0);
+}
+
+#[test]
+fn ranges_split_on_glyph_gaps() {
+    let mut sub = Vec::new();
+    for v in [6, 0, 0, 0x30, 5, 1, 2, 4, 0, 5] {
+        push_u16(&mut sub, v); // format, length, language, firstCode, entryCount, glyphIdArray
+    }
+    let sub = fill_length(sub);
+    let subtable = CmapSubtable::parse(&sub).unwrap();
+    assert_eq!(
+        subtable.ranges().collect::<Vec<_>>(),
+        vec![(0x30..=0x31, 1), (0x32..=0x32, 4), (0x34..=0x34, 5)]
+    );
+}
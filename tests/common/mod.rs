@@ -0,0 +1,812 @@
+// テスト用に最小限の TrueType フォントをメモリ上で組み立てる．
+#![allow(dead_code)]
+
+pub struct FontBuilder {
+    sfnt_version: u32,
+    tables: Vec<([u8; 4], Vec<u8>)>,
+}
+
+impl FontBuilder {
+    pub fn new() -> Self {
+        Self {
+            sfnt_version: 0x00010000,
+            tables: Vec::new(),
+        }
+    }
+
+    pub fn sfnt_version(mut self, sfnt_version: u32) -> Self {
+        self.sfnt_version = sfnt_version;
+        self
+    }
+
+    pub fn table(mut self, tag: &[u8; 4], data: Vec<u8>) -> Self {
+        self.tables.retain(|(t, _)| t != tag);
+        self.tables.push((*tag, data));
+        self
+    }
+
+    // table directory はタグ順に並べ，各テーブルは 4 バイト境界に揃える．
+    pub fn build(mut self) -> Vec<u8> {
+        self.tables.sort_by_key(|(tag, _)| *tag);
+        let num_tables = self.tables.len() as u16;
+        let entry_selector = 15 - num_tables.max(1).leading_zeros() as u16;
+        let search_range = (1 << entry_selector) * 16;
+        let range_shift = (num_tables * 16).saturating_sub(search_range);
+
+        let mut font = Vec::new();
+        push_u32(&mut font, self.sfnt_version);
+        push_u16(&mut font, num_tables);
+        push_u16(&mut font, search_range);
+        push_u16(&mut font, entry_selector);
+        push_u16(&mut font, range_shift);
+
+        let mut offset = 12 + 16 * self.tables.len();
+        for (tag, data) in &self.tables {
+            font.extend_from_slice(tag);
+            push_u32(&mut font, checksum(data));
+            push_u32(&mut font, offset as u32);
+            push_u32(&mut font, data.len() as u32);
+            offset += padded_len(data.len());
+        }
+        for (_, data) in &self.tables {
+            font.extend_from_slice(data);
+            font.resize(padded_len(font.len()), 0);
+        }
+        font
+    }
+}
+
+impl Default for FontBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn push_u16(buf: &mut Vec<u8>, value: u16) {
+    buf.extend_from_slice(&value.to_be_bytes());
+}
+
+pub fn push_i16(buf: &mut Vec<u8>, value: i16) {
+    buf.extend_from_slice(&value.to_be_bytes());
+}
+
+pub fn push_u32(buf: &mut Vec<u8>, value: u32) {
+    buf.extend_from_slice(&value.to_be_bytes());
+}
+
+fn padded_len(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+pub fn checksum(data: &[u8]) -> u32 {
+    data.chunks(4).fold(0u32, |sum, chunk| {
+        let mut word = [0; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        sum.wrapping_add(u32::from_be_bytes(word))
+    })
+}
+
+// glyph 0: 空の .notdef
+// glyph 1: 正方形の simple glyph
+// glyph 2: glyph 1 を 2 回参照する composite glyph
+// glyph 3: 自分自身を参照する composite glyph
+pub const NUM_GLYPHS: u16 = 4;
+
+pub fn head(index_to_loc_format: i16) -> Vec<u8> {
+    let mut t = Vec::new();
+    push_u16(&mut t, 1); // majorVersion
+    push_u16(&mut t, 0); // minorVersion
+    push_u32(&mut t, 0x00010000); // fontRevision
+    push_u32(&mut t, 0); // checksumAdjustment
+    push_u32(&mut t, 0x5F0F3CF5); // magicNumber
+    push_u16(&mut t, 0b11); // flags
+    push_u16(&mut t, 1000); // unitsPerEm
+    t.extend_from_slice(&[0; 16]); // created, modified
+    for v in [0, 0, 1000, 500] {
+        push_i16(&mut t, v); // xMin, yMin, xMax, yMax
+    }
+    push_u16(&mut t, 0); // macStyle
+    push_u16(&mut t, 8); // lowestRecPPEM
+    push_i16(&mut t, 2); // fontDirectionHint
+    push_i16(&mut t, index_to_loc_format);
+    push_i16(&mut t, 0); // glyphDataFormat
+    t
+}
+
+pub fn maxp() -> Vec<u8> {
+    maxp_with(NUM_GLYPHS, 1)
+}
+
+pub fn maxp_with(num_glyphs: u16, max_component_depth: u16) -> Vec<u8> {
+    let mut t = Vec::new();
+    push_u32(&mut t, 0x00010000); // version
+    push_u16(&mut t, num_glyphs);
+    push_u16(&mut t, 4); // maxPoints
+    push_u16(&mut t, 1); // maxContours
+    push_u16(&mut t, 8); // maxCompositePoints
+    push_u16(&mut t, 2); // maxCompositeContours
+    push_u16(&mut t, 2); // maxZones
+    for _ in 0..6 {
+        push_u16(&mut t, 0); // maxTwilightPoints .. maxSizeOfInstructions
+    }
+    push_u16(&mut t, 2); // maxComponentElements
+    push_u16(&mut t, max_component_depth);
+    t
+}
+
+pub fn hhea() -> Vec<u8> {
+    let mut t = Vec::new();
+    push_u16(&mut t, 1); // majorVersion
+    push_u16(&mut t, 0); // minorVersion
+    push_i16(&mut t, 800); // ascender
+    push_i16(&mut t, -200); // descender
+    push_i16(&mut t, 90); // lineGap
+    push_u16(&mut t, 1000); // advanceWidthMax
+    push_i16(&mut t, 0); // minLeftSideBearing
+    push_i16(&mut t, 0); // minRightSideBearing
+    push_i16(&mut t, 1000); // xMaxExtent
+    push_i16(&mut t, 1); // caretSlopeRise
+    push_i16(&mut t, 0); // caretSlopeRun
+    push_i16(&mut t, 0); // caretOffset
+    for _ in 0..4 {
+        push_i16(&mut t, 0); // reserved
+    }
+    push_i16(&mut t, 0); // metricDataFormat
+    push_u16(&mut t, 3); // numberOfHMetrics
+    t
+}
+
+// glyph 3 は leftSideBearings だけを持ち，glyph 2 の advanceWidth を共有する．
+pub fn hmtx() -> Vec<u8> {
+    let mut t = Vec::new();
+    for (advance_width, lsb) in [(500, 0), (600, 0), (1000, 0)] {
+        push_u16(&mut t, advance_width);
+        push_i16(&mut t, lsb);
+    }
+    push_i16(&mut t, 0);
+    t
+}
+
+pub fn glyphs() -> Vec<Vec<u8>> {
+    let notdef = Vec::new();
+
+    let mut square = Vec::new();
+    push_i16(&mut square, 1); // numberOfContours
+    for v in [0, 0, 500, 500] {
+        push_i16(&mut square, v);
+    }
+    push_u16(&mut square, 3); // endPtsOfContours
+    push_u16(&mut square, 0); // instructionLength
+    square.extend_from_slice(&[0x01 | 0x08, 3]); // ON_CURVE_POINT | REPEAT_FLAG
+    for v in [0, 500, 0, -500] {
+        push_i16(&mut square, v);
+    }
+    for v in [0, 0, 500, 0] {
+        push_i16(&mut square, v);
+    }
+
+    let mut composite = Vec::new();
+    push_i16(&mut composite, -1);
+    for v in [0, 0, 1000, 500] {
+        push_i16(&mut composite, v);
+    }
+    // ARG_1_AND_2_ARE_WORDS | ARGS_ARE_XY_VALUES | MORE_COMPONENTS
+    push_u16(&mut composite, 0x0001 | 0x0002 | 0x0020);
+    push_u16(&mut composite, 1);
+    push_i16(&mut composite, 0);
+    push_i16(&mut composite, 0);
+    // ARGS_ARE_XY_VALUES | WE_HAVE_A_SCALE
+    push_u16(&mut composite, 0x0002 | 0x0008);
+    push_u16(&mut composite, 1);
+    composite.extend_from_slice(&[100, 0]);
+    push_i16(&mut composite, 0x2000); // scale 0.5
+
+    let mut cyclic = Vec::new();
+    push_i16(&mut cyclic, -1);
+    for v in [0, 0, 0, 0] {
+        push_i16(&mut cyclic, v);
+    }
+    push_u16(&mut cyclic, 0x0002);
+    push_u16(&mut cyclic, 3);
+    cyclic.extend_from_slice(&[0, 0]);
+
+    vec![notdef, square, composite, cyclic]
+}
+
+pub const ARG_1_AND_2_ARE_WORDS: u16 = 0x0001;
+pub const ARGS_ARE_XY_VALUES: u16 = 0x0002;
+pub const ROUND_XY_TO_GRID: u16 = 0x0004;
+pub const WE_HAVE_A_SCALE: u16 = 0x0008;
+pub const MORE_COMPONENTS: u16 = 0x0020;
+pub const WE_HAVE_A_TWO_BY_TWO: u16 = 0x0080;
+pub const USE_MY_METRICS: u16 = 0x0200;
+pub const SCALED_COMPONENT_OFFSET: u16 = 0x0800;
+pub const UNSCALED_COMPONENT_OFFSET: u16 = 0x1000;
+
+// on-curve の点だけを持つ 1 本の輪郭．
+pub fn simple_glyph(points: &[(i16, i16)]) -> Vec<u8> {
+    let points: Vec<_> = points.iter().map(|&(x, y)| (x, y, true)).collect();
+    curved_glyph(&points)
+}
+
+// (x, y, on_curve) を並べた 1 本の輪郭．header の bbox は点の範囲にする．
+pub fn curved_glyph(points: &[(i16, i16, bool)]) -> Vec<u8> {
+    let mut glyph = Vec::new();
+    push_i16(&mut glyph, 1); // numberOfContours
+    let xs = points.iter().map(|&(x, _, _)| x);
+    let ys = points.iter().map(|&(_, y, _)| y);
+    for v in [
+        xs.clone().min().unwrap_or(0),
+        ys.clone().min().unwrap_or(0),
+        xs.max().unwrap_or(0),
+        ys.max().unwrap_or(0),
+    ] {
+        push_i16(&mut glyph, v);
+    }
+    push_u16(&mut glyph, points.len() as u16 - 1); // endPtsOfContours
+    push_u16(&mut glyph, 0); // instructionLength
+    for &(_, _, on_curve) in points {
+        glyph.push(u8::from(on_curve)); // ON_CURVE_POINT
+    }
+    let mut previous = (0, 0);
+    for &(x, _, _) in points {
+        push_i16(&mut glyph, x - previous.0);
+        previous.0 = x;
+    }
+    for &(_, y, _) in points {
+        push_i16(&mut glyph, y - previous.1);
+        previous.1 = y;
+    }
+    glyph
+}
+
+// (flags, glyphIndex, argument1, argument2, 変換行列の F2DOT14 の値)．引数は常に 16 ビットで書く．
+pub type Component<'a> = (u16, u16, i16, i16, &'a [i16]);
+
+// header の bbox は 0 にする．必要なら set_bbox で書き換える．
+pub fn composite_glyph(components: &[Component]) -> Vec<u8> {
+    let mut glyph = Vec::new();
+    push_i16(&mut glyph, -1);
+    for _ in 0..4 {
+        push_i16(&mut glyph, 0);
+    }
+    for (i, &(flags, glyph_id, argument1, argument2, transform)) in components.iter().enumerate() {
+        let more = if i + 1 < components.len() {
+            MORE_COMPONENTS
+        } else {
+            0
+        };
+        push_u16(&mut glyph, flags | ARG_1_AND_2_ARE_WORDS | more);
+        push_u16(&mut glyph, glyph_id);
+        push_i16(&mut glyph, argument1);
+        push_i16(&mut glyph, argument2);
+        for &value in transform {
+            push_i16(&mut glyph, value);
+        }
+    }
+    glyph
+}
+
+// xMin, yMin, xMax, yMax を書き換える．offset は glyph の header なら 2，head なら 36．
+pub fn set_bbox(data: &mut [u8], offset: usize, bbox: [i16; 4]) {
+    for (i, v) in bbox.into_iter().enumerate() {
+        data[offset + i * 2..offset + i * 2 + 2].copy_from_slice(&v.to_be_bytes());
+    }
+}
+
+// glyphs を glyph 0 から並べたフォント．head の bbox は各 glyph の header の bbox を合わせた範囲にする．
+pub fn font_with_glyphs(glyphs: &[Vec<u8>], max_component_depth: u16) -> FontBuilder {
+    let (glyf, loca) = build_glyf_and_loca(glyphs, 1);
+    let mut head = head(1);
+    let bboxes = glyphs
+        .iter()
+        .filter(|glyph| glyph.len() >= 10)
+        .map(|glyph| {
+            let v = |i: usize| i16::from_be_bytes([glyph[2 + i * 2], glyph[3 + i * 2]]);
+            [v(0), v(1), v(2), v(3)]
+        });
+    if let Some(bbox) = bboxes.reduce(|a, b| {
+        [
+            a[0].min(b[0]),
+            a[1].min(b[1]),
+            a[2].max(b[2]),
+            a[3].max(b[3]),
+        ]
+    }) {
+        set_bbox(&mut head, 36, bbox);
+    }
+    FontBuilder::new()
+        .table(b"glyf", glyf)
+        .table(b"head", head)
+        .table(b"loca", loca)
+        .table(b"maxp", maxp_with(glyphs.len() as u16, max_component_depth))
+}
+
+// glyf と loca を組で返す．
+pub fn glyf_and_loca(index_to_loc_format: i16) -> (Vec<u8>, Vec<u8>) {
+    build_glyf_and_loca(&glyphs(), index_to_loc_format)
+}
+
+pub fn build_glyf_and_loca(glyphs: &[Vec<u8>], index_to_loc_format: i16) -> (Vec<u8>, Vec<u8>) {
+    let mut glyf = Vec::new();
+    let mut offsets = vec![0];
+    for glyph in glyphs {
+        glyf.extend_from_slice(glyph);
+        glyf.resize((glyf.len() + 1) & !1, 0);
+        offsets.push(glyf.len());
+    }
+    let mut loca = Vec::new();
+    for offset in offsets {
+        if index_to_loc_format == 0 {
+            push_u16(&mut loca, (offset / 2) as u16);
+        } else {
+            push_u32(&mut loca, offset as u32);
+        }
+    }
+    (glyf, loca)
+}
+
+// 'A'..='C' は idDelta で glyph 1..=3 へ，'a'..='b' は glyphIdArray 経由で glyph 1..=2 へ対応させる．
+pub fn cmap() -> Vec<u8> {
+    let seg_count: u16 = 3;
+    let mut sub = Vec::new();
+    push_u16(&mut sub, 4); // format
+    push_u16(&mut sub, 0); // length (あとで埋める)
+    push_u16(&mut sub, 0); // language
+    push_u16(&mut sub, seg_count * 2);
+    push_u16(&mut sub, 4); // searchRange
+    push_u16(&mut sub, 1); // entrySelector
+    push_u16(&mut sub, 2); // rangeShift
+    for v in [0x43, 0x62, 0xFFFF] {
+        push_u16(&mut sub, v); // endCode
+    }
+    push_u16(&mut sub, 0); // reservedPad
+    for v in [0x41, 0x61, 0xFFFF] {
+        push_u16(&mut sub, v); // startCode
+    }
+    for v in [-0x40, 0, 1] {
+        push_i16(&mut sub, v); // idDelta
+    }
+    for v in [0, 4, 0] {
+        push_u16(&mut sub, v); // idRangeOffset
+    }
+    for v in [1, 2] {
+        push_u16(&mut sub, v); // glyphIdArray
+    }
+    let length = sub.len() as u16;
+    sub[2..4].copy_from_slice(&length.to_be_bytes());
+
+    // (0, 5) format 14, (3, 1) format 4, (3, 10) format 12 の順に並べる．
+    cmap_table(&[
+        (0, 5, cmap_format14()),
+        (3, 1, sub),
+        (3, 10, cmap_format12()),
+    ])
+}
+
+// (platformID, encodingID, subtable) を並べた cmap table．
+pub fn cmap_table(subtables: &[(u16, u16, Vec<u8>)]) -> Vec<u8> {
+    let mut t = Vec::new();
+    push_u16(&mut t, 0); // version
+    push_u16(&mut t, subtables.len() as u16); // numTables
+    let mut offset = 4 + 8 * subtables.len();
+    for (platform_id, encoding_id, sub) in subtables {
+        push_u16(&mut t, *platform_id);
+        push_u16(&mut t, *encoding_id);
+        push_u32(&mut t, offset as u32); // subtableOffset
+        offset += sub.len();
+    }
+    for (_, _, sub) in subtables {
+        t.extend_from_slice(sub);
+    }
+    t
+}
+
+// 0x81 を 2 バイト文字の先頭バイトとし，0x20..=0x21 を glyph 1, 2 に，0x8140..=0x8141 を glyph 20, 21 に対応させる．
+// Shift-JIS では 0x8140, 0x8141 は U+3000, U+3001．
+pub fn cmap_format2() -> Vec<u8> {
+    let mut sub = Vec::new();
+    for v in [2, 0, 0] {
+        push_u16(&mut sub, v); // format, length (あとで埋める), language
+    }
+    for high_byte in 0..256 {
+        push_u16(&mut sub, if high_byte == 0x81 { 8 } else { 0 }); // subHeaderKeys
+    }
+    // subHeader 0: 0x20..=0x21 を glyphIdArray[0..2] に．idRangeOffset は自身の位置から数える．
+    for v in [0x20, 2, 0, 2 * 8 - 6] {
+        push_u16(&mut sub, v);
+    }
+    // subHeader 1: 0x40..=0x41 を glyphIdArray[2..4] に．idDelta = 10．
+    for v in [0x40, 2, 10, 8 - 6 + 4] {
+        push_u16(&mut sub, v);
+    }
+    for v in [1, 2, 10, 11] {
+        push_u16(&mut sub, v); // glyphIdArray
+    }
+    let length = sub.len() as u16;
+    sub[2..4].copy_from_slice(&length.to_be_bytes());
+    sub
+}
+
+// format 4 と同じ対応に加えて，BMP 外の U+20000 を glyph 3 に対応させる．
+pub fn cmap_format12() -> Vec<u8> {
+    let groups: [(u32, u32, u32); 3] = [(0x41, 0x43, 1), (0x61, 0x62, 1), (0x20000, 0x20000, 3)];
+    let mut sub = Vec::new();
+    push_u16(&mut sub, 12); // format
+    push_u16(&mut sub, 0); // reserved
+    push_u32(&mut sub, 16 + 12 * groups.len() as u32); // length
+    push_u32(&mut sub, 0); // language
+    push_u32(&mut sub, groups.len() as u32); // numGroups
+    for (start, end, glyph_id) in groups {
+        push_u32(&mut sub, start);
+        push_u32(&mut sub, end);
+        push_u32(&mut sub, glyph_id);
+    }
+    sub
+}
+
+// U+FE00 で 'A' は default，'B' は glyph 3 になる．
+pub fn cmap_format14() -> Vec<u8> {
+    let mut sub = Vec::new();
+    push_u16(&mut sub, 14); // format
+    push_u32(&mut sub, 0); // length (あとで埋める)
+    push_u32(&mut sub, 1); // numVarSelectorRecords
+    sub.extend_from_slice(&[0x00, 0xFE, 0x00]); // varSelector
+    push_u32(&mut sub, 21); // defaultUVSOffset
+    push_u32(&mut sub, 29); // nonDefaultUVSOffset
+    push_u32(&mut sub, 1); // numUnicodeValueRanges
+    sub.extend_from_slice(&[0x00, 0x00, 0x41, 0x00]); // startUnicodeValue, additionalCount
+    push_u32(&mut sub, 1); // numUVSMappings
+    sub.extend_from_slice(&[0x00, 0x00, 0x42]); // unicodeValue
+    push_u16(&mut sub, 3); // glyphID
+    let length = sub.len() as u32;
+    sub[2..6].copy_from_slice(&length.to_be_bytes());
+    sub
+}
+
+pub fn name() -> Vec<u8> {
+    let family: Vec<u8> = "Test".encode_utf16().flat_map(u16::to_be_bytes).collect();
+    let full = b"Test Regular".to_vec();
+    // (platformID, encodingID, languageID, nameID, string)
+    let records: [(u16, u16, u16, u16, &[u8]); 2] =
+        [(3, 1, 0x0409, 1, &family), (1, 0, 0, 4, &full)];
+
+    let mut t = Vec::new();
+    push_u16(&mut t, 0); // version
+    push_u16(&mut t, records.len() as u16);
+    push_u16(&mut t, 6 + 12 * records.len() as u16); // storageOffset
+    let mut storage = Vec::new();
+    for (platform_id, encoding_id, language_id, name_id, string) in records {
+        push_u16(&mut t, platform_id);
+        push_u16(&mut t, encoding_id);
+        push_u16(&mut t, language_id);
+        push_u16(&mut t, name_id);
+        push_u16(&mut t, string.len() as u16);
+        push_u16(&mut t, storage.len() as u16);
+        storage.extend_from_slice(string);
+    }
+    t.extend_from_slice(&storage);
+    t
+}
+
+pub fn os2() -> Vec<u8> {
+    let mut t = Vec::new();
+    push_u16(&mut t, 4); // version
+    push_i16(&mut t, 500); // xAvgCharWidth
+    push_u16(&mut t, 400); // usWeightClass
+    push_u16(&mut t, 5); // usWidthClass
+    push_u16(&mut t, 0); // fsType
+    for _ in 0..11 {
+        push_i16(&mut t, 0); // ySubscriptXSize .. sFamilyClass
+    }
+    t.extend_from_slice(&[0; 10]); // panose
+    push_u32(&mut t, 1); // ulUnicodeRange1: Basic Latin
+    for _ in 0..3 {
+        push_u32(&mut t, 0);
+    }
+    t.extend_from_slice(b"NONE"); // achVendID
+    push_u16(&mut t, 1 << 6); // fsSelection: REGULAR
+    push_u16(&mut t, 0x41); // usFirstCharIndex
+    push_u16(&mut t, 0x62); // usLastCharIndex
+    push_i16(&mut t, 800); // sTypoAscender
+    push_i16(&mut t, -200); // sTypoDescender
+    push_i16(&mut t, 0); // sTypoLineGap
+    push_u16(&mut t, 1000); // usWinAscent
+    push_u16(&mut t, 200); // usWinDescent
+    push_u32(&mut t, 1); // ulCodePageRange1
+    push_u32(&mut t, 0); // ulCodePageRange2
+    push_i16(&mut t, 500); // sxHeight
+    push_i16(&mut t, 700); // sCapHeight
+    push_u16(&mut t, 0); // usDefaultChar
+    push_u16(&mut t, 0x20); // usBreakChar
+    push_u16(&mut t, 1); // usMaxContext
+    t
+}
+
+pub fn gsub() -> Vec<u8> {
+    let mut t = Vec::new();
+    push_u16(&mut t, 1); // majorVersion
+    push_u16(&mut t, 0); // minorVersion
+    push_u16(&mut t, 10); // scriptListOffset
+    push_u16(&mut t, 30); // featureListOffset
+    push_u16(&mut t, 44); // lookupListOffset
+
+    // ScriptList
+    push_u16(&mut t, 1);
+    t.extend_from_slice(b"latn");
+    push_u16(&mut t, 8);
+    // Script
+    push_u16(&mut t, 4); // defaultLangSysOffset
+    push_u16(&mut t, 0); // langSysCount
+                         // LangSys
+    push_u16(&mut t, 0); // lookupOrderOffset
+    push_u16(&mut t, 0xFFFF); // requiredFeatureIndex
+    push_u16(&mut t, 1);
+    push_u16(&mut t, 0);
+
+    // FeatureList
+    push_u16(&mut t, 1);
+    t.extend_from_slice(b"liga");
+    push_u16(&mut t, 8);
+    // Feature
+    push_u16(&mut t, 0); // featureParamsOffset
+    push_u16(&mut t, 1);
+    push_u16(&mut t, 0);
+
+    // LookupList
+    push_u16(&mut t, 1);
+    push_u16(&mut t, 4);
+    // Lookup: SingleSubst
+    push_u16(&mut t, 1); // lookupType
+    push_u16(&mut t, 0); // lookupFlag
+    push_u16(&mut t, 1);
+    push_u16(&mut t, 10);
+    push_u16(&mut t, 0); // markFilteringSet
+                         // SingleSubstFormat1
+    push_u16(&mut t, 1);
+    push_u16(&mut t, 6); // coverageOffset
+    push_i16(&mut t, 1); // deltaGlyphID
+                         // CoverageFormat1
+    push_u16(&mut t, 1);
+    push_u16(&mut t, 1);
+    push_u16(&mut t, 1);
+    t
+}
+
+pub fn fvar() -> Vec<u8> {
+    let mut t = Vec::new();
+    push_u16(&mut t, 1); // majorVersion
+    push_u16(&mut t, 0); // minorVersion
+    push_u16(&mut t, 16); // axesArrayOffset
+    push_u16(&mut t, 2); // reserved
+    push_u16(&mut t, 1); // axisCount
+    push_u16(&mut t, 20); // axisSize
+    push_u16(&mut t, 1); // instanceCount
+    push_u16(&mut t, 10); // instanceSize
+    t.extend_from_slice(b"wght");
+    for v in [100, 400, 900] {
+        push_u32(&mut t, v << 16);
+    }
+    push_u16(&mut t, 0); // flags
+    push_u16(&mut t, 256); // axisNameID
+    push_u16(&mut t, 2); // subfamilyNameID
+    push_u16(&mut t, 0); // flags
+    push_u32(&mut t, 400 << 16);
+    push_u16(&mut t, 6); // postScriptNameID
+    t
+}
+
+// wght が最大 (1.0) と最小 (-1.0) の 2 つの region を持つ MVAR．
+// hasc は (100, -50)，hcla は (200, 0)，hdsc は (-20, 10) の差分を持つ．
+pub fn mvar() -> Vec<u8> {
+    let mut t = Vec::new();
+    push_u16(&mut t, 1); // majorVersion
+    push_u16(&mut t, 0); // minorVersion
+    push_u16(&mut t, 0); // reserved
+    push_u16(&mut t, 8); // valueRecordSize
+    push_u16(&mut t, 3); // valueRecordCount
+    push_u16(&mut t, 12 + 8 * 3); // itemVariationStoreOffset
+    for (tag, inner) in [(b"hasc", 0), (b"hcla", 1), (b"hdsc", 2)] {
+        t.extend_from_slice(tag);
+        push_u16(&mut t, 0); // deltaSetOuterIndex
+        push_u16(&mut t, inner); // deltaSetInnerIndex
+    }
+    // ItemVariationStore
+    push_u16(&mut t, 1); // format
+    push_u32(&mut t, 12); // variationRegionListOffset
+    push_u16(&mut t, 1); // itemVariationDataCount
+    push_u32(&mut t, 28); // itemVariationDataOffsets
+                          // VariationRegionList
+    push_u16(&mut t, 1); // axisCount
+    push_u16(&mut t, 2); // regionCount
+    for (start, peak, end) in [(0, 0x4000, 0x4000), (-0x4000, -0x4000, 0)] {
+        push_i16(&mut t, start);
+        push_i16(&mut t, peak);
+        push_i16(&mut t, end);
+    }
+    // ItemVariationData
+    push_u16(&mut t, 3); // itemCount
+    push_u16(&mut t, 1); // wordDeltaCount
+    push_u16(&mut t, 2); // regionIndexCount
+    push_u16(&mut t, 0);
+    push_u16(&mut t, 1);
+    for (word, byte) in [(100, -50), (200, 0), (-20, 10)] {
+        push_i16(&mut t, word);
+        t.push(byte as u8);
+    }
+    t
+}
+
+pub fn stat() -> Vec<u8> {
+    let mut t = Vec::new();
+    push_u16(&mut t, 1); // majorVersion
+    push_u16(&mut t, 2); // minorVersion
+    push_u16(&mut t, 8); // designAxisSize
+    push_u16(&mut t, 1); // designAxisCount
+    push_u32(&mut t, 20); // designAxesOffset
+    push_u16(&mut t, 2); // axisValueCount
+    push_u32(&mut t, 28); // offsetToAxisValueOffsets
+    push_u16(&mut t, 2); // elidedFallbackNameID
+                         // AxisRecord
+    t.extend_from_slice(b"wght");
+    push_u16(&mut t, 256);
+    push_u16(&mut t, 0);
+    // axisValueOffsets
+    push_u16(&mut t, 4);
+    push_u16(&mut t, 16);
+    // AxisValueFormat1
+    push_u16(&mut t, 1);
+    push_u16(&mut t, 0); // axisIndex
+    push_u16(&mut t, 2); // flags
+    push_u16(&mut t, 257); // valueNameID
+    push_u32(&mut t, 400 << 16);
+    // AxisValueFormat4
+    push_u16(&mut t, 4);
+    push_u16(&mut t, 1); // axisCount
+    push_u16(&mut t, 0); // flags
+    push_u16(&mut t, 258); // valueNameID
+    push_u16(&mut t, 0); // axisIndex
+    push_u32(&mut t, 700 << 16);
+    t
+}
+
+// Windows 形式の kern．format 0 のサブテーブルに (glyph 1, glyph 2) の -100 を入れる．
+pub fn kern() -> Vec<u8> {
+    kern_with(&[(0x0001, &[(1, 2, -100), (2, 1, 50)])])
+}
+
+// (left, right, value)．
+pub type KernPair = (u16, u16, i16);
+
+// (coverage, pairs) を並べた Windows 形式の kern．
+pub fn kern_with(subtables: &[(u16, &[KernPair])]) -> Vec<u8> {
+    let mut t = Vec::new();
+    push_u16(&mut t, 0); // version
+    push_u16(&mut t, subtables.len() as u16); // nTables
+    for (coverage, pairs) in subtables {
+        let n_pairs = pairs.len() as u16;
+        let entry_selector = 15 - n_pairs.max(1).leading_zeros() as u16;
+        let search_range = (1 << entry_selector) * 6;
+        push_u16(&mut t, 0); // version
+        push_u16(&mut t, 6 + 8 + 6 * n_pairs); // length
+        push_u16(&mut t, *coverage);
+        push_u16(&mut t, n_pairs);
+        push_u16(&mut t, search_range);
+        push_u16(&mut t, entry_selector);
+        push_u16(&mut t, (6 * n_pairs).saturating_sub(search_range)); // rangeShift
+        for &(left, right, value) in *pairs {
+            push_u16(&mut t, left);
+            push_u16(&mut t, right);
+            push_i16(&mut t, value);
+        }
+    }
+    t
+}
+
+// format 2 の post．glyph 1 は標準の名前 A，glyph 2 と 3 は独自の名前を持つ．
+pub fn post() -> Vec<u8> {
+    let mut t = post_header(0x00020000);
+    push_u16(&mut t, NUM_GLYPHS); // numGlyphs
+    for index in [0, 36, 258, 259] {
+        push_u16(&mut t, index); // glyphNameIndex
+    }
+    for name in ["composite", "cyclic"] {
+        t.push(name.len() as u8);
+        t.extend_from_slice(name.as_bytes());
+    }
+    t
+}
+
+pub fn post_header(version: u32) -> Vec<u8> {
+    let mut t = Vec::new();
+    push_u32(&mut t, version);
+    push_u32(&mut t, (-12i32 << 16) as u32 | 0x8000); // italicAngle: -11.5
+    push_i16(&mut t, -100); // underlinePosition
+    push_i16(&mut t, 50); // underlineThickness
+    push_u32(&mut t, 1); // isFixedPitch
+    for _ in 0..4 {
+        push_u32(&mut t, 0); // minMemType42 .. maxMemType1
+    }
+    t
+}
+
+pub fn build_font(index_to_loc_format: i16) -> Vec<u8> {
+    let (glyf, loca) = glyf_and_loca(index_to_loc_format);
+    FontBuilder::new()
+        .table(b"GSUB", gsub())
+        .table(b"MVAR", mvar())
+        .table(b"OS/2", os2())
+        .table(b"STAT", stat())
+        .table(b"cmap", cmap())
+        .table(b"fvar", fvar())
+        .table(b"glyf", glyf)
+        .table(b"head", head(index_to_loc_format))
+        .table(b"hhea", hhea())
+        .table(b"hmtx", hmtx())
+        .table(b"kern", kern())
+        .table(b"loca", loca)
+        .table(b"maxp", maxp())
+        .table(b"name", name())
+        .table(b"post", post())
+        .build()
+}
+
+pub fn test_font() -> Vec<u8> {
+    build_font(0)
+}
+
+// 同じフォントを 2 つ並べた TrueType Collection を作る．
+pub fn test_collection() -> Vec<u8> {
+    let font = test_font();
+    build_collection(&[font.clone(), font], None)
+}
+
+// dsig を渡すと version 2.0 の header にして，末尾に DSIG table を置く．
+pub fn build_collection(fonts: &[Vec<u8>], dsig: Option<&[u8]>) -> Vec<u8> {
+    let dsig_fields_len = if dsig.is_some() { 12 } else { 0 };
+    let header_len = 12 + 4 * fonts.len() + dsig_fields_len;
+    let fonts_len: usize = fonts.iter().map(|font| font.len()).sum();
+    let mut ttc = Vec::new();
+    ttc.extend_from_slice(b"ttcf");
+    push_u32(
+        &mut ttc,
+        if dsig.is_some() {
+            0x00020000
+        } else {
+            0x00010000
+        },
+    );
+    push_u32(&mut ttc, fonts.len() as u32);
+    let mut offset = header_len;
+    for font in fonts {
+        push_u32(&mut ttc, offset as u32);
+        offset += font.len();
+    }
+    if let Some(dsig) = dsig {
+        ttc.extend_from_slice(b"DSIG");
+        push_u32(&mut ttc, dsig.len() as u32);
+        push_u32(&mut ttc, (header_len + fonts_len) as u32);
+    }
+    for font in fonts {
+        ttc.extend_from_slice(&rebase(font, ttc.len()));
+    }
+    if let Some(dsig) = dsig {
+        ttc.extend_from_slice(dsig);
+    }
+    ttc
+}
+
+// table directory 内の offset を base だけずらす．
+fn rebase(font: &[u8], base: usize) -> Vec<u8> {
+    let mut font = font.to_vec();
+    if font.len() < 12 {
+        return font;
+    }
+    let num_tables = u16::from_be_bytes([font[4], font[5]]) as usize;
+    for i in 0..num_tables {
+        let at = 12 + 16 * i + 8;
+        let offset = u32::from_be_bytes(font[at..at + 4].try_into().unwrap());
+        font[at..at + 4].copy_from_slice(&(offset + base as u32).to_be_bytes());
+    }
+    font
+}
@@ -0,0 +1,226 @@
+mod common;
+
+use common::{
+    composite_glyph, push_i16, push_u16, simple_glyph, Component, ARGS_ARE_XY_VALUES,
+    ROUND_XY_TO_GRID, SCALED_COMPONENT_OFFSET, UNSCALED_COMPONENT_OFFSET, USE_MY_METRICS,
+    WE_HAVE_A_SCALE, WE_HAVE_A_TWO_BY_TWO,
+};
+use font_decoder::{glyf::GlyphPoint, ErrorKind, Font};
+
+// glyph 0 (.notdef) は空．
+// glyph 1: (0, 0) から (500, 500) の正方形．
+// glyph 2: (0, 0), (100, 0), (0, 100) の三角形．
+fn base_glyphs() -> Vec<Vec<u8>> {
+    vec![
+        Vec::new(),
+        simple_glyph(&[(0, 0), (0, 500), (500, 500), (500, 0)]),
+        simple_glyph(&[(0, 0), (100, 0), (0, 100)]),
+    ]
+}
+
+fn resolve(component: Component) -> Vec<(f64, f64)> {
+    let mut glyphs = base_glyphs();
+    glyphs.push(composite_glyph(&[component]));
+    let data = common::font_with_glyphs(&glyphs, 1).build();
+    let font = Font::new(&data).unwrap();
+    let (points, _) = font.outline(3).unwrap();
+    points.iter().map(|point| (point.x, point.y)).collect()
+}
+
+fn coordinates(points: &[GlyphPoint]) -> Vec<(f64, f64)> {
+    points.iter().map(|point| (point.x, point.y)).collect()
+}
+
+#[test]
+fn point_matching() {
+    // 三角形の点 1 を正方形の点 2 (500, 500) に合わせる．
+    let mut glyphs = base_glyphs();
+    glyphs.push(composite_glyph(&[
+        (ARGS_ARE_XY_VALUES, 1, 0, 0, &[]),
+        (0, 2, 2, 1, &[]),
+    ]));
+    let data = common::font_with_glyphs(&glyphs, 1).build();
+    let font = Font::new(&data).unwrap();
+    let resolved = font.resolve_glyph(3).unwrap();
+    assert_eq!(
+        coordinates(&resolved.points[4..]),
+        [(400.0, 500.0), (500.0, 500.0), (400.0, 600.0)]
+    );
+    assert_eq!((resolved.bbox.xmax, resolved.bbox.ymax), (500.0, 600.0));
+
+    // 範囲外の点番号はエラー．
+    let mut glyphs = base_glyphs();
+    glyphs.push(composite_glyph(&[
+        (ARGS_ARE_XY_VALUES, 1, 0, 0, &[]),
+        (0, 2, 4, 0, &[]),
+    ]));
+    let data = common::font_with_glyphs(&glyphs, 1).build();
+    let error = Font::new(&data).unwrap().outline(3).unwrap_err();
+    assert_eq!(error.field, Some("argument1"));
+}
+
+#[test]
+fn component_offset_scaling() {
+    let half = &[0x2000];
+    // どちらのフラグも無い場合は拡大しない．
+    let points = resolve((ARGS_ARE_XY_VALUES | WE_HAVE_A_SCALE, 2, 100, 10, half));
+    assert_eq!(points[1], (150.0, 10.0));
+    let points = resolve((
+        ARGS_ARE_XY_VALUES | WE_HAVE_A_SCALE | SCALED_COMPONENT_OFFSET,
+        2,
+        100,
+        10,
+        half,
+    ));
+    assert_eq!(points[1], (100.0, 5.0));
+    // 両方のフラグが立っている場合は拡大しない．
+    let points = resolve((
+        ARGS_ARE_XY_VALUES | WE_HAVE_A_SCALE | SCALED_COMPONENT_OFFSET | UNSCALED_COMPONENT_OFFSET,
+        2,
+        100,
+        10,
+        half,
+    ));
+    assert_eq!(points[1], (150.0, 10.0));
+    // ROUND_XY_TO_GRID は拡大したオフセットを丸める．
+    let points = resolve((
+        ARGS_ARE_XY_VALUES | WE_HAVE_A_SCALE | SCALED_COMPONENT_OFFSET | ROUND_XY_TO_GRID,
+        2,
+        101,
+        -11,
+        half,
+    ));
+    assert_eq!(points[0], (51.0, -6.0));
+}
+
+#[test]
+fn two_by_two_transform() {
+    // (a, b, c, d) = (0, 1, -1, 0) は 90 度の回転で，(x, y) は (-y, x) になる．
+    let points = resolve((
+        ARGS_ARE_XY_VALUES | WE_HAVE_A_TWO_BY_TWO,
+        2,
+        0,
+        0,
+        &[0, 0x4000, -0x4000, 0],
+    ));
+    assert_eq!(points, [(0.0, 0.0), (0.0, 100.0), (-100.0, 0.0)]);
+    // 拡大したオフセットは各列の長さで拡大する．
+    let points = resolve((
+        ARGS_ARE_XY_VALUES | WE_HAVE_A_TWO_BY_TWO | SCALED_COMPONENT_OFFSET,
+        2,
+        100,
+        100,
+        &[0, 0x2000, -0x2000, 0],
+    ));
+    assert_eq!(points[0], (50.0, 50.0));
+}
+
+#[test]
+fn use_my_metrics() {
+    let mut glyphs = base_glyphs();
+    glyphs.push(composite_glyph(&[
+        (ARGS_ARE_XY_VALUES, 1, 0, 0, &[]),
+        (ARGS_ARE_XY_VALUES | USE_MY_METRICS, 2, 0, 0, &[]),
+    ]));
+    glyphs.push(composite_glyph(&[(ARGS_ARE_XY_VALUES, 1, 0, 0, &[])]));
+    // USE_MY_METRICS を持つ composite glyph を USE_MY_METRICS で参照する．
+    glyphs.push(composite_glyph(&[(
+        ARGS_ARE_XY_VALUES | USE_MY_METRICS,
+        3,
+        0,
+        0,
+        &[],
+    )]));
+    let data = common::font_with_glyphs(&glyphs, 2).build();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(font.resolve_glyph(3).unwrap().metrics_glyph_id, Some(2));
+    assert_eq!(font.resolve_glyph(4).unwrap().metrics_glyph_id, None);
+    assert_eq!(font.resolve_glyph(5).unwrap().metrics_glyph_id, Some(2));
+    assert_eq!(font.resolve_glyph(1).unwrap().metrics_glyph_id, None);
+}
+
+#[test]
+fn use_my_metrics_applies_to_h_metrics() {
+    let mut glyphs = base_glyphs();
+    glyphs.push(composite_glyph(&[
+        (ARGS_ARE_XY_VALUES, 1, 0, 0, &[]),
+        (ARGS_ARE_XY_VALUES | USE_MY_METRICS, 2, 0, 0, &[]),
+    ]));
+    glyphs.push(composite_glyph(&[(ARGS_ARE_XY_VALUES, 1, 0, 0, &[])]));
+    glyphs.push(composite_glyph(&[(
+        ARGS_ARE_XY_VALUES | USE_MY_METRICS,
+        3,
+        0,
+        0,
+        &[],
+    )]));
+    let mut hhea = common::hhea();
+    hhea[34..36].copy_from_slice(&(glyphs.len() as u16).to_be_bytes()); // numberOfHMetrics
+    let mut hmtx = Vec::new();
+    for advance_width in [0, 500, 100, 600, 700, 800] {
+        push_u16(&mut hmtx, advance_width);
+        push_i16(&mut hmtx, advance_width as i16 / 10);
+    }
+    let data = common::font_with_glyphs(&glyphs, 2)
+        .table(b"hhea", hhea)
+        .table(b"hmtx", hmtx)
+        .build();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(font.advance_width(3).unwrap(), 100);
+    assert_eq!(font.glyph_h_metrics(3).unwrap().lsb, 10);
+    assert_eq!(font.advance_width(4).unwrap(), 700);
+    assert_eq!(font.advance_width(5).unwrap(), 100);
+    assert_eq!(font.advance_width(1).unwrap(), 500);
+}
+
+#[test]
+fn depth_ignores_max_component_depth() {
+    let mut glyphs = base_glyphs();
+    glyphs.push(composite_glyph(&[(ARGS_ARE_XY_VALUES, 1, 0, 0, &[])]));
+    glyphs.push(composite_glyph(&[(ARGS_ARE_XY_VALUES, 3, 0, 0, &[])]));
+
+    // maxp の maxComponentDepth より深い入れ子も展開する．
+    for max_component_depth in [0, 1, 2] {
+        let data = common::font_with_glyphs(&glyphs, max_component_depth).build();
+        let font = Font::new(&data).unwrap();
+        assert_eq!(font.resolve_glyph(1).unwrap().depth, 0);
+        assert_eq!(font.resolve_glyph(3).unwrap().depth, 1);
+        let resolved = font.resolve_glyph(4).unwrap();
+        assert_eq!(resolved.depth, 2);
+        assert_eq!(resolved.points.len(), 4);
+    }
+
+    // 内部の上限 64 を超える入れ子はエラーにする．
+    for glyph_id in 4..70 {
+        glyphs.push(composite_glyph(&[(
+            ARGS_ARE_XY_VALUES | USE_MY_METRICS,
+            glyph_id,
+            0,
+            0,
+            &[],
+        )]));
+    }
+    let data = common::font_with_glyphs(&glyphs, 1).build();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(font.resolve_glyph(66).unwrap().depth, 64);
+    let error = font.outline(67).unwrap_err();
+    assert_eq!(error.kind, ErrorKind::InvalidValue);
+    assert_eq!(error.field, Some("glyphIndex"));
+    // USE_MY_METRICS をたどる場合も同じ上限を使う．glyph 4 は USE_MY_METRICS を持たない．
+    assert_eq!(font.metrics_glyph_id(68).unwrap(), 4);
+    assert!(font.metrics_glyph_id(69).is_err());
+}
+
+#[test]
+fn cycles_are_errors() {
+    // glyph 3 と glyph 4 が互いに参照し合う．
+    let mut glyphs = base_glyphs();
+    glyphs.push(composite_glyph(&[(ARGS_ARE_XY_VALUES, 4, 0, 0, &[])]));
+    glyphs.push(composite_glyph(&[(ARGS_ARE_XY_VALUES, 3, 0, 0, &[])]));
+    let data = common::font_with_glyphs(&glyphs, 0).build();
+    let font = Font::new(&data).unwrap();
+    for glyph_id in [3, 4] {
+        let error = font.outline(glyph_id).unwrap_err();
+        assert_eq!(error.field, Some("glyphIndex"));
+    }
+}
@@ -0,0 +1,161 @@
+mod common;
+
+use font_decoder::{
+    coverage::{CharacterSet, Coverage, CoverageCount, Os2Issue},
+    Font,
+};
+
+// テスト用のフォントは A, B, C, a, b, U+20000 を持つ．
+fn test_coverage() -> Coverage {
+    let data = common::test_font();
+    let font = Font::new(&data).unwrap();
+    Coverage::from_charmap(font.charmap().unwrap())
+}
+
+#[test]
+fn counts_blocks_and_scripts() {
+    let coverage = test_coverage();
+    assert_eq!(coverage.chars(), &['A', 'B', 'C', 'a', 'b', '\u{20000}']);
+
+    let blocks = coverage.blocks();
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(
+        blocks[0],
+        CoverageCount {
+            name: "Basic Latin",
+            covered: 5,
+            total: 128,
+        }
+    );
+    assert_eq!(blocks[1].name, "CJK Unified Ideographs Extension B");
+    assert_eq!(blocks[1].covered, 1);
+
+    let scripts = coverage.scripts();
+    let names: Vec<_> = scripts.iter().map(|count| count.name).collect();
+    assert_eq!(names, ["Latin", "Han"]);
+    assert_eq!(scripts[0].covered, 5);
+
+    // 未割り当ての文字は total に含めない．
+    let greek = coverage.get_block_coverage("Greek and Coptic").unwrap();
+    assert_eq!((greek.covered, greek.total), (0, 135));
+    let private_use = coverage.get_block_coverage("Private Use Area").unwrap();
+    assert_eq!(private_use.total, 0x1900);
+    assert_eq!(
+        coverage.get_script_coverage("Latin").unwrap().missing(),
+        scripts[0].total - 5
+    );
+    assert!(coverage.get_script_coverage("Klingon").is_none());
+}
+
+#[test]
+fn unknown_script() {
+    // 私用領域と未割り当ての文字は Unknown に数える．
+    let coverage = Coverage::new(['A', '\u{E000}', '\u{E001}', '\u{0378}']);
+    let scripts = coverage.scripts();
+    let names: Vec<_> = scripts.iter().map(|count| count.name).collect();
+    assert_eq!(names, ["Latin", "Unknown"]);
+    assert_eq!(scripts[1].covered, 3);
+    let unknown = coverage.get_script_coverage("Unknown").unwrap();
+    assert_eq!(unknown, scripts[1]);
+    // 私用領域はすべて Unknown に含まれる．
+    assert!(unknown.total > 0x1900 + 0xFFFE * 2);
+
+    let coverage = test_coverage();
+    assert_eq!(coverage.get_script_coverage("Unknown").unwrap().covered, 0);
+}
+
+#[test]
+fn compares_with_character_sets() {
+    let text = "\
+# テスト用の文字集合
+[Upper]
+U+0041..U+0043
+
+[Lower] # 範囲の書き方は 3 通り
+0061-0063 0064..0064
+0065
+";
+    let sets = CharacterSet::parse_sets(text).unwrap();
+    assert_eq!(sets.len(), 2);
+    assert_eq!(sets[0].name, "Upper");
+    assert_eq!(sets[1].chars(), &['a', 'b', 'c', 'd', 'e']);
+
+    let coverage = test_coverage();
+    let upper = coverage.compare(&sets[0]);
+    assert!(upper.is_complete());
+    assert_eq!((upper.covered, upper.total), (3, 3));
+    let lower = coverage.compare(&sets[1]);
+    assert_eq!(lower.name, "Lower");
+    assert_eq!(lower.missing, ['c', 'd', 'e']);
+}
+
+#[test]
+fn malformed_character_sets_are_errors() {
+    // 集合の名前より前に文字がある．
+    assert_eq!(CharacterSet::parse_sets("0041\n").unwrap_err().offset, 0);
+    // offset は不正な字句の位置．
+    let error = CharacterSet::parse_sets("[A]\n  0041 XYZ\n").unwrap_err();
+    assert_eq!(error.offset, 11);
+    assert_eq!(error.field, Some("characterSetRange"));
+    assert!(CharacterSet::parse_sets("[A]\n0043..0041\n").is_err());
+    assert!(CharacterSet::parse_sets("[A]\n110000\n").is_err());
+}
+
+#[test]
+fn cross_checks_os2() {
+    let data = common::test_font();
+    let font = Font::new(&data).unwrap();
+    let coverage = Coverage::from_charmap(font.charmap().unwrap());
+    let os2 = font.table().get_os2_table().unwrap();
+    let issues = coverage.check_os2(&os2);
+    assert_eq!(
+        issues[..2],
+        [
+            Os2Issue::UnicodeRangeNotClaimed {
+                bit: 57,
+                name: "Non-Plane 0",
+                covered: 1,
+            },
+            Os2Issue::UnicodeRangeNotClaimed {
+                bit: 59,
+                name: "CJK Unified Ideographs",
+                covered: 1,
+            },
+        ]
+    );
+    // cp1252 を申告しているが，ASCII 以外の文字が一つも無い．
+    let Os2Issue::CodePageIncomplete {
+        bit: 0,
+        missing,
+        total,
+        ..
+    } = issues[2]
+    else {
+        panic!("{:?}", issues[2]);
+    };
+    assert_eq!(missing, total);
+    assert_eq!(issues.len(), 3);
+    assert_eq!(
+        issues[2].to_string(),
+        format!(
+            "ulCodePageRange bit 0 (cp1252) is set but {total} of {total} characters are missing"
+        )
+    );
+
+    // ASCII だけでは cp1252 を満たさない．cp1252 の 0x80..=0xFF の文字がそろえば満たす．
+    let ascii = Coverage::new(' '..='~');
+    let issues = ascii.check_os2(&os2);
+    assert!(matches!(
+        issues[..],
+        [Os2Issue::CodePageIncomplete { bit: 0, .. }]
+    ));
+    let latin1 = Coverage::new(
+        (' '..='~')
+            .chain('\u{A0}'..='\u{FF}')
+            .chain("ŒœŠšŸŽžƒˆ˜–—‘’‚“”„†‡•…‰‹›€™".chars()),
+    );
+    assert!(latin1
+        .check_os2(&os2)
+        .iter()
+        .all(|issue| !matches!(issue, Os2Issue::CodePageIncomplete { .. })));
+}
@@ -0,0 +1,152 @@
+mod common;
+
+use font_decoder::{
+    data_types::{HEAD, MAXP, NAME},
+    table::{DirectoryIssue, Table},
+};
+
+// i 番目の table record の位置．
+fn record_at(i: usize) -> usize {
+    12 + 16 * i
+}
+
+fn set_u32(data: &mut [u8], at: usize, value: u32) {
+    data[at..at + 4].copy_from_slice(&value.to_be_bytes());
+}
+
+fn three_tables() -> Vec<u8> {
+    common::FontBuilder::new()
+        .table(b"head", common::head(0))
+        .table(b"maxp", common::maxp())
+        .table(b"name", common::name())
+        .build()
+}
+
+#[test]
+fn valid_directory() {
+    let data = common::test_font();
+    let table = Table::new(&data).unwrap();
+    assert!(table.table_directory.is_sorted());
+    assert_eq!(table.validate_directory(), vec![]);
+
+    let data = common::FontBuilder::new().build();
+    assert_eq!(Table::new(&data).unwrap().validate_directory(), vec![]);
+}
+
+#[test]
+fn unsorted_records_use_linear_lookup() {
+    let mut data = three_tables();
+    // head と name の record を入れ替える．
+    let (head, name) = (record_at(0), record_at(2));
+    let head_record = data[head..head + 16].to_vec();
+    data.copy_within(name..name + 16, head);
+    data[name..name + 16].copy_from_slice(&head_record);
+
+    let table = Table::new(&data).unwrap();
+    assert!(!table.table_directory.is_sorted());
+    for tag in [HEAD, MAXP, NAME] {
+        assert_eq!(table.get_table_record(&tag).unwrap().tableTag, tag);
+    }
+    assert!(table.get_head_table().is_ok());
+    assert_eq!(
+        table.validate_directory(),
+        vec![
+            DirectoryIssue::Unsorted {
+                tag: MAXP,
+                previous: NAME
+            },
+            DirectoryIssue::Unsorted {
+                tag: HEAD,
+                previous: MAXP
+            },
+        ]
+    );
+}
+
+#[test]
+fn search_params() {
+    let mut data = three_tables();
+    data[6..12].copy_from_slice(&[0, 0, 0, 0, 0, 0]);
+    let issues = Table::new(&data).unwrap().validate_directory();
+    assert_eq!(
+        issues,
+        vec![
+            DirectoryIssue::SearchRange {
+                stored: 0,
+                expected: 32
+            },
+            DirectoryIssue::EntrySelector {
+                stored: 0,
+                expected: 1
+            },
+            DirectoryIssue::RangeShift {
+                stored: 0,
+                expected: 16
+            },
+        ]
+    );
+}
+
+#[test]
+fn ranges() {
+    let mut data = three_tables();
+    let maxp_offset = Table::new(&data)
+        .unwrap()
+        .get_table_record(&MAXP)
+        .unwrap()
+        .offset;
+    // head を maxp に重ね，name をファイルの外に出す．
+    set_u32(&mut data, record_at(0) + 8, maxp_offset - 2);
+    set_u32(&mut data, record_at(2) + 12, 0x10000);
+    let issues = Table::new(&data).unwrap().validate_directory();
+    assert!(issues.contains(&DirectoryIssue::Misaligned {
+        tag: HEAD,
+        offset: maxp_offset - 2
+    }));
+    assert!(issues.contains(&DirectoryIssue::Overlap {
+        tag: MAXP,
+        other: HEAD
+    }));
+    assert!(issues.contains(&DirectoryIssue::OutOfRange {
+        tag: NAME,
+        offset: Table::new(&data)
+            .unwrap()
+            .get_table_record(&NAME)
+            .unwrap()
+            .offset,
+        length: 0x10000
+    }));
+
+    let mut data = three_tables();
+    set_u32(&mut data, record_at(1) + 8, 0);
+    let issues = Table::new(&data).unwrap().validate_directory();
+    assert!(issues.contains(&DirectoryIssue::OverlapsDirectory { tag: MAXP }));
+}
+
+#[test]
+fn duplicate_records() {
+    let mut data = three_tables();
+    data.copy_within(record_at(0)..record_at(0) + 4, record_at(1));
+    let issues = Table::new(&data).unwrap().validate_directory();
+    assert!(issues.contains(&DirectoryIssue::Duplicate { tag: HEAD }));
+    // 別の範囲を指しているので Overlap にはならない．
+    assert_eq!(issues.len(), 1);
+}
+
+#[test]
+fn non_adjacent_duplicate_records() {
+    // head, maxp, head の順にして，離れた位置で重複させる．
+    let mut data = three_tables();
+    data.copy_within(record_at(0)..record_at(0) + 4, record_at(2));
+    let issues = Table::new(&data).unwrap().validate_directory();
+    assert_eq!(
+        issues,
+        vec![
+            DirectoryIssue::Unsorted {
+                tag: HEAD,
+                previous: MAXP
+            },
+            DirectoryIssue::Duplicate { tag: HEAD },
+        ]
+    );
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use common::push_u16;
+use font_decoder::{cmap::Charmap, encoding::CmapEncoding, table::Table};
+
+fn format6(first_code: u16, glyph_ids: &[u16]) -> Vec<u8> {
+    let mut sub = Vec::new();
+    let length = 10 + 2 * glyph_ids.len() as u16;
+    for v in [6, length, 0, first_code, glyph_ids.len() as u16] {
+        push_u16(&mut sub, v); // format, length, language, firstCode, entryCount
+    }
+    for &glyph_id in glyph_ids {
+        push_u16(&mut sub, glyph_id); // glyphIdArray
+    }
+    sub
+}
+
+fn with_charmap(subtables: &[(u16, u16, Vec<u8>)], f: impl FnOnce(&Charmap)) {
+    let data = common::FontBuilder::new()
+        .table(b"cmap", common::cmap_table(subtables))
+        .build();
+    let cmap = Table::new(&data).unwrap().get_cmap_table().unwrap();
+    f(&Charmap::new(&cmap).unwrap());
+}
+
+#[test]
+fn encode_and_decode() {
+    let cases = [
+        (CmapEncoding::ShiftJis, 'あ', 0x82A0),
+        (CmapEncoding::ShiftJis, 'ｱ', 0xB1),
+        (CmapEncoding::Prc, '中', 0xD6D0),
+        (CmapEncoding::Big5, '中', 0xA4A4),
+        (CmapEncoding::Wansung, '가', 0xB0A1),
+        (CmapEncoding::Johab, '가', 0x8861),
+        (CmapEncoding::Johab, '힣', 0xD3BD),
+        (CmapEncoding::MacRoman, 'é', 0x8E),
+        (CmapEncoding::MacJapanese, 'A', 0x41),
+    ];
+    for (encoding, c, code) in cases {
+        assert_eq!(encoding.encode(c), Some(code), "{:?} {}", encoding, c);
+        assert_eq!(encoding.decode(code), Some(c), "{:?} {:x}", encoding, code);
+    }
+    assert_eq!(CmapEncoding::ShiftJis.encode('가'), None);
+    assert_eq!(CmapEncoding::ShiftJis.decode(0x82), None);
+    assert_eq!(CmapEncoding::Johab.encode('あ'), None);
+    assert_eq!(CmapEncoding::Unknown.encode('A'), None);
+    assert_eq!(CmapEncoding::new(3, 2), CmapEncoding::ShiftJis);
+    assert_eq!(CmapEncoding::new(0, 3), CmapEncoding::Unicode);
+}
+
+#[test]
+fn shift_jis_format2() {
+    with_charmap(&[(3, 2, common::cmap_format2())], |charmap| {
+        assert_eq!(charmap.get_encoding(), (3, 2));
+        assert_eq!(charmap.map(' '), Some(1));
+        assert_eq!(charmap.map('!'), Some(2));
+        assert_eq!(charmap.map('\u{3000}'), Some(20));
+        assert_eq!(charmap.map('、'), Some(21));
+        assert_eq!(charmap.map('あ'), None);
+        assert_eq!(
+            charmap.iter().collect::<Vec<_>>(),
+            vec![(' ', 1), ('!', 2), ('\u{3000}', 20), ('、', 21)]
+        );
+    });
+}
+
+#[test]
+fn legacy_subtables_are_used_without_unicode() {
+    with_charmap(&[(3, 6, format6(0x8861, &[7]))], |charmap| {
+        assert_eq!(charmap.map('가'), Some(7));
+        assert_eq!(charmap.iter().collect::<Vec<_>>(), vec![('가', 7)]);
+    });
+    with_charmap(&[(3, 4, format6(0xA4A4, &[8]))], |charmap| {
+        assert_eq!(charmap.map('中'), Some(8));
+    });
+    // Unicode の subtable があればそちらを使う．
+    with_charmap(
+        &[(3, 4, format6(0xA4A4, &[8])), (3, 1, format6(0x4E2D, &[9]))],
+        |charmap| assert_eq!(charmap.map('中'), Some(9)),
+    );
+}
+
+#[test]
+fn symbol_private_use_area() {
+    with_charmap(&[(3, 0, format6(0xF041, &[5]))], |charmap| {
+        assert_eq!(charmap.get_cmap_encoding(), CmapEncoding::Symbol);
+        assert_eq!(charmap.map('A'), Some(5));
+        assert_eq!(charmap.map('\u{F041}'), Some(5));
+        assert_eq!(charmap.map('B'), None);
+    });
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use font_decoder::{ErrorKind, Font};
+
+#[test]
+fn glyph_index() {
+    let data = common::test_font();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(font.glyph_index('A'), Some(1));
+    assert_eq!(font.glyph_index('C'), Some(3));
+    assert_eq!(font.glyph_index('b'), Some(2));
+    assert_eq!(font.glyph_index('z'), None);
+    assert_eq!(font.glyph_index('\u{1F600}'), None);
+}
+
+#[test]
+fn outline() {
+    let data = common::test_font();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(font.number_of_glyphs().unwrap(), common::NUM_GLYPHS);
+    assert_eq!(font.units_per_em().unwrap(), 1000);
+
+    let (points, bbox) = font.outline(1).unwrap();
+    assert_eq!(points.len(), 4);
+    assert_eq!(
+        (bbox.xmin, bbox.ymin, bbox.xmax, bbox.ymax),
+        (0.0, 0.0, 500.0, 500.0)
+    );
+
+    // 空の glyph は点を持たない．
+    let (points, _) = font.outline(0).unwrap();
+    assert!(points.is_empty());
+    assert!(font.glyph(0).unwrap().is_none());
+
+    assert_eq!(font.outline(2).unwrap().0.len(), 8);
+    assert!(font.outline(3).is_err());
+    assert_eq!(
+        font.outline(common::NUM_GLYPHS).unwrap_err().kind,
+        ErrorKind::InvalidValue
+    );
+}
+
+#[test]
+fn advance_width() {
+    let data = common::test_font();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(font.advance_width(0).unwrap(), 500);
+    assert_eq!(font.advance_width(2).unwrap(), 1000);
+    // numberOfHMetrics 以降は最後の値を使う．
+    assert_eq!(font.advance_width(3).unwrap(), 1000);
+}
+
+#[test]
+fn family_name() {
+    let data = common::test_font();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(font.family_name().as_deref(), Some("Test"));
+}
+
+#[test]
+fn missing_tables() {
+    let data = common::FontBuilder::new()
+        .table(b"head", common::head(0))
+        .build();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(font.glyph_index('A'), None);
+    assert_eq!(font.family_name(), None);
+    assert_eq!(font.outline(0).unwrap_err().kind, ErrorKind::MissingTable);
+    assert_eq!(
+        font.advance_width(0).unwrap_err().kind,
+        ErrorKind::MissingTable
+    );
+}
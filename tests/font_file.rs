@@ -0,0 +1,87 @@
+mod common;
+
+use font_decoder::{ErrorKind, FontFile};
+
+#[test]
+fn single_font() {
+    let data = common::test_font();
+    let font_file = FontFile::from_bytes(&data).unwrap();
+    assert!(!font_file.is_collection());
+    assert_eq!(font_file.len(), 1);
+    let faces: Vec<_> = font_file.faces().collect();
+    assert_eq!(faces.len(), 1);
+    assert_eq!(faces[0].0, 0);
+    assert!(faces[0].1.as_ref().unwrap().get_head_table().is_ok());
+    assert!(font_file.face(1).is_err());
+}
+
+#[test]
+fn sfnt_versions() {
+    for sfnt_version in [
+        0x00010000,
+        u32::from_be_bytes(*b"OTTO"),
+        u32::from_be_bytes(*b"true"),
+    ] {
+        let data = common::FontBuilder::new()
+            .sfnt_version(sfnt_version)
+            .table(b"head", common::head(0))
+            .build();
+        assert_eq!(FontFile::from_bytes(&data).unwrap().len(), 1);
+    }
+}
+
+#[test]
+fn unsupported_containers() {
+    for tag in [*b"wOFF", *b"wOF2", *b"typ1"] {
+        let data = common::FontBuilder::new()
+            .sfnt_version(u32::from_be_bytes(tag))
+            .build();
+        let err = FontFile::from_bytes(&data).err().unwrap();
+        assert_eq!(
+            err.kind,
+            ErrorKind::UnsupportedFormat(u32::from_be_bytes(tag))
+        );
+    }
+    let err = FontFile::from_bytes(b"tt").err().unwrap();
+    assert_eq!(err.kind, ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn collection() {
+    let data = common::test_collection();
+    let font_file = FontFile::from_bytes(&data).unwrap();
+    assert!(font_file.is_collection());
+    assert_eq!(font_file.len(), 2);
+    for (i, (index, table)) in font_file.faces().enumerate() {
+        assert_eq!(i, index);
+        let table = table.unwrap();
+        assert_eq!(table.get_head_table().unwrap().unitsPerEm, 1000);
+    }
+    let FontFile::Collection(collection) = &font_file else {
+        unreachable!()
+    };
+    assert_eq!(collection.header.dsigTag, None);
+    assert_eq!(collection.get_dsig_data().unwrap(), None);
+}
+
+#[test]
+fn collection_version_2() {
+    let data = common::build_collection(&[common::test_font()], Some(b"signature"));
+    let font_file = FontFile::from_bytes(&data).unwrap();
+    let FontFile::Collection(collection) = &font_file else {
+        unreachable!()
+    };
+    assert_eq!(collection.header.majorVersion, 2);
+    assert_eq!(collection.get_dsig_data().unwrap(), Some(&b"signature"[..]));
+    assert!(font_file.face(0).unwrap().get_name_table().is_ok());
+}
+
+#[test]
+fn broken_face_does_not_hide_others() {
+    let data = common::build_collection(&[b"broken".to_vec(), common::test_font()], None);
+    let font_file = FontFile::from_bytes(&data).unwrap();
+    let faces: Vec<_> = font_file.faces().collect();
+    assert_eq!(faces.len(), 2);
+    assert!(faces[0].1.is_err());
+    assert!(faces[1].1.is_ok());
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use common::{push_i16, push_u16};
+use font_decoder::{
+    hhea::HheaTable,
+    hmtx::{HmtxTable, LongHorMetric},
+    table::Table,
+    ErrorKind, Font,
+};
+
+#[test]
+fn hhea() {
+    let data = common::hhea();
+    let hhea = HheaTable::parse(&data).unwrap();
+    assert_eq!((hhea.majorVersion, hhea.minorVersion), (1, 0));
+    assert_eq!(
+        (hhea.ascender, hhea.descender, hhea.lineGap),
+        (800, -200, 90)
+    );
+    assert_eq!(hhea.advanceWidthMax, 1000);
+    assert_eq!(hhea.xMaxExtent, 1000);
+    assert_eq!((hhea.caretSlopeRise, hhea.caretSlopeRun), (1, 0));
+    assert_eq!(hhea.numberOfHMetrics, 3);
+
+    let err = HheaTable::parse(&data[..34]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnexpectedEof);
+    assert_eq!(err.field, Some("numberOfHMetrics"));
+}
+
+fn metric(advance_width: u16, lsb: i16) -> LongHorMetric {
+    LongHorMetric {
+        advanceWidth: advance_width,
+        lsb,
+    }
+}
+
+#[test]
+fn hmtx() {
+    // 2 つの hMetrics と 3 つの leftSideBearings．
+    let mut data = Vec::new();
+    for (advance_width, lsb) in [(500, 10), (600, -20)] {
+        push_u16(&mut data, advance_width);
+        push_i16(&mut data, lsb);
+    }
+    for lsb in [30, 40, 50] {
+        push_i16(&mut data, lsb);
+    }
+    let hmtx = HmtxTable::parse(&data, 2, 5).unwrap();
+    assert_eq!(hmtx.len(), 5);
+    assert_eq!(hmtx.glyph_h_metrics(0), Some(metric(500, 10)));
+    assert_eq!(hmtx.glyph_h_metrics(1), Some(metric(600, -20)));
+    // numberOfHMetrics 以降は最後の advanceWidth と leftSideBearings の値になる．
+    assert_eq!(hmtx.glyph_h_metrics(2), Some(metric(600, 30)));
+    assert_eq!(hmtx.glyph_h_metrics(4), Some(metric(600, 50)));
+    assert_eq!(hmtx.glyph_h_metrics(5), None);
+
+    // すべての glyph が hMetrics を持つ場合．
+    let hmtx = HmtxTable::parse(&data[..8], 2, 2).unwrap();
+    assert!(hmtx.leftSideBearings.is_empty());
+    assert_eq!(hmtx.glyph_h_metrics(2), None);
+
+    // numberOfHMetrics が 0 の場合は advance width が分からない．
+    let hmtx = HmtxTable::parse(&data, 0, 2).unwrap();
+    assert_eq!(hmtx.glyph_h_metrics(0), None);
+
+    // leftSideBearings が足りない場合も，advance width は引ける．無い lsb は 0 になる．
+    let hmtx = HmtxTable::parse(&data, 2, 7).unwrap();
+    assert_eq!(hmtx.len(), 7);
+    assert_eq!(hmtx.leftSideBearings.len(), 3);
+    assert_eq!(hmtx.glyph_h_metrics(4), Some(metric(600, 50)));
+    assert_eq!(hmtx.glyph_h_metrics(6), Some(metric(600, 0)));
+    assert_eq!(hmtx.glyph_h_metrics(7), None);
+    let hmtx = HmtxTable::parse(&data[..data.len() - 1], 2, 5).unwrap();
+    assert_eq!(hmtx.glyph_h_metrics(4), Some(metric(600, 0)));
+    let err = HmtxTable::parse(&data[..6], 2, 2).unwrap_err();
+    assert_eq!(err.field, Some("hMetrics"));
+}
+
+#[test]
+fn table_and_font() {
+    let data = common::test_font();
+    let table = Table::new(&data).unwrap();
+    let hhea = table.get_hhea_table().unwrap();
+    let hmtx = table.get_hmtx_table(hhea.numberOfHMetrics, 4).unwrap();
+    assert_eq!(hmtx.glyph_h_metrics(3), Some(metric(1000, 0)));
+
+    let font = Font::new(&data).unwrap();
+    assert_eq!(font.hhea().unwrap().ascender, 800);
+    assert_eq!(font.glyph_h_metrics(1).unwrap(), metric(600, 0));
+    assert_eq!(font.glyph_h_metrics(3).unwrap(), metric(1000, 0));
+    let err = font.glyph_h_metrics(4).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::InvalidValue);
+    assert_eq!(err.field, Some("glyphId"));
+
+    // hmtx だけが無い場合．
+    let data = common::FontBuilder::new()
+        .table(b"head", common::head(0))
+        .table(b"hhea", common::hhea())
+        .table(b"maxp", common::maxp())
+        .build();
+    let font = Font::new(&data).unwrap();
+    assert!(font.hhea().is_ok());
+    assert_eq!(
+        font.glyph_h_metrics(0).unwrap_err().kind,
+        ErrorKind::MissingTable
+    );
+}
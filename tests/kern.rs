@@ -0,0 +1,92 @@
+mod common;
+
+use common::{push_i16, push_u16, push_u32};
+use font_decoder::{kern::KernTable, ErrorKind, Font};
+
+#[test]
+fn windows_kern() {
+    let data = common::kern();
+    let kern = KernTable::parse(&data).unwrap();
+    assert_eq!((kern.version, kern.nTables), (0, 1));
+    let subtable = &kern.subtables[0];
+    assert_eq!(subtable.format, 0);
+    assert!(subtable.is_horizontal());
+    assert!(!subtable.is_cross_stream());
+    assert_eq!(subtable.pairs.len(), 2);
+    assert_eq!(kern.get_kerning(1, 2), -100);
+    assert_eq!(kern.get_kerning(2, 1), 50);
+    assert_eq!(kern.get_kerning(1, 1), 0);
+}
+
+#[test]
+fn subtables_accumulate() {
+    let data = common::kern_with(&[
+        (0x0001, &[(1, 2, -100), (3, 4, 10)]),
+        (0x0001, &[(1, 2, -20)]),
+        // minimum と cross-stream は横方向の値として使わない．
+        (0x0003, &[(1, 2, -1000)]),
+        (0x0005, &[(1, 2, -1000)]),
+        // format 2 はペアを引かない．
+        (0x0201, &[]),
+    ]);
+    let kern = KernTable::parse(&data).unwrap();
+    assert_eq!(kern.subtables.len(), 5);
+    assert_eq!(kern.subtables[4].format, 2);
+    assert_eq!(kern.get_kerning(1, 2), -120);
+    assert_eq!(kern.get_kerning(3, 4), 10);
+
+    // override は累積した値を置き換える．
+    let data = common::kern_with(&[(0x0001, &[(1, 2, -100)]), (0x0009, &[(1, 2, -30)])]);
+    assert_eq!(KernTable::parse(&data).unwrap().get_kerning(1, 2), -30);
+}
+
+#[test]
+fn apple_kern() {
+    let mut t = Vec::new();
+    push_u32(&mut t, 0x00010000); // version
+    push_u32(&mut t, 2); // nTables
+    for coverage in [0x0000, 0x8000] {
+        push_u32(&mut t, 8 + 8 + 6); // length
+        push_u16(&mut t, coverage);
+        push_u16(&mut t, 0); // tupleIndex
+        push_u16(&mut t, 1); // nPairs
+        t.extend_from_slice(&[0; 6]);
+        push_u16(&mut t, 1);
+        push_u16(&mut t, 2);
+        push_i16(&mut t, -40);
+    }
+    let kern = KernTable::parse(&t).unwrap();
+    assert_eq!(kern.version, 0x00010000);
+    assert!(kern.subtables[0].is_horizontal());
+    // 縦書きのサブテーブルは使わない．
+    assert!(!kern.subtables[1].is_horizontal());
+    assert_eq!(kern.get_kerning(1, 2), -40);
+}
+
+#[test]
+fn unsupported_and_broken() {
+    let err = KernTable::parse(&[0, 2, 0, 0]).err().unwrap();
+    assert_eq!(err.kind, ErrorKind::UnsupportedFormat(2));
+
+    let mut data = common::kern();
+    data.truncate(data.len() - 1);
+    let err = KernTable::parse(&data).err().unwrap();
+    assert_eq!(err.kind, ErrorKind::UnexpectedEof);
+    assert_eq!(err.field, Some("pairs"));
+}
+
+#[test]
+fn font_kerning() {
+    let data = common::test_font();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(font.kerning(1, 2), -100);
+    assert_eq!(font.kerning(2, 2), 0);
+
+    // kern が無いフォントでは 0．
+    let data = common::FontBuilder::new()
+        .table(b"head", common::head(0))
+        .build();
+    let font = Font::new(&data).unwrap();
+    assert!(font.kern().is_err());
+    assert_eq!(font.kerning(1, 2), 0);
+}
@@ -0,0 +1,214 @@
+mod common;
+
+use common::push_u16;
+use font_decoder::{
+    data_types::{Tag, HHEA, MVAR},
+    item_variation::{ItemVariationData, ItemVariationStore},
+    line_metrics::{
+        line_metrics, scaled_line_metrics, LineMetrics, LineMetricsConvention, LineMetricsSource,
+    },
+    mvar::{MvarTable, HORIZONTAL_ASCENDER, HORIZONTAL_DESCENDER, HORIZONTAL_LINE_GAP},
+    ErrorKind, Font,
+};
+use LineMetricsConvention::*;
+
+fn metrics(ascender: f64, descender: f64, line_gap: f64, source: LineMetricsSource) -> LineMetrics {
+    LineMetrics {
+        ascender,
+        descender,
+        line_gap,
+        source,
+    }
+}
+
+// fsSelection に USE_TYPO_METRICS を立てた OS/2．
+fn os2_use_typo_metrics() -> Vec<u8> {
+    let mut t = common::os2();
+    t[62..64].copy_from_slice(&(1u16 << 6 | 1 << 7).to_be_bytes());
+    t
+}
+
+#[test]
+fn item_variation_store() {
+    let data = common::mvar();
+    let mvar = MvarTable::parse(&data).unwrap();
+    assert_eq!(mvar.valueRecordCount, 3);
+    let store = mvar.itemVariationStore.as_ref().unwrap();
+    assert_eq!(store.variationRegionList.regionCount, 2);
+    assert_eq!(store.get_delta(0, 0, &[1.0]), Some(100.0));
+    assert_eq!(store.get_delta(0, 0, &[-1.0]), Some(-50.0));
+    // region の途中では peak からの距離に応じて弱める．
+    assert_eq!(store.get_delta(0, 0, &[0.5]), Some(50.0));
+    assert_eq!(store.get_delta(0, 0, &[-0.25]), Some(-12.5));
+    assert_eq!(store.get_delta(0, 0, &[]), Some(0.0));
+    assert_eq!(store.get_delta(0, 3, &[1.0]), None);
+    assert_eq!(store.get_delta(1, 0, &[1.0]), None);
+
+    assert_eq!(mvar.get_delta(HORIZONTAL_ASCENDER, &[1.0]), 100.0);
+    assert_eq!(mvar.get_delta(HORIZONTAL_DESCENDER, &[-1.0]), 10.0);
+    assert_eq!(mvar.get_delta(HORIZONTAL_LINE_GAP, &[1.0]), 0.0);
+
+    // LONG_WORDS では 4 バイトと 2 バイトの差分になる．
+    let mut t = Vec::new();
+    push_u16(&mut t, 1); // itemCount
+    push_u16(&mut t, 0x8001); // wordDeltaCount
+    push_u16(&mut t, 2); // regionIndexCount
+    push_u16(&mut t, 0);
+    push_u16(&mut t, 1);
+    t.extend_from_slice(&100000i32.to_be_bytes());
+    t.extend_from_slice(&(-300i16).to_be_bytes());
+    let data = ItemVariationData::parse(&t).unwrap();
+    assert_eq!(data.get_deltas(0), Some(vec![100000, -300]));
+    assert!(ItemVariationData::parse(&t[..t.len() - 1]).is_err());
+
+    // word の数が region の数を超えてはならない．
+    t[3] = 3;
+    let err = ItemVariationData::parse(&t).unwrap_err();
+    assert_eq!(err.field, Some("wordDeltaCount"));
+
+    let err = ItemVariationStore::parse(&[0, 2, 0, 0, 0, 0, 0, 0]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnsupportedFormat(2));
+}
+
+#[test]
+fn conventions() {
+    // hhea: 800, -200, 90．OS/2: sTypo* 800, -200, 0，usWin* 1000, 200．
+    let data = common::test_font();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(
+        line_metrics(&font, Mac, &[]).unwrap(),
+        metrics(800.0, -200.0, 90.0, LineMetricsSource::Hhea)
+    );
+    assert_eq!(
+        line_metrics(&font, Css, &[]).unwrap(),
+        metrics(800.0, -200.0, 90.0, LineMetricsSource::Hhea)
+    );
+    // usWin* の行の高さ 1200 が hhea の 1090 より大きいので，line gap は 0 になる．
+    let windows = line_metrics(&font, Windows, &[]).unwrap();
+    assert_eq!(
+        windows,
+        metrics(1000.0, -200.0, 0.0, LineMetricsSource::Win)
+    );
+    assert_eq!(windows.line_height(), 1200.0);
+
+    // USE_TYPO_METRICS があれば Windows と CSS は sTypo* を使う．
+    let data = common::FontBuilder::new()
+        .table(b"head", common::head(0))
+        .table(b"OS/2", os2_use_typo_metrics())
+        .table(b"hhea", common::hhea())
+        .build();
+    let font = Font::new(&data).unwrap();
+    let typo = metrics(800.0, -200.0, 0.0, LineMetricsSource::Typo);
+    assert_eq!(line_metrics(&font, Windows, &[]).unwrap(), typo);
+    assert_eq!(line_metrics(&font, Css, &[]).unwrap(), typo);
+    assert_eq!(
+        line_metrics(&font, Mac, &[]).unwrap().source,
+        LineMetricsSource::Hhea
+    );
+
+    // hhea が無ければ OS/2 の値を使う．
+    let data = common::FontBuilder::new()
+        .table(b"head", common::head(0))
+        .table(b"OS/2", common::os2())
+        .build();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(line_metrics(&font, Mac, &[]).unwrap(), typo);
+    assert_eq!(line_metrics(&font, Windows, &[]).unwrap().line_gap, 0.0);
+
+    // どちらも無ければエラーにする．
+    let data = common::FontBuilder::new()
+        .table(b"head", common::head(0))
+        .build();
+    let font = Font::new(&data).unwrap();
+    let err = line_metrics(&font, Css, &[]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::MissingTable);
+    assert_eq!(err.tag, Some(HHEA));
+}
+
+#[test]
+fn variations() {
+    let data = common::test_font();
+    let font = Font::new(&data).unwrap();
+    let fvar = font.table().get_fvar_table().unwrap();
+    let wght = Tag::from_be_bytes(*b"wght");
+    assert_eq!(fvar.normalize_coordinates(&[(wght, 900.0)]), [1.0]);
+    assert_eq!(fvar.normalize_coordinates(&[(wght, 250.0)]), [-0.5]);
+    assert_eq!(fvar.normalize_coordinates(&[(wght, 2000.0)]), [1.0]);
+    assert_eq!(fvar.normalize_coordinates(&[]), [0.0]);
+
+    // hhea には hasc, hdsc を足す．
+    let coords = fvar.normalize_coordinates(&[(wght, 900.0)]);
+    assert_eq!(
+        line_metrics(&font, Css, &coords).unwrap(),
+        metrics(900.0, -220.0, 90.0, LineMetricsSource::Hhea)
+    );
+    // usWin* には hcla, hcld を足す．hcld は無いので descender は変わらない．
+    assert_eq!(
+        line_metrics(&font, Windows, &coords).unwrap(),
+        metrics(1200.0, -200.0, 0.0, LineMetricsSource::Win)
+    );
+    let coords = fvar.normalize_coordinates(&[(wght, 100.0)]);
+    assert_eq!(
+        line_metrics(&font, Mac, &coords).unwrap(),
+        metrics(750.0, -190.0, 90.0, LineMetricsSource::Hhea)
+    );
+
+    // usWin* の line gap には，hhea の差分と usWin* の差分の両方が反映される．
+    let mut os2 = common::os2();
+    os2[74..76].copy_from_slice(&700u16.to_be_bytes()); // usWinAscent
+    os2[76..78].copy_from_slice(&150u16.to_be_bytes()); // usWinDescent
+    let data = common::FontBuilder::new()
+        .table(b"head", common::head(0))
+        .table(b"MVAR", common::mvar())
+        .table(b"OS/2", os2.clone())
+        .table(b"hhea", common::hhea())
+        .build();
+    let font = Font::new(&data).unwrap();
+    // 既定のインスタンスでは hhea の 1090 と usWin* の 850 の差が gap になる．
+    assert_eq!(
+        line_metrics(&font, Windows, &[]).unwrap(),
+        metrics(700.0, -150.0, 240.0, LineMetricsSource::Win)
+    );
+    // hhea は 900, -220, 90 で 1210，usWin* は 900, 150 で 1050 になる．
+    let windows = line_metrics(&font, Windows, &[1.0]).unwrap();
+    assert_eq!(
+        windows,
+        metrics(900.0, -150.0, 160.0, LineMetricsSource::Win)
+    );
+    assert_eq!(
+        windows.line_height(),
+        line_metrics(&font, Mac, &[1.0]).unwrap().line_height()
+    );
+    // hhea が無ければ gap は 0 のままにする．
+    let data = common::FontBuilder::new()
+        .table(b"head", common::head(0))
+        .table(b"MVAR", common::mvar())
+        .table(b"OS/2", os2)
+        .build();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(
+        line_metrics(&font, Windows, &[1.0]).unwrap(),
+        metrics(900.0, -150.0, 0.0, LineMetricsSource::Win)
+    );
+
+    // MVAR が壊れている場合はエラーにする．
+    let data = common::FontBuilder::new()
+        .table(b"head", common::head(0))
+        .table(b"MVAR", common::mvar()[..20].to_vec())
+        .table(b"hhea", common::hhea())
+        .build();
+    let font = Font::new(&data).unwrap();
+    assert!(line_metrics(&font, Css, &[]).is_ok());
+    let err = line_metrics(&font, Css, &[1.0]).unwrap_err();
+    assert_eq!(err.tag, Some(MVAR));
+}
+
+#[test]
+fn scaled() {
+    let data = common::test_font();
+    let font = Font::new(&data).unwrap();
+    // 1000 units/em を 20px にする．
+    let scaled = scaled_line_metrics(&font, Css, &[], 20.0).unwrap();
+    assert_eq!(scaled, metrics(16.0, -4.0, 1.8, LineMetricsSource::Hhea));
+    assert!((scaled.line_height() - 21.8).abs() < 1e-9);
+}
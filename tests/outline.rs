@@ -0,0 +1,162 @@
+mod common;
+
+use font_decoder::{
+    glyf::{GlyphPoint, SimpleGlyphFlags},
+    outline::draw_points,
+    Font, OutlinePen,
+};
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    M(f64, f64),
+    L(f64, f64),
+    Q(f64, f64, f64, f64),
+    C(f64, f64, f64, f64, f64, f64),
+    Z,
+}
+
+use Command::*;
+
+#[derive(Default)]
+struct Recorder(Vec<Command>);
+
+impl OutlinePen for Recorder {
+    fn move_to(&mut self, x: f64, y: f64) {
+        self.0.push(M(x, y));
+    }
+    fn line_to(&mut self, x: f64, y: f64) {
+        self.0.push(L(x, y));
+    }
+    fn quad_to(&mut self, x1: f64, y1: f64, x: f64, y: f64) {
+        self.0.push(Q(x1, y1, x, y));
+    }
+    fn curve_to(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, x: f64, y: f64) {
+        self.0.push(C(x1, y1, x2, y2, x, y));
+    }
+    fn close(&mut self) {
+        self.0.push(Z);
+    }
+}
+
+// (x, y, on-curve か) の並びを 1 つの輪郭にする．
+fn contour(points: &[(f64, f64, bool)]) -> Vec<GlyphPoint> {
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, &(x, y, on_curve))| GlyphPoint {
+            x,
+            y,
+            flags: SimpleGlyphFlags(on_curve as u8),
+            is_last: i + 1 == points.len(),
+        })
+        .collect()
+}
+
+fn draw(points: &[GlyphPoint]) -> Vec<Command> {
+    let mut pen = Recorder::default();
+    draw_points(points, &mut pen);
+    pen.0
+}
+
+#[test]
+fn on_curve_points_are_lines() {
+    let points = contour(&[(0.0, 0.0, true), (100.0, 0.0, true), (100.0, 100.0, true)]);
+    assert_eq!(
+        draw(&points),
+        [M(0.0, 0.0), L(100.0, 0.0), L(100.0, 100.0), Z]
+    );
+}
+
+#[test]
+fn implied_on_curve_points() {
+    let points = contour(&[
+        (0.0, 0.0, true),
+        (100.0, 0.0, false),
+        (100.0, 100.0, false),
+        (0.0, 100.0, true),
+    ]);
+    assert_eq!(
+        draw(&points),
+        [
+            M(0.0, 0.0),
+            Q(100.0, 0.0, 100.0, 50.0),
+            Q(100.0, 100.0, 0.0, 100.0),
+            Z
+        ]
+    );
+
+    // 末尾の off-curve 点は始点へ戻る曲線になる．
+    let points = contour(&[(0.0, 0.0, true), (100.0, 0.0, true), (100.0, 100.0, false)]);
+    assert_eq!(
+        draw(&points),
+        [M(0.0, 0.0), L(100.0, 0.0), Q(100.0, 100.0, 0.0, 0.0), Z]
+    );
+}
+
+#[test]
+fn contours_starting_off_curve() {
+    // 末尾の on-curve 点から始める．
+    let points = contour(&[(100.0, 0.0, false), (100.0, 100.0, true), (0.0, 0.0, true)]);
+    assert_eq!(draw(&points), [M(0.0, 0.0), Q(100.0, 0.0, 100.0, 100.0), Z]);
+
+    // すべて off-curve の場合は，先頭と末尾の中点から始める．
+    let points = contour(&[
+        (0.0, 0.0, false),
+        (100.0, 0.0, false),
+        (100.0, 100.0, false),
+        (0.0, 100.0, false),
+    ]);
+    assert_eq!(
+        draw(&points),
+        [
+            M(0.0, 50.0),
+            Q(0.0, 0.0, 50.0, 0.0),
+            Q(100.0, 0.0, 100.0, 50.0),
+            Q(100.0, 100.0, 50.0, 100.0),
+            Q(0.0, 100.0, 0.0, 50.0),
+            Z
+        ]
+    );
+}
+
+#[test]
+fn multiple_contours() {
+    let mut points = contour(&[(0.0, 0.0, true), (10.0, 0.0, true)]);
+    points.extend(contour(&[(20.0, 0.0, true), (30.0, 0.0, true)]));
+    // 最後の輪郭に is_last が無くても描く．
+    points.last_mut().unwrap().is_last = false;
+    assert_eq!(
+        draw(&points),
+        [M(0.0, 0.0), L(10.0, 0.0), Z, M(20.0, 0.0), L(30.0, 0.0), Z]
+    );
+    assert!(draw(&[]).is_empty());
+}
+
+#[test]
+fn outline_glyph() {
+    let data = common::test_font();
+    let font = Font::new(&data).unwrap();
+    let mut pen = Recorder::default();
+    font.outline_glyph(1, &mut pen).unwrap();
+    assert_eq!(
+        pen.0,
+        [
+            M(0.0, 0.0),
+            L(500.0, 0.0),
+            L(500.0, 500.0),
+            L(0.0, 500.0),
+            Z
+        ]
+    );
+
+    // composite glyph はコンポーネントごとに輪郭になる．
+    let mut pen = Recorder::default();
+    font.outline_glyph(2, &mut pen).unwrap();
+    assert_eq!(pen.0.iter().filter(|command| **command == Z).count(), 2);
+    assert_eq!(pen.0[5], M(100.0, 0.0));
+
+    let mut pen = Recorder::default();
+    font.outline_glyph(0, &mut pen).unwrap();
+    assert!(pen.0.is_empty());
+    assert!(font.outline_glyph(3, &mut pen).is_err());
+}
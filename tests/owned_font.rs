@@ -0,0 +1,122 @@
+mod common;
+
+use std::{sync::Arc, thread};
+
+use font_decoder::{data_types::HEAD, OwnedFont};
+
+#[test]
+fn owned_font_outlives_buffer_scope() {
+    let owned = {
+        let data = common::test_font();
+        OwnedFont::new(data, 0).unwrap()
+    };
+    let font = owned.font();
+    assert_eq!(font.glyph_index('A'), Some(1));
+    assert!(owned.face(1).is_err());
+}
+
+#[test]
+fn shared_between_threads() {
+    let data: Arc<[u8]> = common::test_font().into();
+    let owned = OwnedFont::new(data.clone(), 0).unwrap();
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let owned = owned.clone();
+            thread::spawn(move || owned.font().units_per_em().unwrap())
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), 1000);
+    }
+    // バイト列はコピーされない．
+    assert_eq!(owned.data().as_ptr(), data.as_ptr());
+}
+
+#[test]
+fn collection_faces_share_buffer() {
+    let faces: Vec<_> = OwnedFont::all_faces(common::test_collection())
+        .unwrap()
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+    assert!(faces.len() > 1);
+    for (index, face) in faces.iter().enumerate() {
+        assert_eq!(face.index(), index);
+        assert_eq!(face.data().as_ptr(), faces[0].data().as_ptr());
+        assert!(face.table().get_head_table().is_ok());
+        assert!(face.table().directory_offset() > 0);
+    }
+    assert!(OwnedFont::new(common::test_collection(), faces.len()).is_err());
+
+    // 壊れた face があっても，残りの face は読める．
+    let mut data = common::test_collection();
+    data[16..20].copy_from_slice(&u32::MAX.to_be_bytes());
+    let faces = OwnedFont::all_faces(data).unwrap();
+    assert_eq!(faces.len(), 2);
+    assert!(faces[0].is_ok());
+    assert!(faces[1].is_err());
+}
+
+#[test]
+fn owned_table_accessors() {
+    let owned = OwnedFont::new(common::test_font(), 0).unwrap();
+    assert_eq!(owned.units_per_em().unwrap(), 1000);
+    assert_eq!(owned.number_of_glyphs().unwrap(), common::NUM_GLYPHS);
+    assert_eq!(owned.hhea().unwrap().ascender, 800);
+    assert_eq!(owned.os2().unwrap().sTypoAscender, Some(800));
+    assert_eq!(
+        owned.table_data(&HEAD).unwrap().len(),
+        common::head(0).len()
+    );
+    assert!(owned.family_name().is_some());
+
+    // Font とパースした結果は clone した OwnedFont の間で共有する．
+    let cloned = owned.clone();
+    assert!(std::ptr::eq(owned.font(), cloned.font()));
+    assert!(std::ptr::eq(owned.head().unwrap(), cloned.head().unwrap()));
+    assert!(std::ptr::eq(
+        owned.font().cmap().unwrap(),
+        cloned.font().cmap().unwrap()
+    ));
+
+    // 別のスレッドに移しても，借用無しで値を取り出せる．
+    let ascender = thread::spawn(move || cloned.hhea().unwrap().ascender)
+        .join()
+        .unwrap();
+    assert_eq!(ascender, 800);
+}
+
+#[cfg(feature = "memmap2")]
+#[test]
+fn memory_mapped() {
+    let path = std::env::temp_dir().join(format!("owned_font_{}.ttf", std::process::id()));
+    std::fs::write(&path, common::test_font()).unwrap();
+    let owned = OwnedFont::from_path(&path, 0).unwrap();
+    assert_eq!(owned.glyph_index('A'), Some(1));
+    assert!(OwnedFont::from_path(&path, 1).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn glyph_accessors() {
+    let owned = OwnedFont::new(common::test_font(), 0).unwrap();
+    assert_eq!(owned.glyph_index('A'), Some(1));
+    assert_eq!(owned.glyph_name(1), Some("A"));
+    assert_eq!(owned.glyph_by_name("A"), Some(1));
+    assert_eq!(owned.advance_width(1).unwrap(), 600);
+    assert_eq!(owned.glyph_h_metrics(2).unwrap().advanceWidth, 1000);
+    assert_eq!(owned.glyph_v_metrics(1).unwrap().advance_height, 1000);
+    assert_eq!(owned.kerning(1, 2), -100);
+    let (points, bbox) = owned.outline(1).unwrap();
+    assert_eq!(points.len(), 4);
+    assert_eq!(owned.control_box(1).unwrap(), Some(bbox));
+    assert_eq!(owned.tight_bbox(1).unwrap(), Some(bbox));
+    assert!(owned.outline(3).is_err());
+
+    // 別のスレッドでも，同じキャッシュを使って glyph を引ける．
+    let cloned = owned.clone();
+    let glyph_id = thread::spawn(move || cloned.glyph_index('b'))
+        .join()
+        .unwrap();
+    assert_eq!(glyph_id, Some(2));
+}
@@ -0,0 +1,116 @@
+mod common;
+
+use common::push_u16;
+use font_decoder::{
+    post::{PostGlyphNames, PostTable, MAC_GLYPH_NAMES},
+    ErrorKind, Font,
+};
+
+#[test]
+fn header() {
+    let data = common::post();
+    let post = PostTable::parse(&data).unwrap();
+    assert_eq!(post.version.0, 0x00020000);
+    assert_eq!(post.italic_angle(), -11.5);
+    assert_eq!(post.underlinePosition, -100);
+    assert_eq!(post.underlineThickness, 50);
+    assert!(post.is_fixed_pitch());
+
+    let err = PostTable::parse(&data[..31]).err().unwrap();
+    assert_eq!(err.kind, ErrorKind::UnexpectedEof);
+    assert_eq!(err.field, Some("maxMemType1"));
+    let err = PostTable::parse(&common::post_header(0x00050000))
+        .err()
+        .unwrap();
+    assert_eq!(err.kind, ErrorKind::UnsupportedFormat(0x00050000));
+}
+
+#[test]
+fn format1() {
+    let data = common::post_header(0x00010000);
+    let post = PostTable::parse(&data).unwrap();
+    assert!(matches!(post.glyphNames, PostGlyphNames::Standard));
+    assert_eq!(post.len(), 258);
+    assert_eq!(post.glyph_name(0), Some(".notdef"));
+    assert_eq!(post.glyph_name(36), Some("A"));
+    assert_eq!(post.glyph_name(257), Some("dcroat"));
+    assert_eq!(post.glyph_name(258), None);
+    assert_eq!(post.glyph_by_name("space"), Some(3));
+    assert_eq!(post.glyph_by_name("uni3042"), None);
+}
+
+#[test]
+fn format2() {
+    let data = common::post();
+    let post = PostTable::parse(&data).unwrap();
+    assert_eq!(post.len(), 4);
+    assert_eq!(post.glyph_name(0), Some(".notdef"));
+    assert_eq!(post.glyph_name(1), Some("A"));
+    assert_eq!(post.glyph_name(2), Some("composite"));
+    assert_eq!(post.glyph_name(3), Some("cyclic"));
+    assert_eq!(post.glyph_name(4), None);
+    assert_eq!(post.glyph_by_name("cyclic"), Some(3));
+    assert_eq!(post.glyph_by_name("B"), None);
+
+    // 存在しない文字列を指す index と ASCII でない名前は None にする．
+    let mut data = common::post_header(0x00020000);
+    push_u16(&mut data, 2);
+    push_u16(&mut data, 258);
+    push_u16(&mut data, 300);
+    data.extend_from_slice(&[2, 0xC3, 0xA9]);
+    let post = PostTable::parse(&data).unwrap();
+    assert_eq!(post.glyph_name(0), None);
+    assert_eq!(post.glyph_name(1), None);
+
+    // 途中で切れた文字列は無視する．
+    let mut data = common::post();
+    data.truncate(data.len() - 2);
+    let post = PostTable::parse(&data).unwrap();
+    assert_eq!(post.glyph_name(2), Some("composite"));
+    assert_eq!(post.glyph_name(3), None);
+}
+
+#[test]
+fn format2_5_and_3() {
+    let mut data = common::post_header(0x00025000);
+    push_u16(&mut data, 3);
+    // glyph 0 は 3 (space)，glyph 1 は 36 (A)，glyph 2 は範囲外．
+    data.extend_from_slice(&[3, 35, (-3i8) as u8]);
+    let post = PostTable::parse(&data).unwrap();
+    assert_eq!(post.glyph_name(0), Some("space"));
+    assert_eq!(post.glyph_name(1), Some("A"));
+    assert_eq!(post.glyph_name(2), None);
+    assert_eq!(post.glyph_by_name("A"), Some(1));
+
+    let data = common::post_header(0x00030000);
+    let post = PostTable::parse(&data).unwrap();
+    assert!(post.is_empty());
+    assert_eq!(post.glyph_name(0), None);
+    assert_eq!(post.glyph_by_name(".notdef"), None);
+    assert_eq!(MAC_GLYPH_NAMES.len(), 258);
+}
+
+#[test]
+fn font_glyph_names() {
+    let data = common::test_font();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(font.post().unwrap().underlineThickness, 50);
+    assert_eq!(font.glyph_name(1), Some("A"));
+    assert_eq!(font.glyph_by_name("composite"), Some(2));
+
+    // format 1 でも maxp の glyph 数を超える名前は返さない．
+    let data = common::FontBuilder::new()
+        .table(b"maxp", common::maxp())
+        .table(b"post", common::post_header(0x00010000))
+        .build();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(font.glyph_name(3), Some("space"));
+    assert_eq!(font.glyph_name(4), None);
+    assert_eq!(font.glyph_by_name("A"), None);
+
+    let data = common::FontBuilder::new()
+        .table(b"maxp", common::maxp())
+        .build();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(font.glyph_name(0), None);
+}
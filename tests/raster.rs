@@ -0,0 +1,129 @@
+mod common;
+
+use font_decoder::{
+    raster::{rasterize_glyph, Bitmap, Rasterizer},
+    Font,
+};
+
+fn coverage_sum(alpha: &[u8]) -> f64 {
+    alpha.iter().map(|&a| f64::from(a) / 255.0).sum()
+}
+
+#[test]
+fn rasterizer_coverage() {
+    // 画素の格子に沿った正方形は，中だけが埋まる．
+    let mut rasterizer = Rasterizer::new(4, 4);
+    let square = [(1.0, 1.0), (3.0, 1.0), (3.0, 3.0), (1.0, 3.0), (1.0, 1.0)];
+    for line in square.windows(2) {
+        rasterizer.draw_line(line[0], line[1]);
+    }
+    assert_eq!(
+        rasterizer.into_alpha(),
+        [0, 0, 0, 0, 0, 255, 255, 0, 0, 255, 255, 0, 0, 0, 0, 0]
+    );
+
+    // 斜めの辺を持つ三角形の面積は 8 画素分になる．
+    let mut rasterizer = Rasterizer::new(4, 4);
+    let triangle = [(0.0, 0.0), (4.0, 4.0), (0.0, 4.0), (0.0, 0.0)];
+    for line in triangle.windows(2) {
+        rasterizer.draw_line(line[0], line[1]);
+    }
+    let alpha = rasterizer.into_alpha();
+    assert!((coverage_sum(&alpha) - 8.0).abs() < 0.05);
+    // 対角線上の画素は半分だけ覆われる．
+    assert_eq!(alpha[0], 128);
+    assert_eq!(alpha[4], 255);
+
+    // 円に近い曲線も線分に分けた誤差の範囲で面積が合う．
+    let mut rasterizer = Rasterizer::new(20, 20);
+    let (c, r) = (10.0, 8.0);
+    let k = 0.5523 * r;
+    rasterizer.draw_cubic((c + r, c), (c + r, c + k), (c + k, c + r), (c, c + r));
+    rasterizer.draw_cubic((c, c + r), (c - k, c + r), (c - r, c + k), (c - r, c));
+    rasterizer.draw_cubic((c - r, c), (c - r, c - k), (c - k, c - r), (c, c - r));
+    rasterizer.draw_cubic((c, c - r), (c + k, c - r), (c + r, c - k), (c + r, c));
+    let area = coverage_sum(&rasterizer.into_alpha());
+    assert!((area / (std::f64::consts::PI * 64.0) - 1.0).abs() < 0.02);
+}
+
+#[test]
+fn rasterize_glyphs() {
+    let data = common::test_font();
+    let font = Font::new(&data).unwrap();
+
+    // unitsPerEm 1000 で 20px なら，500 単位の正方形は 10px になる．
+    let bitmap = rasterize_glyph(&font, 1, 20.0, (0.0, 0.0)).unwrap();
+    assert_eq!((bitmap.width, bitmap.height), (10, 10));
+    assert_eq!((bitmap.left, bitmap.top), (0, 10));
+    assert!(bitmap.data.iter().all(|&a| a == 255));
+
+    // 半画素ずらすと両端の列が半分の濃さになる．
+    let bitmap = rasterize_glyph(&font, 1, 20.0, (0.5, 0.0)).unwrap();
+    assert_eq!((bitmap.width, bitmap.height), (11, 10));
+    assert_eq!(bitmap.get(0, 0), Some(128));
+    assert_eq!(bitmap.get(5, 0), Some(255));
+    assert_eq!(bitmap.get(10, 9), Some(128));
+    assert_eq!(bitmap.get(11, 0), None);
+
+    // 上にずらすと上端が 1 行増える．
+    let bitmap = rasterize_glyph(&font, 1, 20.0, (0.0, 0.25)).unwrap();
+    assert_eq!((bitmap.height, bitmap.top), (11, 11));
+    assert_eq!(bitmap.get(0, 0), Some(64));
+
+    // 重なった輪郭は 255 で打ち切る．
+    let bitmap = rasterize_glyph(&font, 2, 20.0, (0.0, 0.0)).unwrap();
+    assert!(bitmap.data.iter().all(|&a| a == 255));
+
+    assert_eq!(
+        rasterize_glyph(&font, 0, 20.0, (0.0, 0.0)).unwrap(),
+        Bitmap::default()
+    );
+    assert!(rasterize_glyph(&font, 3, 20.0, (0.0, 0.0)).is_err());
+}
+
+#[test]
+fn huge_bitmaps_are_errors() {
+    // 幅と高さはそれぞれ上限の 16384px に収まるが，画素数が 4M を超える．
+    let glyphs = vec![
+        Vec::new(),
+        common::simple_glyph(&[(0, 0), (0, 10000), (10000, 10000), (10000, 0)]),
+        common::simple_glyph(&[(0, 0), (0, 10), (16000, 10), (16000, 0)]),
+    ];
+    let data = common::font_with_glyphs(&glyphs, 1).build();
+    let font = Font::new(&data).unwrap();
+    let err = rasterize_glyph(&font, 1, 1000.0, (0.0, 0.0)).unwrap_err();
+    assert_eq!(err.field, Some("pixelSize"));
+    assert!(rasterize_glyph(&font, 1, 200.0, (0.0, 0.0)).is_ok());
+    // 細長い glyph は画素数が少なければ描ける．
+    let bitmap = rasterize_glyph(&font, 2, 1000.0, (0.0, 0.0)).unwrap();
+    assert_eq!((bitmap.width, bitmap.height), (16000, 10));
+    assert!(rasterize_glyph(&font, 2, 2000.0, (0.0, 0.0)).is_err());
+}
+
+#[test]
+fn image_files() {
+    let bitmap = Bitmap {
+        width: 2,
+        height: 1,
+        left: 0,
+        top: 1,
+        data: vec![0, 255],
+    };
+
+    let mut pgm = vec![];
+    bitmap.write_pgm(&mut pgm).unwrap();
+    assert_eq!(pgm, b"P5\n2 1\n255\n\xff\x00");
+
+    let mut png = vec![];
+    bitmap.write_png(&mut png).unwrap();
+    assert!(png.starts_with(
+        b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR\x00\x00\x00\x02\x00\x00\x00\x01\x08\x04"
+    ));
+    // IDAT は zlib ヘッダ，無圧縮ブロック (フィルタ 1 バイトと 2 画素)，Adler-32．
+    let idat = png.windows(4).position(|w| w == b"IDAT").unwrap();
+    assert_eq!(
+        &png[idat + 4..idat + 4 + 16],
+        [0x78, 0x01, 1, 5, 0, 0xFA, 0xFF, 0, 0, 0, 0, 255, 0x01, 0x04, 0x01, 0x00]
+    );
+    assert!(png.ends_with(b"\x00\x00\x00\x00IEND\xae\x42\x60\x82"));
+}
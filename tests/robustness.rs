@@ -0,0 +1,332 @@
+// 壊れたフォントを与えても panic しないことを確かめる．
+mod common;
+
+use font_decoder::{
+    bbox::verify_bboxes,
+    glyf::Glyph,
+    id::NameID,
+    line_metrics::{scaled_line_metrics, LineMetricsConvention},
+    table::Table,
+    text::render_text,
+    Font, FontFile,
+};
+
+// 公開 API を一通り呼び出す．エラーになるのは構わないが，panic してはならない．
+fn exercise_table(table: &Table) {
+    let _ = table.verify_checksums().is_valid();
+    for issue in table.validate_directory() {
+        let _ = issue.to_string();
+    }
+
+    if let Ok(name) = table.get_name_table() {
+        for item in &name {
+            let _ = format!("{:?}", item);
+        }
+        for record in &name.nameRecords {
+            let _ = record.to_string();
+            let _ = name.get_string(&record);
+        }
+        for name_id in 0..=25 {
+            let _ = name.get_strings_by_name_id(NameID(name_id));
+        }
+    }
+
+    if let Ok(cmap) = table.get_cmap_table() {
+        for record in &cmap.header.encodingRecords {
+            if let Ok(subtable) = cmap.get_subtable(record) {
+                for c in [
+                    '\0',
+                    'A',
+                    'B',
+                    'C',
+                    'a',
+                    'b',
+                    'z',
+                    '\u{FFFF}',
+                    '\u{20000}',
+                    '\u{10FFFF}',
+                ] {
+                    let _ = subtable.get_glyph_id(c);
+                    let _ = subtable.get_variant_glyph_id(c, '\u{FE00}');
+                }
+                let _ = subtable.get_code_point_glyph_id_map();
+            }
+        }
+    }
+
+    if let Ok(os2) = table.get_os2_table() {
+        let _ = format!("{:?}", os2.get_weight());
+        let _ = os2.is_regular();
+    }
+
+    let maxp = table.get_maxp_table();
+    if let (Ok(hhea), Ok(maxp)) = (table.get_hhea_table(), &maxp) {
+        let num_glyphs = maxp.get_number_of_glyphs();
+        if let Ok(hmtx) = table.get_hmtx_table(hhea.numberOfHMetrics, num_glyphs) {
+            for glyph_id in 0..=num_glyphs {
+                let _ = hmtx.glyph_h_metrics(glyph_id);
+            }
+        }
+    }
+    if let Ok(head) = table.get_head_table() {
+        if let (Ok(format), Ok(maxp)) = (head.get_loca_offset_format(), maxp) {
+            let num_glyphs = maxp.get_number_of_glyphs();
+            if let (Ok(loca), Ok(glyf)) = (
+                table.get_loca_table(format, num_glyphs),
+                table.get_glyf_table(),
+            ) {
+                for i in 0..loca.len() {
+                    let _ = loca.at(i);
+                }
+                for glyph_id in 0..=num_glyphs {
+                    let Some(data) = loca
+                        .get_glyf_range(glyph_id)
+                        .and_then(|range| glyf.get_data(range))
+                    else {
+                        continue;
+                    };
+                    if let Ok(glyph) = Glyph::parse(data) {
+                        let _ = glyph.get_points(&loca, &glyf);
+                    }
+                }
+            }
+        }
+    }
+
+    if let Ok(gsub) = table.get_gsub_table() {
+        for i in 0..gsub.script_list.scriptRecords.len() {
+            if let Ok(script) = gsub.script_list.get(i) {
+                let _ = script.get_default_lang_sys_table();
+                for j in 0..script.langSysRecords.len() {
+                    let _ = script.get(j);
+                }
+            }
+        }
+        for i in 0..gsub.feature_list.featureRecords.len() {
+            let _ = gsub.feature_list.get(i);
+        }
+        for i in 0..gsub.lookup_list.lookupOffsets.len() {
+            let _ = gsub.lookup_list.get(i);
+        }
+    }
+
+    if let Ok(fvar) = table.get_fvar_table() {
+        let _ = format!("{:?} {:?}", fvar.axes, fvar.instances);
+    }
+
+    if let Ok(mvar) = table.get_mvar_table() {
+        for coord in [-1.0, -0.3, 0.0, 0.5, 1.0] {
+            for record in &mvar.valueRecords {
+                let _ = mvar.get_delta(record.valueTag, &[coord, coord]);
+            }
+        }
+    }
+
+    if let Ok(kern) = table.get_kern_table() {
+        for subtable in &kern.subtables {
+            let _ = subtable.pairs.iter().count();
+        }
+        let _ = kern.get_kerning(1, 2);
+    }
+
+    if let Ok(post) = table.get_post_table() {
+        for glyph_id in 0..=post.len().min(300) as u16 {
+            let _ = post.glyph_name(glyph_id);
+        }
+        let _ = post.glyph_by_name("A");
+    }
+
+    if let Ok(stat) = table.get_stat_table() {
+        for value in stat.get_axis_value_table_iter().flatten() {
+            let _ = value.get_value_name_id();
+            let _ = value.get_axis_indices();
+        }
+    }
+}
+
+fn exercise_font(font: &Font) {
+    let _ = font.family_name();
+    for c in ['A', 'b', 'z'] {
+        let _ = font.glyph_index(c);
+    }
+    if let Some(charmap) = font.charmap() {
+        let _ = charmap.map_with_selector('B', '\u{FE00}');
+        // build_reverse は charmap.iter() をすべてたどる．
+        let reverse = charmap.build_reverse();
+        let _ = reverse.get_variants(3).count();
+    }
+    let num_glyphs = font.number_of_glyphs().unwrap_or(0);
+    for glyph_id in 0..=num_glyphs {
+        let _ = font.outline(glyph_id);
+        let _ = font.advance_width(glyph_id);
+        let _ = font.glyph_h_metrics(glyph_id);
+        let _ = font.glyph_v_metrics(glyph_id);
+        let _ = font.glyph_name(glyph_id);
+        let _ = font.control_box(glyph_id);
+        let _ = font.tight_bbox(glyph_id);
+    }
+    let _ = verify_bboxes(font);
+    for convention in [
+        LineMetricsConvention::Windows,
+        LineMetricsConvention::Mac,
+        LineMetricsConvention::Css,
+    ] {
+        let _ = scaled_line_metrics(font, convention, &[1.0], 16.0);
+    }
+    let _ = render_text(font, "Ab\nCz", 8.0);
+}
+
+fn exercise(data: &[u8]) {
+    let Ok(font_file) = FontFile::from_bytes(data) else {
+        return;
+    };
+    if let FontFile::Collection(collection) = &font_file {
+        let _ = collection.get_dsig_data();
+    }
+    for (_, table) in &font_file {
+        if let Ok(table) = table {
+            exercise_table(&table);
+            exercise_font(&Font::from_table(table));
+        }
+    }
+}
+
+// 再現性のある疑似乱数．
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+fn corpus() -> Vec<Vec<u8>> {
+    vec![
+        common::build_font(0),
+        common::build_font(1),
+        common::test_collection(),
+        common::build_collection(&[common::test_font()], Some(b"dsig")),
+    ]
+}
+
+#[test]
+fn valid_fonts_parse() {
+    let font = common::test_font();
+    let table = Table::new(&font).unwrap();
+    assert!(table.get_name_table().is_ok());
+    assert!(table.get_cmap_table().is_ok());
+    assert!(table.get_os2_table().is_ok());
+    assert!(table.get_head_table().is_ok());
+    assert!(table.get_maxp_table().is_ok());
+    assert!(table.get_hhea_table().is_ok());
+    assert!(table.get_gsub_table().is_ok());
+    assert!(table.get_fvar_table().is_ok());
+    assert!(table.get_stat_table().is_ok());
+
+    let cmap = table.get_cmap_table().unwrap();
+    let subtable = cmap.get_subtable(&cmap.header.encodingRecords[1]).unwrap();
+    assert_eq!(subtable.get_glyph_id('A'), Some(1));
+    assert_eq!(subtable.get_glyph_id('b'), Some(2));
+    assert_eq!(subtable.get_glyph_id('z'), Some(0));
+
+    for data in corpus() {
+        exercise(&data);
+    }
+}
+
+#[test]
+fn missing_tables_are_errors() {
+    let font = common::FontBuilder::new()
+        .table(b"head", common::head(0))
+        .build();
+    let table = Table::new(&font).unwrap();
+    assert!(table.get_name_table().is_err());
+    assert!(table.get_cmap_table().is_err());
+    assert!(table.get_os2_table().is_err());
+    assert!(table.get_maxp_table().is_err());
+    assert!(table.get_head_table().is_ok());
+}
+
+#[test]
+fn out_of_range_values_are_errors() {
+    let font = common::FontBuilder::new()
+        .table(b"head", common::head(7))
+        .build();
+    let table = Table::new(&font).unwrap();
+    let head = table.get_head_table().unwrap();
+    assert!(head.get_loca_offset_format().is_err());
+}
+
+#[test]
+fn cyclic_composite_is_error() {
+    let font = common::test_font();
+    let table = Table::new(&font).unwrap();
+    let head = table.get_head_table().unwrap();
+    let maxp = table.get_maxp_table().unwrap();
+    let loca = table
+        .get_loca_table(
+            head.get_loca_offset_format().unwrap(),
+            maxp.get_number_of_glyphs(),
+        )
+        .unwrap();
+    let glyf = table.get_glyf_table().unwrap();
+    let glyph = |glyph_id| {
+        let data = glyf
+            .get_data(loca.get_glyf_range(glyph_id).unwrap())
+            .unwrap();
+        Glyph::parse(data).unwrap()
+    };
+    let (points, _) = glyph(2).get_points(&loca, &glyf).unwrap();
+    assert_eq!(points.len(), 8);
+    assert!(glyph(3).get_points(&loca, &glyf).is_err());
+}
+
+#[test]
+fn truncated_fonts_do_not_panic() {
+    for data in corpus() {
+        for len in 0..data.len() {
+            exercise(&data[..len]);
+        }
+    }
+}
+
+#[test]
+fn corrupted_fonts_do_not_panic() {
+    let mut rng = XorShift(0x2545F4914F6CDD1D);
+    for data in corpus() {
+        for _ in 0..1000 {
+            let mut data = data.clone();
+            for _ in 0..=rng.below(8) {
+                let at = rng.below(data.len());
+                data[at] = match rng.below(4) {
+                    0 => 0x00,
+                    1 => 0xFF,
+                    2 => data[at] ^ (1 << rng.below(8)),
+                    _ => rng.next() as u8,
+                };
+            }
+            exercise(&data);
+        }
+    }
+}
+
+#[test]
+fn every_byte_extreme_values_do_not_panic() {
+    // collection の各 face は単体のフォントと同じ内容なので，単体のフォントだけを調べる．
+    for data in [common::build_font(0), common::build_font(1)] {
+        for at in 0..data.len() {
+            for value in [0x00, 0x7F, 0x80, 0xFF] {
+                let mut data = data.clone();
+                data[at] = value;
+                exercise(&data);
+            }
+        }
+    }
+}
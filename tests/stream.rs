@@ -0,0 +1,99 @@
+use font_decoder::{
+    data_types::{Offset16, Offset32},
+    decoder::{
+        get_subtable_data, parse_optional_subtable, parse_subtable, FromData, LazyArray, Stream,
+    },
+    ErrorKind,
+};
+
+#[test]
+fn failed_reads_leave_cursor_untouched() {
+    let data = [0x00, 0x01, 0x02];
+    let mut s = Stream::new(&data);
+    assert_eq!(s.read::<u16>().unwrap(), 1);
+    assert!(s.read::<u16>().is_err());
+    assert_eq!(s.get_offset(), 2);
+    assert!(s.read_bytes(2).is_err());
+    assert_eq!(s.get_offset(), 2);
+    assert!(s.read_array::<u16>(1).is_err());
+    assert_eq!(s.get_offset(), 2);
+    assert_eq!(s.read::<u8>().unwrap(), 2);
+    assert!(s.is_end());
+}
+
+#[test]
+fn overflowing_lengths_are_errors() {
+    let data = [0; 8];
+    let mut s = Stream::new(&data);
+    s.set_offset(4);
+    assert!(s.read_bytes(usize::MAX).is_err());
+    assert!(s.read_array::<u32>(usize::MAX / 2).is_err());
+    assert!(s
+        .read_unsized_array(usize::MAX, usize::MAX, u8::parse)
+        .is_err());
+    assert_eq!(s.get_offset(), 4);
+}
+
+#[test]
+fn offset_past_end() {
+    let data = [0; 4];
+    let mut s = Stream::new(&data);
+    s.set_offset(10);
+    assert!(s.is_end());
+    assert!(s.read::<u8>().is_err());
+    assert!(s.read_all_array::<u16>().is_err());
+    assert!(s.get_tail().is_err());
+}
+
+#[test]
+fn error_offset_points_to_failed_element() {
+    let data = [0x00, 0x01, 0x00];
+    let mut s = Stream::new(&data);
+    let err = s.read::<u32>().unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnexpectedEof);
+    assert_eq!(err.offset, 0);
+}
+
+#[test]
+fn subtable_helpers() {
+    let data = [0x00, 0x04, 0x00, 0x00, 0x12, 0x34];
+    let offset: Offset16 = 4;
+    assert_eq!(get_subtable_data(&data, offset).unwrap(), &[0x12, 0x34]);
+    assert_eq!(parse_subtable(&data, offset, u16::parse).unwrap(), 0x1234);
+
+    let null: Offset32 = 0;
+    assert_eq!(
+        parse_optional_subtable(&data, null, u16::parse).unwrap(),
+        None
+    );
+
+    let out_of_range: Offset32 = 7;
+    let err = parse_subtable(&data, out_of_range, u16::parse).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnexpectedEof);
+    assert_eq!(err.offset, 7);
+
+    // サブテーブル内のエラーはテーブルの先頭からの位置になる．
+    let err = parse_subtable(&data, 5 as Offset16, u16::parse).unwrap_err();
+    assert_eq!(err.offset, data.len());
+}
+
+#[test]
+fn lazy_array() {
+    let data = [0x00, 0x01, 0x00, 0x03, 0x00, 0x05, 0xFF];
+    let mut s = Stream::new(&data);
+    let array: LazyArray<u16> = s.read_lazy_array(3).unwrap();
+    assert_eq!(s.get_offset(), 6);
+    assert_eq!(array.len(), 3);
+    assert_eq!(array.get(1), Some(3));
+    assert_eq!(array.get(3), None);
+    assert_eq!(array.get(usize::MAX), None);
+    assert_eq!(array.last(), Some(5));
+    assert_eq!(array.iter().collect::<Vec<_>>(), vec![1, 3, 5]);
+    assert_eq!(array.binary_search_by(|x| x.cmp(&5)), Some((2, 5)));
+    assert_eq!(array.binary_search_by(|x| x.cmp(&4)), None);
+
+    // 末尾の半端なバイトは要素にならない．
+    let array: LazyArray<u16> = LazyArray::new(&data);
+    assert_eq!(array.len(), 3);
+    assert!(Stream::new(&data).read_lazy_array::<u16>(4).is_err());
+}
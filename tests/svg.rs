@@ -0,0 +1,68 @@
+mod common;
+
+use font_decoder::{
+    svg::{glyph_path, glyph_sheet_svg, glyph_to_svg, SvgOptions, SvgPathPen},
+    Font, OutlinePen,
+};
+
+#[test]
+fn path_data() {
+    let data = common::test_font();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(glyph_path(&font, 1).unwrap(), "M0 0L500 0L500 500L0 500Z");
+    assert_eq!(
+        glyph_path(&font, 2).unwrap(),
+        "M0 0L500 0L500 500L0 500ZM100 0L350 0L350 250L100 250Z"
+    );
+    assert_eq!(glyph_path(&font, 0).unwrap(), "");
+    assert!(glyph_path(&font, 3).is_err());
+
+    let mut pen = SvgPathPen::new();
+    pen.move_to(0.0, 0.0);
+    pen.quad_to(10.0, 20.0, 30.5, -40.0);
+    pen.curve_to(1.0, 2.0, 3.0, 4.0, 5.0, 6.0);
+    pen.close();
+    assert_eq!(pen.as_str(), "M0 0Q10 20 30.5 -40C1 2 3 4 5 6Z");
+}
+
+#[test]
+fn document() {
+    let data = common::test_font();
+    let font = Font::new(&data).unwrap();
+
+    // hhea の ascender 800, descender -200 と advance width 600 に余白 50 を付けた範囲．
+    let svg = glyph_to_svg(&font, 1, &SvgOptions::default()).unwrap();
+    assert!(svg.starts_with(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="-50 -850 700 1100" width="700" height="1100">"#
+    ));
+    assert!(svg.contains(r#"<path d="M0 0L500 0L500 500L0 500Z""#));
+    assert!(!svg.contains("<circle"));
+    assert!(!svg.contains("<line"));
+    assert!(svg.ends_with("</svg>\n"));
+
+    let options = SvgOptions {
+        points: true,
+        bbox: true,
+        metrics: true,
+    };
+    let svg = glyph_to_svg(&font, 1, &options).unwrap();
+    assert_eq!(svg.matches(r#"class="on-curve""#).count(), 4);
+    assert_eq!(svg.matches(r#"class="advance""#).count(), 2);
+    assert!(svg.contains(r#"<line class="ascender" x1="-50" y1="800""#));
+    assert!(svg.contains(r#"<rect class="bbox" x="0" y="0" width="500" height="500""#));
+}
+
+#[test]
+fn sheet() {
+    let data = common::test_font();
+    let font = Font::new(&data).unwrap();
+    let svg = glyph_sheet_svg(&font, 0..4, 3, 100.0, &SvgOptions::default());
+    // 2 行 3 列で，各行の下にラベルの高さ 20 が付く．
+    assert!(svg.starts_with(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 300 240" width="300" height="240">"#
+    ));
+    // glyph 3 は循環しているので，マスとラベルだけになる．
+    assert_eq!(svg.matches(r#"<svg class="glyph""#).count(), 3);
+    assert_eq!(svg.matches("<text").count(), 4);
+    assert!(svg.contains(">3</text>"));
+}
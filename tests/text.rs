@@ -0,0 +1,115 @@
+mod common;
+
+use common::{push_i16, push_u16};
+use font_decoder::{
+    text::{layout_text, render_text, PositionedGlyph},
+    Font,
+};
+
+#[test]
+fn layout() {
+    let data = common::test_font();
+    let font = Font::new(&data).unwrap();
+
+    // a (glyph 1, advance 600) と b (glyph 2) の間に kern の -100 が入る．
+    // cmap に無い文字は .notdef (advance 500) になり，改行でベースラインが 1000 下がる．
+    let glyphs = layout_text(&font, "ab?\nba").unwrap();
+    let positions: Vec<_> = glyphs
+        .iter()
+        .map(|&PositionedGlyph { glyph_id, x, y, .. }| (glyph_id, x, y))
+        .collect();
+    assert_eq!(
+        positions,
+        [
+            (1, 0.0, 0.0),
+            (2, 500.0, 0.0),
+            (0, 1500.0, 0.0),
+            (2, 0.0, -1000.0),
+            (1, 1050.0, -1000.0),
+        ]
+    );
+    assert_eq!(glyphs[1].advance, 1000.0);
+    assert!(layout_text(&font, "").unwrap().is_empty());
+}
+
+#[test]
+fn layout_uses_component_metrics() {
+    // C (glyph 3) は USE_MY_METRICS で glyph 1 の advance 500 を使う．
+    let glyphs = vec![
+        Vec::new(),
+        common::simple_glyph(&[(0, 0), (0, 500), (500, 500), (500, 0)]),
+        common::simple_glyph(&[(0, 0), (100, 0), (0, 100)]),
+        common::composite_glyph(&[(
+            common::ARGS_ARE_XY_VALUES | common::USE_MY_METRICS,
+            1,
+            0,
+            0,
+            &[],
+        )]),
+    ];
+    let mut hhea = common::hhea();
+    hhea[34..36].copy_from_slice(&4u16.to_be_bytes()); // numberOfHMetrics
+    let mut hmtx = Vec::new();
+    for advance_width in [500, 500, 600, 900] {
+        push_u16(&mut hmtx, advance_width);
+        push_i16(&mut hmtx, 0);
+    }
+    let data = common::font_with_glyphs(&glyphs, 1)
+        .table(b"cmap", common::cmap())
+        .table(b"hhea", hhea)
+        .table(b"hmtx", hmtx)
+        .build();
+    let font = Font::new(&data).unwrap();
+    let glyphs = layout_text(&font, "CA").unwrap();
+    assert_eq!((glyphs[0].glyph_id, glyphs[0].advance), (3, 500.0));
+    assert_eq!((glyphs[1].glyph_id, glyphs[1].x), (1, 500.0));
+}
+
+#[test]
+fn render() {
+    let data = common::test_font();
+    let font = Font::new(&data).unwrap();
+
+    // 20px では ascender 800 が 16px，descender -200 が -4px，advance 600 が 12px になる．
+    let bitmap = render_text(&font, "a", 20.0).unwrap();
+    assert_eq!((bitmap.width, bitmap.height), (12, 20));
+    assert_eq!((bitmap.left, bitmap.top), (0, 16));
+    // 500 単位の正方形は左下から 10px 四方を埋める．
+    assert_eq!(bitmap.get(0, 6), Some(255));
+    assert_eq!(bitmap.get(9, 15), Some(255));
+    assert_eq!(bitmap.get(10, 15), Some(0));
+    assert_eq!(bitmap.get(0, 5), Some(0));
+    assert_eq!(bitmap.get(0, 16), Some(0));
+
+    // カーニングで b は 10px の位置に来る．b の advance 1000 で幅は 30px になる．
+    let bitmap = render_text(&font, "ab", 20.0).unwrap();
+    assert_eq!((bitmap.width, bitmap.height), (30, 20));
+    assert_eq!(bitmap.get(10, 15), Some(255));
+    assert_eq!(bitmap.get(19, 6), Some(255));
+    assert_eq!(bitmap.get(20, 15), Some(0));
+
+    // 2 行目は 20px 下に描く．
+    let bitmap = render_text(&font, "a\na", 20.0).unwrap();
+    assert_eq!(bitmap.height, 40);
+    assert_eq!(bitmap.get(0, 26), Some(255));
+
+    // 端数の位置はサブピクセルのずれになる．
+    let bitmap = render_text(&font, "a", 15.0).unwrap();
+    assert_eq!((bitmap.width, bitmap.height, bitmap.top), (9, 15, 12));
+    assert_eq!(bitmap.get(7, 11), Some(128));
+
+    // 循環する glyph はエラーになる．
+    assert!(render_text(&font, "C", 20.0).is_err());
+
+    // glyph ごとには収まっても，全体が上限の 16384px や 4M 画素を超える場合はエラーにする．
+    let text = "a".repeat(30);
+    assert!(render_text(&font, &text[..27], 100.0).is_ok());
+    let err = render_text(&font, &text, 1000.0).unwrap_err();
+    assert_eq!(err.field, Some("pixelSize"));
+    let text = "a\n".repeat(20);
+    assert!(render_text(&font, &text, 1000.0).is_err());
+    // 幅と高さはそれぞれ収まるが，画素数が 4M を超える．
+    let text = "aaaaaaaaaa\n".repeat(4);
+    assert!(render_text(&font, &text, 300.0).is_ok());
+    assert!(render_text(&font, &text, 1000.0).is_err());
+}
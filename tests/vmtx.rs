@@ -0,0 +1,202 @@
+mod common;
+
+use common::{push_i16, push_u16, push_u32};
+use font_decoder::{
+    vhea::VheaTable,
+    vmtx::{GlyphVMetrics, LongVerMetric, VmtxTable},
+    vorg::VorgTable,
+    ErrorKind, Font,
+};
+
+fn vhea(num_of_long_ver_metrics: u16) -> Vec<u8> {
+    let mut t = Vec::new();
+    push_u32(&mut t, 0x00011000); // version
+    push_i16(&mut t, 500); // vertTypoAscender
+    push_i16(&mut t, -500); // vertTypoDescender
+    push_i16(&mut t, 0); // vertTypoLineGap
+    push_u16(&mut t, 1000); // advanceHeightMax
+    push_i16(&mut t, 50); // minTopSideBearing
+    push_i16(&mut t, 0); // minBottomSideBearing
+    push_i16(&mut t, 950); // yMaxExtent
+    push_i16(&mut t, 0); // caretSlopeRise
+    push_i16(&mut t, 1); // caretSlopeRun
+    push_i16(&mut t, 0); // caretOffset
+    for _ in 0..4 {
+        push_i16(&mut t, 0); // reserved
+    }
+    push_i16(&mut t, 0); // metricDataFormat
+    push_u16(&mut t, num_of_long_ver_metrics);
+    t
+}
+
+// glyph 0, 1 は vMetrics を，glyph 2, 3 は topSideBearings だけを持つ．
+fn vmtx() -> Vec<u8> {
+    let mut t = Vec::new();
+    for (advance_height, tsb) in [(1000, 100), (900, 300)] {
+        push_u16(&mut t, advance_height);
+        push_i16(&mut t, tsb);
+    }
+    for tsb in [50, 60] {
+        push_i16(&mut t, tsb);
+    }
+    t
+}
+
+fn vorg() -> Vec<u8> {
+    let mut t = Vec::new();
+    push_u16(&mut t, 1); // majorVersion
+    push_u16(&mut t, 0); // minorVersion
+    push_i16(&mut t, 880); // defaultVertOriginY
+    push_u16(&mut t, 2); // numVertOriginYMetrics
+    for (glyph_index, vert_origin_y) in [(1, 900), (3, 870)] {
+        push_u16(&mut t, glyph_index);
+        push_i16(&mut t, vert_origin_y);
+    }
+    t
+}
+
+fn metric(advance_height: u16, top_side_bearing: i16) -> LongVerMetric {
+    LongVerMetric {
+        advanceHeight: advance_height,
+        topSideBearing: top_side_bearing,
+    }
+}
+
+#[test]
+fn tables() {
+    let table = VheaTable::parse(&vhea(2)).unwrap();
+    assert_eq!(table.version.0, 0x00011000);
+    assert_eq!(
+        (table.vertTypoAscender, table.vertTypoDescender),
+        (500, -500)
+    );
+    assert_eq!(table.advanceHeightMax, 1000);
+    assert_eq!(table.numOfLongVerMetrics, 2);
+    let err = VheaTable::parse(&vhea(2)[..35]).unwrap_err();
+    assert_eq!(err.field, Some("numOfLongVerMetrics"));
+
+    let data = vmtx();
+    let vmtx = VmtxTable::parse(&data, 2, 4).unwrap();
+    assert_eq!(vmtx.len(), 4);
+    assert_eq!(vmtx.glyph_v_metrics(0), Some(metric(1000, 100)));
+    // numOfLongVerMetrics 以降は最後の advanceHeight を共有する．
+    assert_eq!(vmtx.glyph_v_metrics(3), Some(metric(900, 60)));
+    assert_eq!(vmtx.glyph_v_metrics(4), None);
+    // topSideBearings が足りない場合も，advance height は引ける．無い tsb は 0 になる．
+    let vmtx = VmtxTable::parse(&data, 2, 5).unwrap();
+    assert_eq!(vmtx.len(), 5);
+    assert_eq!(vmtx.glyph_v_metrics(3), Some(metric(900, 60)));
+    assert_eq!(vmtx.glyph_v_metrics(4), Some(metric(900, 0)));
+    assert_eq!(vmtx.glyph_v_metrics(5), None);
+    let err = VmtxTable::parse(&data[..6], 2, 4).unwrap_err();
+    assert_eq!(err.field, Some("vMetrics"));
+
+    let data = vorg();
+    let vorg = VorgTable::parse(&data).unwrap();
+    assert_eq!(vorg.numVertOriginYMetrics, 2);
+    assert_eq!(vorg.get_vert_origin_y(1), 900);
+    assert_eq!(vorg.get_vert_origin_y(3), 870);
+    assert_eq!(vorg.get_vert_origin_y(2), 880);
+    let err = VorgTable::parse(&[0, 2, 0, 0, 0, 0, 0, 0]).unwrap_err();
+    assert_eq!(err.kind, ErrorKind::UnsupportedFormat(2));
+}
+
+fn v_metrics(advance_height: u16, top_side_bearing: i16, vert_origin_y: i16) -> GlyphVMetrics {
+    GlyphVMetrics {
+        advance_height,
+        top_side_bearing,
+        vert_origin_y,
+    }
+}
+
+#[test]
+fn fallback_without_vertical_tables() {
+    // OS/2 の sTypoAscender 800, sTypoDescender -200 を使う．glyph 1 の yMax は 500．
+    let data = common::test_font();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(font.glyph_v_metrics(1).unwrap(), v_metrics(1000, 300, 800));
+    // 輪郭が無い glyph は yMax を 0 とする．
+    assert_eq!(font.glyph_v_metrics(0).unwrap(), v_metrics(1000, 800, 800));
+    assert_eq!(font.glyph_v_metrics(4).unwrap_err().field, Some("glyphId"));
+}
+
+#[test]
+fn vertical_tables() {
+    let (glyf, loca) = common::glyf_and_loca(0);
+    let builder = common::FontBuilder::new()
+        .table(b"OS/2", common::os2())
+        .table(b"glyf", glyf)
+        .table(b"head", common::head(0))
+        .table(b"loca", loca)
+        .table(b"maxp", common::maxp())
+        .table(b"vhea", vhea(2))
+        .table(b"vmtx", vmtx());
+
+    // 原点は tsb + yMax になる．
+    let data = builder.build();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(font.vhea().unwrap().numOfLongVerMetrics, 2);
+    assert_eq!(font.glyph_v_metrics(1).unwrap(), v_metrics(900, 300, 800));
+    assert_eq!(font.glyph_v_metrics(2).unwrap(), v_metrics(900, 50, 550));
+
+    // VORG があれば原点はその値になる．
+    let (glyf, loca) = common::glyf_and_loca(0);
+    let data = common::FontBuilder::new()
+        .table(b"OS/2", common::os2())
+        .table(b"VORG", vorg())
+        .table(b"glyf", glyf)
+        .table(b"head", common::head(0))
+        .table(b"loca", loca)
+        .table(b"maxp", common::maxp())
+        .build();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(font.glyph_v_metrics(1).unwrap(), v_metrics(1000, 400, 900));
+    assert_eq!(font.glyph_v_metrics(2).unwrap(), v_metrics(1000, 380, 880));
+
+    // vhea があるのに vmtx が壊れている場合はエラーにする．
+    let data = common::FontBuilder::new()
+        .table(b"OS/2", common::os2())
+        .table(b"head", common::head(0))
+        .table(b"maxp", common::maxp())
+        .table(b"vhea", vhea(4))
+        .table(b"vmtx", vmtx())
+        .build();
+    let font = Font::new(&data).unwrap();
+    assert!(font.vmtx().is_err());
+}
+
+#[test]
+fn without_glyf() {
+    // CFF のフォントのように glyf と loca が無くても，VORG と vmtx から求める．
+    let data = common::FontBuilder::new()
+        .table(b"OS/2", common::os2())
+        .table(b"VORG", vorg())
+        .table(b"head", common::head(0))
+        .table(b"maxp", common::maxp())
+        .table(b"vhea", vhea(2))
+        .table(b"vmtx", vmtx())
+        .build();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(font.glyph_v_metrics(1).unwrap(), v_metrics(900, 300, 900));
+    assert_eq!(font.glyph_v_metrics(2).unwrap(), v_metrics(900, 50, 880));
+    assert_eq!(font.glyph_v_metrics(4).unwrap_err().field, Some("glyphId"));
+
+    // VORG も無い場合は yMax を 0 として扱う．
+    let data = common::FontBuilder::new()
+        .table(b"OS/2", common::os2())
+        .table(b"head", common::head(0))
+        .table(b"maxp", common::maxp())
+        .table(b"vhea", vhea(2))
+        .table(b"vmtx", vmtx())
+        .build();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(font.glyph_v_metrics(1).unwrap(), v_metrics(900, 300, 300));
+    let data = common::FontBuilder::new()
+        .table(b"OS/2", common::os2())
+        .table(b"head", common::head(0))
+        .table(b"maxp", common::maxp())
+        .build();
+    let font = Font::new(&data).unwrap();
+    assert_eq!(font.glyph_v_metrics(1).unwrap(), v_metrics(1000, 800, 800));
+    assert_eq!(font.glyph_v_metrics(4).unwrap_err().field, Some("glyphId"));
+}